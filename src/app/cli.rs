@@ -0,0 +1,134 @@
+/// Sub-commands accepted on the command line. No argument runs the full pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Radarr + Sonarr pipelines (default).
+    Run,
+    /// Only retry queued uploads; `--all` also retries items flagged for manual review.
+    RetryUploads { include_flagged: bool },
+    /// Show how the scene name of one item is derived; `--json` for tooling.
+    Explain { target: ExplainTarget, json: bool },
+}
+
+/// Item looked up by `explain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExplainTarget {
+    /// Radarr movie id or title.
+    Movie(String),
+    /// Sonarr series id or title, and an episode.
+    Episode {
+        series: String,
+        season: u16,
+        episode: u16,
+    },
+}
+
+pub const USAGE: &str = "Usage: seedarr [run | retry-uploads [--all] | explain movie <id|title> [--json] | explain episode <series id|title> <SxxEyy> [--json]]";
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let args: Vec<String> = args.into_iter().collect();
+    let Some((cmd, rest)) = args.split_first() else {
+        return Ok(Command::Run);
+    };
+
+    match cmd.as_str() {
+        "run" if rest.is_empty() => Ok(Command::Run),
+        "retry-uploads" => {
+            let mut include_flagged = false;
+            for arg in rest {
+                match arg.as_str() {
+                    "--all" => include_flagged = true,
+                    other => return Err(format!("Unknown option for retry-uploads: {other}")),
+                }
+            }
+            Ok(Command::RetryUploads { include_flagged })
+        }
+        "explain" => parse_explain(rest),
+        other => Err(format!("Unknown command: {other}")),
+    }
+}
+
+fn parse_explain(args: &[String]) -> Result<Command, String> {
+    let mut json = false;
+    let mut words: Vec<&str> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown option for explain: {other}"))
+            }
+            other => words.push(other),
+        }
+    }
+
+    let target = match words.split_first() {
+        Some((&"movie", query)) if !query.is_empty() => ExplainTarget::Movie(query.join(" ")),
+        Some((&"episode", [series @ .., marker])) if !series.is_empty() => {
+            let (season, episode) = parse_episode_marker(marker)
+                .ok_or_else(|| format!("Expected an episode like S01E02, got '{marker}'"))?;
+            ExplainTarget::Episode {
+                series: series.join(" "),
+                season,
+                episode,
+            }
+        }
+        _ => return Err("explain needs 'movie <id|title>' or 'episode <series> <SxxEyy>'".into()),
+    };
+    Ok(Command::Explain { target, json })
+}
+
+/// "S01E02" (case-insensitive) -> (1, 2).
+fn parse_episode_marker(s: &str) -> Option<(u16, u16)> {
+    let upper = s.to_ascii_uppercase();
+    let (season, episode) = upper.strip_prefix('S')?.split_once('E')?;
+    Some((season.parse().ok()?, episode.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(parse(&[]), Ok(Command::Run));
+        assert_eq!(parse(&["run"]), Ok(Command::Run));
+        assert_eq!(
+            parse(&["retry-uploads"]),
+            Ok(Command::RetryUploads {
+                include_flagged: false
+            })
+        );
+        assert_eq!(
+            parse(&["retry-uploads", "--all"]),
+            Ok(Command::RetryUploads {
+                include_flagged: true
+            })
+        );
+        assert!(parse(&["retry-uploads", "--nope"]).is_err());
+        assert_eq!(
+            parse(&["explain", "movie", "Le", "Samouraï", "--json"]),
+            Ok(Command::Explain {
+                target: ExplainTarget::Movie("Le Samouraï".to_string()),
+                json: true
+            })
+        );
+        assert_eq!(
+            parse(&["explain", "episode", "The Expanse", "s02e05"]),
+            Ok(Command::Explain {
+                target: ExplainTarget::Episode {
+                    series: "The Expanse".to_string(),
+                    season: 2,
+                    episode: 5
+                },
+                json: false
+            })
+        );
+        assert!(parse(&["explain", "episode", "S01E02"]).is_err());
+        assert!(parse(&["explain", "episode", "Show", "Pilot"]).is_err());
+        assert!(parse(&["explain", "movie"]).is_err());
+        assert!(parse(&["explode"]).is_err());
+    }
+}
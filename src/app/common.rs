@@ -1,14 +1,194 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use tracing::Level;
 
+/// External programs run by the pipelines, built from the configuration (replaced by fakes in
+/// tests).
+pub struct Tools {
+    pub probe: Box<dyn crate::core::media::probe::MediaProbe>,
+    /// Writes the seed NFOs: the `mediainfo` text report whatever `[media] probe` is, else a
+    /// MediaInfo-style report of what `probe` found.
+    pub nfo: Box<dyn crate::core::media::probe::MediaProbe>,
+    pub torrent: Box<dyn crate::core::torrent::TorrentCreator>,
+}
+
+impl Tools {
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        Self {
+            probe: crate::core::media::probe::from_config(&config.media),
+            nfo: crate::core::media::probe::nfo_from_config(&config.media),
+            torrent: crate::core::torrent::from_config(&config.torrent),
+        }
+    }
+}
+
 pub fn init_logging(config: &crate::config::Config) {
     tracing_subscriber::fmt()
         .with_max_level(Level::from_str(&config.logs.level).unwrap_or(Level::INFO))
         .init();
 }
 
+/// Title used in release names, per `[media] title_strategy`. When the naming profile
+/// transliterates to ASCII and the title can't be fully spelled (kanji, hangul), the first of
+/// the localized, original and alternate titles that can is used instead.
+pub fn choose_title(
+    titles: &crate::core::naming::titles::TitleCandidates,
+    config: &crate::config::Config,
+) -> String {
+    use crate::core::naming::transliterate::to_ascii_exact;
+
+    let title = crate::core::naming::titles::choose(
+        titles,
+        config.media.effective_title_strategy(),
+        config.media.title_locale.as_deref(),
+    );
+    let profile = config.naming.active_profile().unwrap_or_default();
+    if profile.transliterate != crate::config::Transliteration::Ascii
+        || to_ascii_exact(&title).is_some()
+    {
+        return title;
+    }
+    let latin = std::iter::once(&titles.local)
+        .chain(titles.original.as_ref())
+        .chain(&titles.alternates)
+        .find(|t| !t.trim().is_empty() && to_ascii_exact(t).is_some());
+    match latin {
+        Some(latin) => {
+            tracing::warn!(
+                "Title '{}' has no ASCII spelling, using '{}' (transliterate = \"ascii\")",
+                title,
+                latin
+            );
+            latin.clone()
+        }
+        None => title,
+    }
+}
+
+/// Release name for a naming decision: "-NoTag" per `[media]`, shortened to the active naming
+/// profile's length limit.
+pub fn final_scene_name(
+    decision: &crate::core::naming::SceneDecision,
+    config: &crate::config::Config,
+) -> String {
+    let profile = config.naming.active_profile().unwrap_or_default();
+    crate::core::naming::finalize_scene_name(
+        decision,
+        config.media.append_no_tag_on_missing_group,
+        &profile,
+    )
+}
+
+/// [`final_scene_name`] with the final parts and the rules that changed them.
+pub fn final_name(
+    decision: &crate::core::naming::SceneDecision,
+    config: &crate::config::Config,
+) -> crate::core::naming::FinalName {
+    let profile = config.naming.active_profile().unwrap_or_default();
+    crate::core::naming::finalize(
+        decision,
+        config.media.append_no_tag_on_missing_group,
+        &profile,
+    )
+}
+
+/// Fallback names for a release whose seed name is owned by other files: the name with the
+/// alternative title (`alt`) and the name with the TMDB/TVDB id, both finished like
+/// [`final_scene_name`].
+pub fn disambiguation(
+    decision: &crate::core::naming::SceneDecision,
+    alt: Option<&crate::core::naming::SceneDecision>,
+    tmdb_id: Option<i64>,
+    tvdb_id: Option<i64>,
+    config: &crate::config::Config,
+) -> crate::core::fs::collision::Disambiguation {
+    let mut names = crate::core::fs::collision::Disambiguation {
+        tmdb_id,
+        tvdb_id,
+        alt_name: alt.map(|alt| final_scene_name(alt, config)),
+        id_name: None,
+    };
+    let profile = config.naming.active_profile().unwrap_or_default();
+    names.id_name = names.id_tag().map(|tag| {
+        crate::core::naming::finalize_scene_name_tagged(
+            decision,
+            config.media.append_no_tag_on_missing_group,
+            &profile,
+            &tag,
+        )
+    });
+    names
+}
+
+/// Run the active naming profile's rules on a release name. Warnings are logged; errors are
+/// reported and return false, so nothing is linked, created or uploaded for that name.
+pub fn validate_release_name(name: &str, config: &crate::config::Config) -> bool {
+    let profile = config.naming.active_profile().unwrap_or_default();
+    let report = crate::core::naming::rules::check(name, &profile);
+    for w in report.warnings() {
+        tracing::warn!("Scene name '{}': {}", name, w);
+    }
+    if report.has_errors() {
+        tracing::error!(
+            "Scene name '{}' failed validation (profile '{}'), skipping:\n{}",
+            name,
+            config.naming.profile,
+            report
+        );
+        return false;
+    }
+    true
+}
+
+/// Validate `name`, then claim its seed folder under `seed_root`: a name that fails its rules
+/// reserves nothing, and a name renamed by `on_collision` is validated again. None when the
+/// item is skipped.
+pub fn claim_seed_name(
+    seeds: &crate::core::fs::collision::SeedRegistry,
+    seed_root: &str,
+    name: &str,
+    sources: &[PathBuf],
+    alt: &crate::core::fs::collision::Disambiguation,
+    config: &crate::config::Config,
+) -> Option<String> {
+    if !validate_release_name(name, config) {
+        return None;
+    }
+    match seeds.claim(Path::new(seed_root), name, sources, alt) {
+        Ok(claimed) if claimed == name => Some(claimed),
+        Ok(claimed) => validate_release_name(&claimed, config).then_some(claimed),
+        Err(e) => {
+            tracing::error!("Skipping '{}': {}", name, e);
+            None
+        }
+    }
+}
+
+/// After an export relinked stale files, set the seed dir's torrent aside and forget its
+/// uploads so the torrent is rebuilt and uploaded again. False when the item must be skipped.
+pub fn reset_relinked_seed(
+    seed_dir: &Path,
+    scene_name: &str,
+    config: &crate::config::Config,
+    upload_service: &crate::core::upload::UploadService,
+) -> bool {
+    match crate::core::torrent::set_stale_torrent_aside(seed_dir, scene_name, config) {
+        Ok(torrent) => {
+            upload_service.forget_release(scene_name, &torrent);
+            true
+        }
+        Err(e) => {
+            tracing::error!(
+                "Skipping '{}': failed to move its stale torrent aside: {}",
+                scene_name,
+                e
+            );
+            false
+        }
+    }
+}
+
 pub fn ensure_seed_path(config: &crate::config::Config) -> Result<(), String> {
     let Some(seed_root) = &config.media.seed_path else {
         return Ok(());
@@ -60,3 +240,41 @@ pub fn apply_resolution_fallback(
         None
     };
 }
+
+/// Log one line per tracker for an upload; an empty list means the upload service is off.
+pub fn log_upload_outcomes(scene_name: &str, outcomes: &[crate::core::upload::TrackerOutcome]) {
+    use crate::core::upload::UploadOutcome;
+
+    if outcomes.is_empty() {
+        tracing::info!(
+            "Upload service disabled: skipping upload for '{}'",
+            scene_name
+        );
+        return;
+    }
+    for o in outcomes {
+        match &o.outcome {
+            UploadOutcome::Uploaded(_) => {
+                tracing::info!("Uploaded torrent for '{}' to {}", scene_name, o.tracker)
+            }
+            UploadOutcome::Duplicate { existing } => tracing::info!(
+                "Upload of '{}' to {} skipped: duplicate of '{}'",
+                scene_name,
+                o.tracker,
+                existing
+            ),
+            UploadOutcome::Skipped { reason } => tracing::info!(
+                "Upload of '{}' to {} skipped: {}",
+                scene_name,
+                o.tracker,
+                reason
+            ),
+            UploadOutcome::Failed { error } => tracing::error!(
+                "Failed to upload torrent for '{}' to {}: {}",
+                scene_name,
+                o.tracker,
+                error
+            ),
+        }
+    }
+}
@@ -0,0 +1,369 @@
+//! Stand-ins for the services and programs the pipelines talk to: an HTTP server for the
+//! Radarr/Sonarr APIs and the tracker, a media probe fed with MediaInfo fixtures, a torrent
+//! creator that lists the files it was given, and a throwaway directory tree.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::core::media::mediainfo::parse_mediainfo_json;
+use crate::core::media::model::MediaInfo;
+use crate::core::media::probe::MediaProbe;
+use crate::core::torrent::TorrentCreator;
+use crate::utils::Error;
+
+/// A request received by a [`FakeServer`].
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    /// Path and query string ("/api/v3/episode?seriesId=10").
+    pub target: String,
+    /// Lower-cased names.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or_default()
+    }
+
+    /// Fields of a `multipart/form-data` body by name.
+    pub fn multipart(&self) -> HashMap<String, Vec<u8>> {
+        let Some(boundary) = self
+            .header("content-type")
+            .and_then(|ct| ct.split("boundary=").nth(1))
+        else {
+            return HashMap::new();
+        };
+        let delimiter = format!("--{}", boundary.trim_matches('"'));
+        split(&self.body, delimiter.as_bytes())
+            .into_iter()
+            .filter_map(|part| {
+                let start = find(part, b"\r\n\r\n")?;
+                let head = String::from_utf8_lossy(&part[..start]);
+                let name = head.split("name=\"").nth(1)?.split('"').next()?.to_string();
+                let content = &part[start + 4..];
+                let content = content.strip_suffix(b"\r\n").unwrap_or(content);
+                Some((name, content.to_vec()))
+            })
+            .collect()
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn split<'a>(mut data: &'a [u8], delimiter: &[u8]) -> Vec<&'a [u8]> {
+    let mut parts = Vec::new();
+    while let Some(i) = find(data, delimiter) {
+        parts.push(&data[..i]);
+        data = &data[i + delimiter.len()..];
+    }
+    parts
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn json(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            content_type: "application/json",
+            body: body.into().into_bytes(),
+        }
+    }
+
+    pub fn bytes(body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status: 200,
+            content_type: "application/octet-stream",
+            body: body.into(),
+        }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            content_type: "text/plain",
+            body: format!("fake server: HTTP {status}").into_bytes(),
+        }
+    }
+}
+
+type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// HTTP/1.1 server on a local port answering every request with `handler`, one request per
+/// connection. Requests are recorded before they are answered.
+pub struct FakeServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl FakeServer {
+    pub async fn start(handler: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let log = requests.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let (handler, log) = (handler.clone(), log.clone());
+                tokio::spawn(async move {
+                    if let Err(e) = serve(stream, handler.as_ref(), &log).await {
+                        eprintln!("fake server: {e}");
+                    }
+                });
+            }
+        });
+        Self {
+            url,
+            requests,
+            task,
+        }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Requests to `path` (query string ignored).
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<Request> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method && r.path() == path)
+            .collect()
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn serve(
+    stream: TcpStream,
+    handler: &Handler,
+    log: &Mutex<Vec<Request>>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let mut words = line.split_whitespace();
+    let method = words.next().unwrap_or_default().to_string();
+    let target = words.next().unwrap_or_default().to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line).await?;
+        let l = line.trim_end();
+        if l.is_empty() {
+            break;
+        }
+        if let Some((k, v)) = l.split_once(':') {
+            headers.push((k.trim().to_ascii_lowercase(), v.trim().to_string()));
+        }
+    }
+    let mut req = Request {
+        method,
+        target,
+        headers,
+        body: Vec::new(),
+    };
+    req.body = if req
+        .header("transfer-encoding")
+        .is_some_and(|v| v.eq_ignore_ascii_case("chunked"))
+    {
+        read_chunked(&mut reader).await?
+    } else {
+        let len = req
+            .header("content-length")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; len];
+        reader.read_exact(&mut body).await?;
+        body
+    };
+
+    let resp = handler(&req);
+    log.lock().unwrap().push(req);
+
+    let mut stream = reader.into_inner();
+    let head = format!(
+        "HTTP/1.1 {} Fake\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        resp.status,
+        resp.content_type,
+        resp.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&resp.body).await?;
+    stream.shutdown().await
+}
+
+async fn read_chunked(reader: &mut BufReader<TcpStream>) -> std::io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line).await?;
+        let size = usize::from_str_radix(line.trim().split(';').next().unwrap_or("0"), 16)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        if size == 0 {
+            // Trailers, then the final empty line
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+                    return Ok(body);
+                }
+            }
+        }
+        let mut chunk = vec![0; size + 2];
+        reader.read_exact(&mut chunk).await?;
+        body.extend_from_slice(&chunk[..size]);
+    }
+}
+
+/// Media probe answering from MediaInfo JSON fixtures, by file name.
+#[derive(Default, Clone)]
+pub struct FakeProbe {
+    media: HashMap<String, MediaInfo>,
+}
+
+impl FakeProbe {
+    /// Probe files named `file_name` as the `mediainfo --Output=JSON` report `json`.
+    pub fn with(mut self, file_name: &str, json: &str) -> Self {
+        let json = serde_json::from_str(json).expect("invalid MediaInfo fixture");
+        let media = parse_mediainfo_json(&json).expect("MediaInfo fixture has no tracks");
+        self.media.insert(file_name.to_string(), media);
+        self
+    }
+
+    fn lookup(&self, path: &str) -> Option<&MediaInfo> {
+        let name = Path::new(path).file_name()?.to_str()?;
+        self.media.get(name)
+    }
+}
+
+impl MediaProbe for FakeProbe {
+    fn name(&self) -> &str {
+        "fake"
+    }
+
+    fn probe(&self, path: &str) -> Result<MediaInfo, Error> {
+        self.lookup(path)
+            .cloned()
+            .ok_or_else(|| Error::Other(format!("no fake media for '{path}'")))
+    }
+
+    fn text_report(&self, path: &str) -> Option<String> {
+        self.lookup(path)
+            .map(|_| format!("General\nComplete name : {path}\n"))
+    }
+}
+
+/// Torrent creator writing the files it would hash (`<relative path> <size>`, symlinks
+/// followed) instead of a real torrent. Outputs are recorded in `created`.
+#[derive(Default)]
+pub struct FakeTorrent {
+    pub created: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl TorrentCreator for FakeTorrent {
+    fn name(&self) -> &str {
+        "fake"
+    }
+
+    fn create(&self, source: &Path, output: &Path) -> std::io::Result<()> {
+        let mut listing = String::from("fake torrent\n");
+        for file in list_files(source)? {
+            let size = std::fs::metadata(source.join(&file))?.len();
+            listing.push_str(&format!("{file} {size}\n"));
+        }
+        std::fs::write(output, listing)?;
+        self.created.lock().unwrap().push(output.to_path_buf());
+        Ok(())
+    }
+}
+
+/// Files under `dir`, relative and sorted.
+fn list_files(dir: &Path) -> std::io::Result<Vec<String>> {
+    let mut out = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(d) = stack.pop() {
+        for entry in std::fs::read_dir(&d)? {
+            let path = entry?.path();
+            if path.is_dir() && !path.is_symlink() {
+                stack.push(path);
+            } else {
+                let rel = path.strip_prefix(dir).unwrap_or(&path);
+                out.push(rel.to_string_lossy().to_string());
+            }
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+/// Directory removed when dropped.
+pub struct TempTree {
+    pub root: PathBuf,
+}
+
+impl TempTree {
+    pub fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!("seedarr-e2e-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        Self { root }
+    }
+
+    /// Create `rel` with `content`, and its parent directories.
+    pub fn file(&self, rel: &str, content: &[u8]) -> PathBuf {
+        let path = self.root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    /// Files under `rel` (relative to it, sorted), symlinks as `link -> target`.
+    pub fn listing(&self, rel: &str) -> Vec<String> {
+        let dir = self.root.join(rel);
+        let Ok(files) = list_files(&dir) else {
+            return Vec::new();
+        };
+        files
+            .into_iter()
+            .map(|f| match std::fs::read_link(dir.join(&f)) {
+                Ok(target) => format!("{f} -> {}", target.display()),
+                Err(_) => f,
+            })
+            .collect()
+    }
+}
+
+impl Drop for TempTree {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
@@ -0,0 +1,432 @@
+//! End-to-end pipeline tests: fake Radarr, Sonarr and Torrust APIs serving the libraries in
+//! `tests/fixtures/e2e`, a fake media probe and torrent creator, and a temporary directory
+//! holding the library, seed tree, watch dir and upload state. Each [`Env::run`] is one
+//! `seedarr run`.
+
+mod fakes;
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use fakes::{FakeProbe, FakeServer, FakeTorrent, Request, Response, TempTree};
+
+use crate::app::common::Tools;
+use crate::config::Config;
+use crate::core;
+use crate::utils::http::HttpClient;
+
+const MOVIE: &str = "Heat.1995.1080p.BluRay.DTS.5.1.x264-CtrlHD";
+const SEASON_PACK: &str = "The.Expanse.2015.S01.1080p.WEB-DL.EAC3.5.1.x264-NTb";
+const EPISODE: &str = "The.Expanse.S02E01.1080p.AMZN.WEB-DL.EAC3.5.1.x264-NTb";
+
+const RADARR_ROUTES: &[(&str, &str)] = &[("/api/v3/movie", "radarr/movie.json")];
+const SONARR_ROUTES: &[(&str, &str)] = &[
+    ("/api/v3/series", "sonarr/series.json"),
+    ("/api/v3/episode?seriesId=10", "sonarr/episode-10.json"),
+    (
+        "/api/v3/episodefile?seriesId=10",
+        "sonarr/episodefile-10.json",
+    ),
+];
+
+fn fixture(rel: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/e2e")
+        .join(rel);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+}
+
+/// *arr API answering GET requests from fixture files.
+fn arr_api(routes: &'static [(&'static str, &'static str)]) -> impl Fn(&Request) -> Response {
+    move |req| match routes.iter().find(|(target, _)| *target == req.target) {
+        Some((_, file)) if req.method == "GET" => Response::json(fixture(file)),
+        _ => Response::status(404),
+    }
+}
+
+/// Torrust API: uploads get ids 1, 2... (after `failures` failed ones) and tracker-issued
+/// torrents are served back (after `download_failures` failed downloads).
+fn tracker_api(
+    failures: Arc<AtomicUsize>,
+    download_failures: Arc<AtomicUsize>,
+) -> impl Fn(&Request) -> Response {
+    let uploads = AtomicUsize::new(0);
+    move |req| match (req.method.as_str(), req.path()) {
+        ("POST", "/api/v1/torrent/upload") => {
+            if failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Response::status(500);
+            }
+            let n = uploads.fetch_add(1, Ordering::SeqCst) + 1;
+            Response::json(format!(
+                r#"{{"data":{{"torrent_id":{n},"info_hash":"hash{n}","canonical_info_hash":"hash{n}"}}}}"#
+            ))
+        }
+        ("GET", path) => match path.strip_prefix("/api/v1/torrent/download/") {
+            Some(_)
+                if download_failures
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok() =>
+            {
+                Response::status(500)
+            }
+            Some(hash) => Response::bytes(format!("tracker torrent {hash}")),
+            None => Response::status(404),
+        },
+        _ => Response::status(404),
+    }
+}
+
+struct Env {
+    tree: TempTree,
+    radarr: FakeServer,
+    sonarr: FakeServer,
+    tracker: FakeServer,
+    /// Tracker uploads still to fail.
+    failures: Arc<AtomicUsize>,
+    /// Tracker torrent downloads still to fail.
+    download_failures: Arc<AtomicUsize>,
+    /// Torrents built so far, across runs.
+    torrents: Arc<Mutex<Vec<PathBuf>>>,
+    config: Config,
+    http: HttpClient,
+}
+
+impl Env {
+    async fn new(name: &str) -> Self {
+        Self::with_watch_dir(name, true).await
+    }
+
+    /// Without `watch_dir`, tracker torrents go next to the locally-built ones.
+    async fn with_watch_dir(name: &str, watch_dir: bool) -> Self {
+        let tree = TempTree::new(name);
+        tree.file(
+            "library/movies/Heat (1995)/Heat.1995.1080p.BluRay.mkv",
+            b"heat video",
+        );
+        for ep in [
+            "Season 01/The.Expanse.S01E01",
+            "Season 01/The.Expanse.S01E02",
+        ] {
+            tree.file(&format!("library/tv/The Expanse/{ep}.mkv"), b"expanse s01");
+        }
+        tree.file(
+            "library/tv/The Expanse/Season 02/The.Expanse.S02E01.mkv",
+            b"expanse s02",
+        );
+
+        let radarr = FakeServer::start(arr_api(RADARR_ROUTES)).await;
+        let sonarr = FakeServer::start(arr_api(SONARR_ROUTES)).await;
+        let failures = Arc::new(AtomicUsize::new(0));
+        let download_failures = Arc::new(AtomicUsize::new(0));
+        let tracker =
+            FakeServer::start(tracker_api(failures.clone(), download_failures.clone())).await;
+
+        let root = tree.root.display();
+        let watch = if watch_dir {
+            format!(r#"seed_torrent_dir = "{root}/watch""#)
+        } else {
+            String::new()
+        };
+        let toml = format!(
+            r#"
+[logs]
+level = "warn"
+enable_reqwest_logging = false
+
+[media]
+use_original_title = false
+seed_path = "{root}/seed"
+
+[torrent]
+announce_url = "http://tracker.invalid/announce"
+
+[http]
+timeout_secs = 5
+max_retries = 0
+
+[radarr]
+base_url = "{radarr}"
+api_key = "radarr-key"
+path_mappings = [{{ radarr_root = "/movies", local_root = "{root}/library/movies" }}]
+
+[sonarr]
+base_url = "{sonarr}"
+api_key = "sonarr-key"
+per_episode_for_incomplete_seasons = true
+path_mappings = [{{ sonarr_root = "/tv", local_root = "{root}/library/tv" }}]
+
+[upload]
+{watch}
+state_dir = "{root}/state"
+
+[upload.torrust]
+enable = true
+api_base = "{tracker}/api/v1"
+api_key = "tracker-key"
+"#,
+            radarr = radarr.url,
+            sonarr = sonarr.url,
+            tracker = tracker.url,
+        );
+        let config: Config = config::Config::builder()
+            .add_source(config::File::from_str(&toml, config::FileFormat::Toml))
+            .build()
+            .and_then(|c| c.try_deserialize())
+            .unwrap();
+        let http = HttpClient::from_config(&config.http).unwrap();
+
+        Self {
+            tree,
+            radarr,
+            sonarr,
+            tracker,
+            failures,
+            download_failures,
+            torrents: Arc::default(),
+            config,
+            http,
+        }
+    }
+
+    fn tools(&self) -> Tools {
+        let expanse = fixture("mediainfo/expanse.json");
+        let probe = FakeProbe::default()
+            .with(
+                "Heat.1995.1080p.BluRay.mkv",
+                &fixture("mediainfo/heat.json"),
+            )
+            .with("The.Expanse.S01E01.mkv", &expanse)
+            .with("The.Expanse.S01E02.mkv", &expanse)
+            .with("The.Expanse.S02E01.mkv", &expanse);
+        Tools {
+            nfo: Box::new(probe.clone()),
+            probe: Box::new(probe),
+            torrent: Box::new(FakeTorrent {
+                created: self.torrents.clone(),
+            }),
+        }
+    }
+
+    fn upload_service(&self) -> core::upload::UploadService {
+        core::upload::UploadService::from_config(&self.config, &self.http).unwrap()
+    }
+
+    /// One `seedarr run`.
+    async fn run(&self) {
+        crate::app::run(
+            &self.config,
+            &self.http,
+            &self.tools(),
+            &self.upload_service(),
+        )
+        .await
+        .unwrap();
+    }
+
+    fn path(&self, rel: &str) -> PathBuf {
+        self.tree.root.join(rel)
+    }
+
+    /// Multipart fields of the upload attempts.
+    fn uploads(&self) -> Vec<std::collections::HashMap<String, Vec<u8>>> {
+        self.tracker
+            .requests_to("POST", "/api/v1/torrent/upload")
+            .iter()
+            .map(Request::multipart)
+            .collect()
+    }
+
+    /// Titles of the upload attempts, sorted.
+    fn upload_titles(&self) -> Vec<String> {
+        let mut titles: Vec<String> = self
+            .uploads()
+            .iter()
+            .map(|f| String::from_utf8_lossy(&f["title"]).to_string())
+            .collect();
+        titles.sort();
+        titles
+    }
+}
+
+#[tokio::test]
+async fn movie_is_seeded_built_and_uploaded() {
+    let env = Env::new("movie").await;
+    env.run().await;
+
+    let list = env.radarr.requests_to("GET", "/api/v3/movie");
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0].header("x-api-key"), Some("radarr-key"));
+
+    // Ronin has no file and Collateral is outside the path mappings
+    assert_eq!(
+        env.tree.listing(&format!("seed/{MOVIE}")),
+        [
+            format!("{MOVIE}.mkv -> ../../library/movies/Heat (1995)/Heat.1995.1080p.BluRay.mkv"),
+            format!("{MOVIE}.nfo"),
+            format!("{MOVIE}.torrent"),
+        ]
+    );
+    let nfo = std::fs::read_to_string(env.path(&format!("seed/{MOVIE}/{MOVIE}.nfo"))).unwrap();
+    assert!(nfo.contains("Heat.1995.1080p.BluRay.mkv"));
+    let torrent_path = env.path(&format!("seed/{MOVIE}/{MOVIE}.torrent"));
+    let torrent = std::fs::read(&torrent_path).unwrap();
+    assert!(String::from_utf8_lossy(&torrent).contains(&format!("{MOVIE}.mkv 10\n")));
+
+    let upload = env
+        .uploads()
+        .into_iter()
+        .find(|f| f["title"] == MOVIE.as_bytes())
+        .unwrap();
+    assert_eq!(upload["category"], b"movies");
+    assert_eq!(upload["torrent"], torrent);
+    let description = String::from_utf8_lossy(&upload["description"]);
+    assert!(description.contains("Heat") && description.contains("1995"));
+    assert!(env
+        .tracker
+        .requests()
+        .iter()
+        .all(|r| r.header("authorization") == Some("ApiKey tracker-key")));
+
+    // The tracker-issued torrent lands in the watch dir
+    let issued = std::fs::read(env.path(&format!("watch/{MOVIE}.torrust.torrent"))).unwrap();
+    assert!(issued.starts_with(b"tracker torrent hash"));
+}
+
+#[tokio::test]
+async fn tracker_torrent_replaces_the_local_one_without_a_watch_dir() {
+    let env = Env::with_watch_dir("no-watch", false).await;
+    env.run().await;
+
+    // A client watching the seed folder only finds the tracker's torrent
+    assert_eq!(
+        env.tree.listing(&format!("seed/{MOVIE}")),
+        [
+            format!("{MOVIE}.mkv -> ../../library/movies/Heat (1995)/Heat.1995.1080p.BluRay.mkv"),
+            format!("{MOVIE}.nfo"),
+            format!("{MOVIE}.torrent.local"),
+            format!("{MOVIE}.torrust.torrent"),
+        ]
+    );
+
+    // Reruns neither rebuild the local torrent nor bring it back
+    let built = env.torrents.lock().unwrap().len();
+    env.run().await;
+    assert_eq!(env.torrents.lock().unwrap().len(), built);
+    assert!(!env.path(&format!("seed/{MOVIE}/{MOVIE}.torrent")).exists());
+}
+
+#[tokio::test]
+async fn series_become_season_packs_and_episodes() {
+    let env = Env::new("series").await;
+    env.run().await;
+
+    let targets: Vec<String> = env
+        .sonarr
+        .requests()
+        .into_iter()
+        .map(|r| r.target)
+        .collect();
+    assert_eq!(
+        targets,
+        [
+            "/api/v3/series",
+            "/api/v3/episode?seriesId=10",
+            "/api/v3/episodefile?seriesId=10"
+        ]
+    );
+
+    // Season 1 is complete: one pack keeping the episode file names
+    assert_eq!(
+        env.tree.listing(&format!("seed/{SEASON_PACK}")),
+        [
+            format!("{SEASON_PACK}.nfo"),
+            format!("{SEASON_PACK}.torrent"),
+            "The.Expanse.S01E01.mkv -> ../../library/tv/The Expanse/Season 01/The.Expanse.S01E01.mkv".to_string(),
+            "The.Expanse.S01E02.mkv -> ../../library/tv/The Expanse/Season 01/The.Expanse.S01E02.mkv".to_string(),
+        ]
+    );
+    // Season 2 misses an episode: the one on disk is released alone
+    assert_eq!(
+        env.tree.listing(&format!("seed/{EPISODE}")),
+        [
+            format!(
+                "{EPISODE}.mkv -> ../../library/tv/The Expanse/Season 02/The.Expanse.S02E01.mkv"
+            ),
+            format!("{EPISODE}.nfo"),
+            format!("{EPISODE}.torrent"),
+        ]
+    );
+
+    let mut seeded: Vec<String> = std::fs::read_dir(env.path("seed"))
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    seeded.sort();
+    assert_eq!(seeded, [MOVIE, SEASON_PACK, EPISODE]);
+
+    assert_eq!(env.upload_titles(), [MOVIE, SEASON_PACK, EPISODE]);
+    for upload in env.uploads() {
+        if upload["title"] != MOVIE.as_bytes() {
+            assert_eq!(upload["category"], b"series");
+        }
+    }
+}
+
+#[tokio::test]
+async fn reruns_leave_done_releases_alone() {
+    let env = Env::new("rerun").await;
+    env.run().await;
+    let built = env.torrents.lock().unwrap().len();
+    let uploaded = env.uploads().len();
+    assert_eq!((built, uploaded), (3, 3));
+
+    env.run().await;
+    assert_eq!(env.torrents.lock().unwrap().len(), built);
+    assert_eq!(env.uploads().len(), uploaded);
+}
+
+#[tokio::test]
+async fn failed_uploads_are_queued_and_retried_next_run() {
+    let env = Env::new("retry").await;
+    env.failures.store(1, Ordering::SeqCst);
+    env.run().await;
+
+    // The first upload (the movie) was rejected and queued; the others went through
+    let queue = std::fs::read_to_string(env.path("state/upload-queue.json")).unwrap();
+    assert!(queue.contains(MOVIE));
+    assert!(!env.path(&format!("watch/{MOVIE}.torrust.torrent")).exists());
+    assert!(env
+        .path(&format!("watch/{EPISODE}.torrust.torrent"))
+        .exists());
+
+    // The next run retries it first
+    env.run().await;
+    assert_eq!(env.upload_titles(), [MOVIE, MOVIE, SEASON_PACK, EPISODE]);
+    let queue = std::fs::read_to_string(env.path("state/upload-queue.json")).unwrap_or_default();
+    assert!(!queue.contains(MOVIE));
+    assert!(env.path(&format!("watch/{MOVIE}.torrust.torrent")).exists());
+}
+
+#[tokio::test]
+async fn failed_torrent_fetches_are_retried_next_run() {
+    let env = Env::new("fetch-retry").await;
+    env.download_failures.store(1, Ordering::SeqCst);
+    env.run().await;
+
+    // The movie is on the tracker but its torrent couldn't be fetched: not done yet
+    assert_eq!(env.upload_titles(), [MOVIE, SEASON_PACK, EPISODE]);
+    assert!(!env.path(&format!("watch/{MOVIE}.torrust.torrent")).exists());
+    let status = std::fs::read_to_string(env.path("state/upload-status.json")).unwrap();
+    assert!(status.contains("torrent_pending"));
+
+    // The next run fetches it without uploading again
+    env.run().await;
+    assert_eq!(env.uploads().len(), 3);
+    assert!(env.path(&format!("watch/{MOVIE}.torrust.torrent")).exists());
+    let status = std::fs::read_to_string(env.path("state/upload-status.json")).unwrap();
+    assert!(!status.contains("torrent_pending"));
+}
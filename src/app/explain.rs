@@ -0,0 +1,359 @@
+use std::path::{Path, PathBuf};
+
+use crate::app::cli::ExplainTarget;
+use crate::core;
+use crate::core::media::model::MediaInfo;
+use crate::core::naming::explain::{Explanation, Sources};
+use crate::core::naming::SceneDecision;
+use crate::utils::http::HttpClient;
+use crate::utils::Error;
+
+/// Print how the scene name of `target` is derived, as text or JSON. Nothing is linked,
+/// created or uploaded.
+pub async fn run(
+    target: &ExplainTarget,
+    json: bool,
+    config: &crate::config::Config,
+    http: &HttpClient,
+) -> Result<(), Error> {
+    let explanation = match target {
+        ExplainTarget::Movie(query) => explain_movie(query, config, http).await?,
+        ExplainTarget::Episode {
+            series,
+            season,
+            episode,
+        } => explain_episode(series, *season, *episode, config, http).await?,
+    };
+
+    if json {
+        let out = serde_json::to_string_pretty(&explanation)
+            .map_err(|e| Error::Other(format!("Failed to serialize explanation: {e}")))?;
+        println!("{out}");
+    } else {
+        print!("{explanation}");
+    }
+    Ok(())
+}
+
+async fn explain_movie(
+    query: &str,
+    config: &crate::config::Config,
+    http: &HttpClient,
+) -> Result<Explanation, Error> {
+    let radarr_config = crate::app::radarr::build_radarr_config(config, http);
+    let movies = crate::app::radarr::fetch_radarr_movies(&radarr_config, false, http)
+        .await
+        .map_err(|e| Error::Other(format!("Failed to list movies from Radarr: {e}")))?;
+    let movie = pick(&movies, query, "movie", |m| {
+        let title = m.title.clone().flatten().unwrap_or_default();
+        let names = [Some(title.clone()), m.original_title.clone().flatten()];
+        let label = format!("{title} ({})", m.year.unwrap_or_default());
+        (
+            m.id.map(i64::from),
+            names.into_iter().flatten().collect(),
+            label,
+        )
+    })?;
+
+    let scene_name = crate::app::radarr::extract_scene_name(movie);
+    let titles = crate::app::radarr::title_candidates(movie);
+    let hints = crate::app::radarr::movie_hints(movie, &titles, config);
+    let (raw_path, local_path) = crate::app::radarr::translate_movie_path(movie, config)
+        .ok_or_else(|| Error::Other("the movie file is not path-mapped".to_string()))?;
+
+    let media = probe(&local_path, config);
+    let mut tech = media
+        .as_ref()
+        .map(MediaInfo::technical_info)
+        .unwrap_or_default();
+    crate::app::common::apply_resolution_fallback(&mut tech, hints.quality.as_deref());
+
+    let profile = config.naming.active_profile().unwrap_or_default();
+    let validation = core::naming::rules::check(&scene_name, &profile);
+    let decision =
+        core::naming::propose_scene_name(Some(&scene_name), &hints, &tech, Some(&validation));
+
+    let item = format!("{} ({})", hints.title, movie.year.unwrap_or_default());
+    let sources = Sources {
+        arr: "Radarr",
+        original: Some(scene_name.as_str()),
+        quality: hints.quality.as_deref(),
+        title_rule: title_rule(config),
+        media: media.as_ref(),
+    };
+    let mut explanation = build(item, raw_path, &decision, &sources, config);
+    let alt = crate::app::radarr::disambiguation(
+        movie,
+        &titles,
+        &hints,
+        &scene_name,
+        &tech,
+        &decision,
+        config,
+    );
+    resolve_collision(&mut explanation, &[local_path], &alt, config);
+    Ok(explanation)
+}
+
+async fn explain_episode(
+    query: &str,
+    season: u16,
+    episode: u16,
+    config: &crate::config::Config,
+    http: &HttpClient,
+) -> Result<Explanation, Error> {
+    let Some(sonarr_cfg) = &config.sonarr else {
+        return Err(Error::Other("Sonarr is not configured".to_string()));
+    };
+    let client = core::sonarr::SonarrClient::new(
+        sonarr_cfg.base_url.clone(),
+        sonarr_cfg.api_key.clone(),
+        http.clone(),
+    );
+
+    let series_list = client.list_series().await?;
+    let series = pick(&series_list, query, "series", |s| {
+        let mut names = vec![s.title.clone()];
+        names.extend(s.alternate_titles.iter().map(|t| t.title.clone()));
+        let label = format!("{} ({})", s.title, s.year.unwrap_or_default());
+        (Some(s.id), names, label)
+    })?;
+
+    let episodes = client.list_episodes(series.id).await?;
+    let wanted = episodes
+        .iter()
+        .find(|e| e.season_number == i32::from(season) && e.episode_number == i32::from(episode))
+        .ok_or_else(|| {
+            Error::Other(format!(
+                "'{}' has no episode S{season:02}E{episode:02}",
+                series.title
+            ))
+        })?
+        .id;
+    let files = client.list_episode_files(series.id).await?;
+    let epf = files
+        .iter()
+        .find(|f| f.episode_ids.contains(&wanted))
+        .ok_or_else(|| {
+            Error::Other(format!(
+                "'{}' S{season:02}E{episode:02} has no file",
+                series.title
+            ))
+        })?;
+    let notes = season_release_notes(&episodes, season, sonarr_cfg);
+    let episode_by_id = episodes.into_iter().map(|e| (e.id, e)).collect();
+
+    let info = crate::app::sonarr::episode_file_info(series, epf, &episode_by_id, config);
+    let local_path = crate::app::sonarr::translate_episode_path(epf, config)
+        .ok_or_else(|| Error::Other("the episode file is not path-mapped".to_string()))?;
+
+    let media = probe(&local_path, config);
+    let mut tech = media
+        .as_ref()
+        .map(MediaInfo::technical_info)
+        .unwrap_or_default();
+    crate::app::common::apply_resolution_fallback(&mut tech, info.hints.quality.as_deref());
+
+    let original = epf.scene_name.as_deref();
+    let decision = core::naming::propose_episode_scene_name(original, &info.hints, &tech);
+
+    let item = format!(
+        "{} S{season:02}E{episode:02}{}",
+        info.hints.series_title,
+        info.episode_title
+            .as_deref()
+            .map(|t| format!(" - {t}"))
+            .unwrap_or_default()
+    );
+    let sources = Sources {
+        arr: "Sonarr",
+        original,
+        quality: info.hints.quality.as_deref(),
+        title_rule: title_rule(config),
+        media: media.as_ref(),
+    };
+    let mut explanation = build(item, epf.path.clone(), &decision, &sources, config);
+    let alt = crate::app::sonarr::disambiguation(series, &decision, config);
+    resolve_collision(&mut explanation, &[local_path], &alt, config);
+    explanation.notes.extend(notes);
+    Ok(explanation)
+}
+
+/// How the Sonarr pipeline releases `season`, when not as single episodes: season and
+/// INTEGRALE packs are named separately, and some episodes aren't released at all.
+fn season_release_notes(
+    episodes: &[core::sonarr::EpisodeResource],
+    season: u16,
+    cfg: &crate::config::SonarrConfig,
+) -> Vec<String> {
+    if season == 0 {
+        return vec!["specials (season 0) are not released by the Sonarr pipeline".to_string()];
+    }
+    let complete = |e: &&core::sonarr::EpisodeResource| !e.monitored || e.has_file;
+    let season_complete = episodes
+        .iter()
+        .filter(|e| e.season_number == i32::from(season))
+        .all(|e| complete(&e));
+    let series_complete = episodes
+        .iter()
+        .filter(|e| e.season_number > 0)
+        .all(|e| complete(&e));
+
+    let mut notes = Vec::new();
+    if season_complete || !cfg.only_complete_seasons {
+        notes.push(format!(
+            "S{season:02} is released as a season pack, under its own name, not episode by episode"
+        ));
+    } else if !cfg.per_episode_for_incomplete_seasons {
+        notes.push(format!(
+            "S{season:02} is incomplete and per_episode_for_incomplete_seasons = false: this episode is not released"
+        ));
+    }
+    if cfg.create_integrale_pack_if_complete && series_complete {
+        notes.push("the series is complete: it is also released as an INTEGRALE pack".to_string());
+    }
+    notes
+}
+
+/// Claim the final name against the seed folders on disk, as a run would (names claimed
+/// earlier in the same run can't be known here).
+fn resolve_collision(
+    explanation: &mut Explanation,
+    sources: &[PathBuf],
+    alt: &core::fs::collision::Disambiguation,
+    config: &crate::config::Config,
+) {
+    let Some(seed_root) = &config.media.seed_path else {
+        return;
+    };
+    let seeds = core::fs::collision::SeedRegistry::new(config.media.on_collision);
+    match seeds.claim(Path::new(seed_root), &explanation.name, sources, alt) {
+        Ok(name) if name != explanation.name => {
+            explanation.rules.push(format!(
+                "seed folder '{}' links other files, renamed to '{}' (on_collision)",
+                explanation.name, name
+            ));
+            let profile = config.naming.active_profile().unwrap_or_default();
+            explanation.violations = core::naming::rules::check(&name, &profile).violations;
+            explanation.name = name;
+        }
+        Ok(_) => {}
+        Err(e) => explanation.notes.push(format!("not released: {e}")),
+    }
+}
+
+fn build(
+    item: String,
+    path: String,
+    decision: &SceneDecision,
+    sources: &Sources,
+    config: &crate::config::Config,
+) -> Explanation {
+    let final_name = crate::app::common::final_name(decision, config);
+    let profile = config.naming.active_profile().unwrap_or_default();
+    let report = core::naming::rules::check(&final_name.name, &profile);
+    core::naming::explain::explain(item, path, decision, &final_name, &report, sources)
+}
+
+fn probe(path: &Path, config: &crate::config::Config) -> Option<MediaInfo> {
+    let path = path.to_string_lossy();
+    match core::media::probe::from_config(&config.media).probe(path.as_ref()) {
+        Ok(media) => Some(media),
+        Err(e) => {
+            tracing::warn!("Failed to probe '{}': {e}", path);
+            None
+        }
+    }
+}
+
+/// `title_strategy = "..."`, with the locale it uses.
+fn title_rule(config: &crate::config::Config) -> String {
+    let strategy = config.media.effective_title_strategy();
+    let mut rule = format!(
+        "title_strategy = {}",
+        serde_json::to_string(&strategy).unwrap_or_default()
+    );
+    if strategy == crate::config::TitleStrategy::PreferLocale {
+        if let Some(locale) = &config.media.title_locale {
+            rule.push_str(&format!(", title_locale = \"{locale}\""));
+        }
+    }
+    rule
+}
+
+/// The one item matching `query`: its id, else a title (exact, then partial, ignoring case).
+fn pick<'a, T>(
+    items: &'a [T],
+    query: &str,
+    what: &str,
+    describe: impl Fn(&T) -> (Option<i64>, Vec<String>, String),
+) -> Result<&'a T, Error> {
+    if let Ok(id) = query.parse::<i64>() {
+        if let Some(item) = items.iter().find(|i| describe(i).0 == Some(id)) {
+            return Ok(item);
+        }
+    }
+
+    let q = query.to_lowercase();
+    let exact: Vec<&T> = items
+        .iter()
+        .filter(|i| describe(i).1.iter().any(|n| n.to_lowercase() == q))
+        .collect();
+    let matches = if exact.is_empty() {
+        items
+            .iter()
+            .filter(|i| describe(i).1.iter().any(|n| n.to_lowercase().contains(&q)))
+            .collect()
+    } else {
+        exact
+    };
+
+    match matches.as_slice() {
+        [one] => Ok(one),
+        [] => Err(Error::Other(format!("No {what} matches '{query}'"))),
+        several => {
+            let labels: Vec<String> = several
+                .iter()
+                .map(|i| {
+                    let (id, _, label) = describe(i);
+                    format!("{label} [id {}]", id.unwrap_or_default())
+                })
+                .collect();
+            Err(Error::Other(format!(
+                "Several {what} match '{query}', use the id: {}",
+                labels.join(", ")
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_say_how_the_season_is_released() {
+        let episodes: Vec<core::sonarr::EpisodeResource> = serde_json::from_value(serde_json::json!([
+            { "id": 1, "seasonNumber": 1, "episodeNumber": 1, "hasFile": true, "monitored": true },
+            { "id": 2, "seasonNumber": 1, "episodeNumber": 2, "hasFile": true, "monitored": true },
+            { "id": 3, "seasonNumber": 2, "episodeNumber": 1, "hasFile": true, "monitored": true },
+            { "id": 4, "seasonNumber": 2, "episodeNumber": 2, "hasFile": false, "monitored": true },
+        ]))
+        .unwrap();
+        let mut cfg: crate::config::SonarrConfig = serde_json::from_value(serde_json::json!({
+            "base_url": "http://sonarr", "api_key": "key"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            season_release_notes(&episodes, 1, &cfg),
+            vec!["S01 is released as a season pack, under its own name, not episode by episode"]
+        );
+        assert_eq!(
+            season_release_notes(&episodes, 2, &cfg),
+            vec!["S02 is incomplete and per_episode_for_incomplete_seasons = false: this episode is not released"]
+        );
+        cfg.per_episode_for_incomplete_seasons = true;
+        assert!(season_release_notes(&episodes, 2, &cfg).is_empty());
+    }
+}
@@ -1,3 +1,37 @@
+pub mod cli;
 pub mod common;
+#[cfg(all(test, target_family = "unix"))]
+mod e2e;
+pub mod explain;
 pub mod radarr;
 pub mod sonarr;
+
+use crate::utils::http::HttpClient;
+use crate::utils::Error;
+
+/// One `seedarr run`: retry queued uploads, then the Radarr and Sonarr pipelines. Errors when
+/// Radarr can't be listed (Sonarr is then skipped) or the Sonarr pipeline fails.
+pub async fn run(
+    config: &crate::config::Config,
+    http: &HttpClient,
+    tools: &common::Tools,
+    upload_service: &crate::core::upload::UploadService,
+) -> Result<(), Error> {
+    // Previously failed uploads go first, so the pipeline doesn't race them.
+    upload_service.retry_queued(false).await;
+
+    let radarr_config = radarr::build_radarr_config(config, http);
+    let movies = radarr::fetch_radarr_movies(&radarr_config, config.test_mode, http)
+        .await
+        .map_err(|e| Error::Other(format!("Failed to list movies from Radarr: {e}")))?;
+
+    // Seed names are claimed across both pipelines so two items never share a seed dir
+    let seeds = crate::core::fs::collision::SeedRegistry::new(config.media.on_collision);
+    for movie in movies {
+        radarr::process_movie(movie, config, http, tools, upload_service, &seeds).await;
+    }
+
+    sonarr::run_sonarr_pipeline(config, http, tools, upload_service, &seeds)
+        .await
+        .map_err(|e| Error::Other(format!("Sonarr pipeline failed: {e}")))
+}
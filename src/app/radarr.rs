@@ -1,9 +1,14 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
 
 use crate::core;
+use crate::core::naming::titles::TitleCandidates;
+use crate::utils::http::{self, HttpClient, Retry};
 
 pub fn build_radarr_config(
     config: &crate::config::Config,
+    http: &HttpClient,
 ) -> radarr::apis::configuration::Configuration {
     radarr::apis::configuration::Configuration {
         base_path: config.radarr.base_url.clone(),
@@ -11,6 +16,7 @@ pub fn build_radarr_config(
             prefix: None,
             key: config.radarr.api_key.clone(),
         }),
+        client: http.inner().clone(),
         ..Default::default()
     }
 }
@@ -18,11 +24,20 @@ pub fn build_radarr_config(
 pub async fn fetch_radarr_movies(
     radarr_config: &radarr::apis::configuration::Configuration,
     test_mode: bool,
+    http: &HttpClient,
 ) -> Result<
     Vec<radarr::models::MovieResource>,
     radarr::apis::Error<radarr::apis::movie_api::ListMovieError>,
 > {
-    let iter = radarr::apis::movie_api::list_movie(radarr_config, None, None, None)
+    let iter = http
+        .retry(
+            &radarr_config.base_path,
+            || radarr::apis::movie_api::list_movie(radarr_config, None, None, None),
+            |res| match res {
+                Ok(_) => Retry::No,
+                Err(e) => classify_radarr_error(e),
+            },
+        )
         .await?
         .into_iter()
         .filter(|m| m.movie_file.as_ref().is_some());
@@ -38,22 +53,17 @@ pub async fn fetch_radarr_movies(
 pub async fn process_movie(
     movie: radarr::models::MovieResource,
     config: &crate::config::Config,
+    http: &HttpClient,
+    tools: &crate::app::common::Tools,
     upload_service: &core::upload::UploadService,
+    seeds: &core::fs::collision::SeedRegistry,
 ) {
     // Step 1. Validate or propose scene names
     let scene_name = extract_scene_name(&movie);
-    let title = choose_title(&movie, config);
-    let year = movie.year;
-    let quality = extract_quality_name(&movie);
-    let release_group = extract_release_group(&movie);
-
-    // Build hints for naming
-    let hints = core::naming::RadarrHints {
-        title: title.clone(),
-        year: year.and_then(|y| u16::try_from(y).ok()),
-        quality: quality.clone(),
-        release_group: release_group.clone(),
-    };
+    let titles = title_candidates(&movie);
+    let hints = movie_hints(&movie, &titles, config);
+    let title = hints.title.clone();
+    let quality = hints.quality.clone();
 
     // MediaInfo integration: only process files that are path-mapped in config
     let (raw_path, local_path) = match translate_movie_path(&movie, config) {
@@ -61,20 +71,19 @@ pub async fn process_movie(
         None => return,
     };
 
-    let mut tech = collect_technical_info(&raw_path, &local_path, config);
+    let mut tech = collect_technical_info(&raw_path, &local_path, tools);
     crate::app::common::apply_resolution_fallback(&mut tech, quality.as_deref());
 
     let cover_url = pick_cover_url(&movie);
 
-    let validation = core::naming::validate_scene_name(&scene_name);
+    let profile = config.naming.active_profile().unwrap_or_default();
+    let validation = core::naming::rules::check(&scene_name, &profile);
     let decision =
         core::naming::propose_scene_name(Some(&scene_name), &hints, &tech, Some(&validation));
 
-    // Optionally append "-NoTag" if no release group and config requests it
-    let mut final_scene_name = decision.chosen.clone();
-    if config.media.append_no_tag_on_missing_group && release_group.is_none() {
-        final_scene_name.push_str("-NoTag");
-    }
+    tracing::debug!(provenance = ?decision.provenance, "Rebuilt '{}'", decision.chosen);
+
+    let final_scene_name = crate::app::common::final_scene_name(&decision, config);
 
     println!("Title: {} | Year: {:?}", title, hints.year);
     println!("  Path: {}", raw_path);
@@ -96,16 +105,54 @@ pub async fn process_movie(
     // Step 2. Create seed symlink structure if configured
     if let Some(seed_root) = &config.media.seed_path {
         let src_video = local_path.as_path();
-        if let Err(e) = core::fs::export_seed_structure(
+
+        // Another movie may already own this name (same title/year, different file)
+        let alt = disambiguation(
+            &movie,
+            &titles,
+            &hints,
+            &scene_name,
+            &tech,
+            &decision,
+            config,
+        );
+        let Some(final_scene_name) = crate::app::common::claim_seed_name(
+            seeds,
+            seed_root,
+            &final_scene_name,
+            std::slice::from_ref(&local_path),
+            &alt,
+            config,
+        ) else {
+            return;
+        };
+
+        let relinked = match core::fs::export_seed_structure(
             PathBuf::from(seed_root).as_path(),
             &final_scene_name,
             src_video,
+            tools.nfo.as_ref(),
         ) {
-            tracing::error!(
-                "Failed to export seed structure for '{}': {}",
-                decision.chosen,
-                e
-            );
+            Ok(relinked) => relinked,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to export seed structure for '{}': {}",
+                    decision.chosen,
+                    e
+                );
+                return;
+            }
+        };
+        let seed_dir = PathBuf::from(seed_root).join(&final_scene_name);
+        if relinked
+            && !crate::app::common::reset_relinked_seed(
+                &seed_dir,
+                &final_scene_name,
+                config,
+                upload_service,
+            )
+        {
+            return;
         }
 
         // Step 3. Create .torrent for the seeded scene directory via intermodal (unless dry_run)
@@ -117,8 +164,8 @@ pub async fn process_movie(
             return;
         }
 
-        let seed_dir = PathBuf::from(seed_root).join(&final_scene_name);
         match core::torrent::create_torrent_for_seed_dir(
+            tools.torrent.as_ref(),
             seed_dir.as_path(),
             &final_scene_name,
             config,
@@ -126,27 +173,35 @@ pub async fn process_movie(
             Ok(torrent_path) => {
                 // Step 4. Upload torrent to private trackers (optional)
                 let overview = movie.overview.clone().flatten();
-                if let Err(e) = upload_service
+                let ids = core::upload::ExternalIds {
+                    tmdb_id: movie.tmdb_id.map(i64::from),
+                    imdb_id: movie.imdb_id.clone().flatten(),
+                    tvdb_id: None,
+                };
+                let meta = release_metadata(
+                    &movie,
+                    &local_path,
+                    &final_scene_name,
+                    config,
+                    http,
+                    tools,
+                    upload_service,
+                )
+                .await;
+                let outcomes = upload_service
                     .upload_movie_torrent(
-                        &title,
+                        &titles,
                         hints.year,
+                        &ids,
                         cover_url.as_deref(),
                         overview.as_deref(),
                         &final_scene_name,
                         &tech,
+                        &meta,
                         torrent_path,
                     )
-                    .await
-                {
-                    tracing::error!("Failed to upload torrent for '{}': {e}", final_scene_name);
-                } else if upload_service.is_enabled() {
-                    tracing::info!("Uploaded torrent for '{}'", final_scene_name);
-                } else {
-                    tracing::info!(
-                        "Upload service disabled: skipping upload for '{}'",
-                        final_scene_name
-                    );
-                }
+                    .await;
+                crate::app::common::log_upload_outcomes(&final_scene_name, &outcomes);
             }
             Err(e) => {
                 tracing::error!("Failed to create torrent for '{}': {}", decision.chosen, e);
@@ -155,7 +210,57 @@ pub async fn process_movie(
     }
 }
 
-fn extract_scene_name(movie: &radarr::models::MovieResource) -> String {
+/// Fallback seed names of a movie: rebuilt with its other title, and with its TMDB id.
+pub fn disambiguation(
+    movie: &radarr::models::MovieResource,
+    titles: &TitleCandidates,
+    hints: &core::naming::RadarrHints,
+    original: &str,
+    tech: &core::naming::TechnicalInfo,
+    decision: &core::naming::SceneDecision,
+    config: &crate::config::Config,
+) -> core::fs::collision::Disambiguation {
+    let alt_decision = titles.alternative(&hints.title).map(|t| {
+        let alt_hints = core::naming::RadarrHints {
+            title: t,
+            ..hints.clone()
+        };
+        core::naming::propose_scene_name(Some(original), &alt_hints, tech, None)
+    });
+    crate::app::common::disambiguation(
+        decision,
+        alt_decision.as_ref(),
+        movie.tmdb_id.map(i64::from),
+        None,
+        config,
+    )
+}
+
+/// Naming hints of a movie; the title follows `title_strategy`.
+pub fn movie_hints(
+    movie: &radarr::models::MovieResource,
+    titles: &TitleCandidates,
+    config: &crate::config::Config,
+) -> core::naming::RadarrHints {
+    core::naming::RadarrHints {
+        title: crate::app::common::choose_title(titles, config),
+        year: movie.year.and_then(|y| u16::try_from(y).ok()),
+        quality: extract_quality_name(movie),
+        source: extract_quality_source(movie),
+        release_group: extract_release_group(movie),
+        edition: extract_edition(movie),
+    }
+}
+
+fn classify_radarr_error<T>(e: &radarr::apis::Error<T>) -> Retry {
+    match e {
+        radarr::apis::Error::Reqwest(e) => http::classify_reqwest_error(e),
+        radarr::apis::Error::ResponseError(rc) => http::classify_status(rc.status, None),
+        _ => Retry::No,
+    }
+}
+
+pub fn extract_scene_name(movie: &radarr::models::MovieResource) -> String {
     movie
         .movie_file
         .as_deref()
@@ -163,43 +268,28 @@ fn extract_scene_name(movie: &radarr::models::MovieResource) -> String {
         .unwrap_or_else(|| "Unknown".to_string())
 }
 
-fn choose_title(movie: &radarr::models::MovieResource, config: &crate::config::Config) -> String {
-    let original_title = movie
-        .original_title
-        .clone()
-        .flatten()
-        .unwrap_or_else(|| "Unknown".to_string());
-    let local_title = movie
-        .title
-        .clone()
-        .flatten()
-        .unwrap_or_else(|| "Unknown".to_string());
-
-    if let Some(strategy) = &config.media.title_strategy {
-        match strategy {
-            crate::config::TitleStrategy::OriginalIfEnElseLocal => {
-                let is_en = movie
-                    .original_language
-                    .as_deref()
-                    .and_then(|ol| ol.name.clone().flatten())
-                    .map(|l| {
-                        let ll = l.to_ascii_lowercase();
-                        ll.starts_with("en") || ll.contains("english")
-                    })
-                    .unwrap_or(false);
-
-                if is_en {
-                    original_title
-                } else {
-                    local_title
-                }
-            }
-            crate::config::TitleStrategy::AlwaysLocal => local_title,
-        }
-    } else if config.media.use_original_title {
-        original_title
-    } else {
-        local_title
+/// Localized, original and alternate titles of a movie.
+pub fn title_candidates(movie: &radarr::models::MovieResource) -> TitleCandidates {
+    let local = movie.title.clone().flatten();
+    let original = movie.original_title.clone().flatten();
+    TitleCandidates {
+        local: local
+            .or_else(|| original.clone())
+            .unwrap_or_else(|| "Unknown".to_string()),
+        original,
+        original_language: movie
+            .original_language
+            .as_deref()
+            .and_then(|ol| ol.name.clone().flatten())
+            .and_then(|name| core::media::language::normalize(&name)),
+        alternates: movie
+            .alternate_titles
+            .clone()
+            .flatten()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|alt| alt.title.flatten())
+            .collect(),
     }
 }
 
@@ -217,6 +307,31 @@ fn extract_quality_name(movie: &radarr::models::MovieResource) -> Option<String>
         .flatten()
 }
 
+/// Source from the quality's `source` + `modifier` enums.
+fn extract_quality_source(movie: &radarr::models::MovieResource) -> Option<core::naming::Source> {
+    use core::naming::Source;
+    use radarr::models::{Modifier, QualitySource};
+
+    let quality = movie
+        .movie_file
+        .as_deref()?
+        .quality
+        .as_deref()?
+        .quality
+        .as_deref()?;
+    let remux = quality.modifier == Some(Modifier::Remux);
+    let source = match quality.source? {
+        QualitySource::Bluray | QualitySource::Dvd if remux => Source::Remux,
+        QualitySource::Bluray => Source::BluRay,
+        QualitySource::Webdl => Source::WebDl,
+        QualitySource::Webrip => Source::WebRip,
+        QualitySource::Tv => Source::Hdtv,
+        QualitySource::Dvd => Source::Dvd,
+        _ => return None,
+    };
+    Some(source)
+}
+
 fn extract_release_group(movie: &radarr::models::MovieResource) -> Option<String> {
     movie
         .movie_file
@@ -224,7 +339,15 @@ fn extract_release_group(movie: &radarr::models::MovieResource) -> Option<String
         .and_then(|mf| mf.release_group.clone().flatten())
 }
 
-fn translate_movie_path(
+fn extract_edition(movie: &radarr::models::MovieResource) -> Option<String> {
+    movie
+        .movie_file
+        .as_deref()
+        .and_then(|mf| mf.edition.clone().flatten())
+        .filter(|e| !e.trim().is_empty())
+}
+
+pub fn translate_movie_path(
     movie: &radarr::models::MovieResource,
     config: &crate::config::Config,
 ) -> Option<(String, PathBuf)> {
@@ -255,20 +378,138 @@ fn translate_movie_path(
 
 fn collect_technical_info(
     raw_path: &str,
-    local_path: &PathBuf,
-    config: &crate::config::Config,
+    local_path: &Path,
+    tools: &crate::app::common::Tools,
 ) -> core::naming::TechnicalInfo {
     tracing::debug!(
         "mediainfo path: radarr='{}' local='{}'",
         raw_path,
         local_path.display()
     );
-    core::media::mediainfo::collect_technical_info_with_cache(
+    core::media::probe::collect_technical_info(
+        tools.probe.as_ref(),
         local_path.to_string_lossy().as_ref(),
-        config.media.enable_mediainfo_cache,
     )
 }
 
+/// Description data: movie metadata and screenshots. Cast and MediaInfo text are only
+/// fetched when a template uses them.
+async fn release_metadata(
+    movie: &radarr::models::MovieResource,
+    local_path: &Path,
+    scene_name: &str,
+    config: &crate::config::Config,
+    http: &HttpClient,
+    tools: &crate::app::common::Tools,
+    upload_service: &core::upload::UploadService,
+) -> core::upload::description::ReleaseMetadata {
+    let mut meta = core::upload::description::ReleaseMetadata {
+        genres: movie.genres.clone().flatten().unwrap_or_default(),
+        runtime: movie
+            .runtime
+            .and_then(|r| u32::try_from(r).ok())
+            .filter(|r| *r > 0),
+        ratings: extract_ratings(movie),
+        ..Default::default()
+    };
+
+    if upload_service.needs_template_var("cast") {
+        if let Some(id) = movie.id {
+            match fetch_radarr_cast(config, http, id).await {
+                Ok(cast) => meta.cast = cast,
+                Err(e) => tracing::warn!("Failed to fetch cast for movie {id}: {e}"),
+            }
+        }
+    }
+    if upload_service.needs_template_var("mediainfo") {
+        meta.mediainfo = tools
+            .probe
+            .text_report(local_path.to_string_lossy().as_ref());
+    }
+    meta.screenshots = upload_service.screenshots(local_path, scene_name).await;
+    meta
+}
+
+/// Ratings are read from the raw JSON: `{ "imdb": { "value": 8.3, "votes": 700000 }, ... }`.
+fn extract_ratings(
+    movie: &radarr::models::MovieResource,
+) -> Vec<core::upload::description::Rating> {
+    let Ok(json) = serde_json::to_value(movie) else {
+        return Vec::new();
+    };
+    let Some(ratings) = json.get("ratings").and_then(|r| r.as_object()) else {
+        return Vec::new();
+    };
+    [
+        ("imdb", "imdb"),
+        ("tmdb", "tmdb"),
+        ("metacritic", "metacritic"),
+        ("rottenTomatoes", "rotten_tomatoes"),
+    ]
+    .into_iter()
+    .filter_map(|(key, source)| {
+        let r = ratings.get(key)?;
+        let value = r.get("value")?.as_f64().filter(|v| *v > 0.0)?;
+        Some(core::upload::description::Rating {
+            source: source.to_string(),
+            value,
+            votes: r.get("votes").and_then(|v| v.as_i64()),
+        })
+    })
+    .collect()
+}
+
+/// `GET /api/v3/credit` entry (cast and crew).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreditResource {
+    person_name: Option<String>,
+    character: Option<String>,
+    #[serde(rename = "type")]
+    credit_type: Option<String>,
+    #[serde(default)]
+    order: i32,
+}
+
+/// Number of top-billed actors exposed to templates.
+const MAX_CAST: usize = 10;
+
+async fn fetch_radarr_cast(
+    config: &crate::config::Config,
+    http: &HttpClient,
+    movie_id: i32,
+) -> Result<Vec<core::upload::description::CastMember>, crate::utils::Error> {
+    let url = format!(
+        "{}/api/v3/credit?movieId={movie_id}",
+        config.radarr.base_url.trim_end_matches('/')
+    );
+    let mut credits: Vec<CreditResource> = http
+        .send(&url, |c| {
+            c.get(&url).header("X-Api-Key", &config.radarr.api_key)
+        })
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    credits.retain(|c| {
+        c.credit_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("cast"))
+    });
+    credits.sort_by_key(|c| c.order);
+    Ok(credits
+        .into_iter()
+        .filter_map(|c| {
+            Some(core::upload::description::CastMember {
+                name: c.person_name?,
+                character: c.character.filter(|ch| !ch.is_empty()),
+            })
+        })
+        .take(MAX_CAST)
+        .collect())
+}
+
 fn pick_cover_url(movie: &radarr::models::MovieResource) -> Option<String> {
     // Prefer images[*].remoteUrl, fallback to images[*].url, then remotePoster
     movie
@@ -1,10 +1,14 @@
 use std::path::PathBuf;
 
 use crate::core;
+use crate::core::naming::titles::TitleCandidates;
 
 pub async fn run_sonarr_pipeline(
     config: &crate::config::Config,
+    http: &crate::utils::http::HttpClient,
+    tools: &crate::app::common::Tools,
     upload_service: &core::upload::UploadService,
+    seeds: &core::fs::collision::SeedRegistry,
 ) -> Result<(), crate::utils::Error> {
     let Some(sonarr_cfg) = &config.sonarr else {
         tracing::info!("Sonarr not configured: skipping series pipeline");
@@ -19,8 +23,11 @@ pub async fn run_sonarr_pipeline(
         sonarr_cfg.per_episode_for_incomplete_seasons
     );
 
-    let client =
-        core::sonarr::SonarrClient::new(sonarr_cfg.base_url.clone(), sonarr_cfg.api_key.clone());
+    let client = core::sonarr::SonarrClient::new(
+        sonarr_cfg.base_url.clone(),
+        sonarr_cfg.api_key.clone(),
+        http.clone(),
+    );
 
     let series_list = client.list_series().await?;
     tracing::info!("Fetched {} series from Sonarr", series_list.len());
@@ -32,11 +39,7 @@ pub async fn run_sonarr_pipeline(
     };
 
     for series in series_iter {
-        tracing::info!(
-            "Processing series: '{}' (id={})",
-            series.title,
-            series.id
-        );
+        tracing::info!("Processing series: '{}' (id={})", series.title, series.id);
         let kind = content_kind_from_series_type(series.series_type.as_deref());
         let cover_url = pick_sonarr_cover_url(&series);
 
@@ -191,7 +194,9 @@ pub async fn run_sonarr_pipeline(
                     cover_url.as_deref(),
                     kind,
                     config,
+                    tools,
                     upload_service,
+                    seeds,
                 )
                 .await;
             } else {
@@ -212,7 +217,9 @@ pub async fn run_sonarr_pipeline(
                 cover_url.as_deref(),
                 kind,
                 config,
+                tools,
                 upload_service,
+                seeds,
             )
             .await;
         } else if sonarr_cfg.create_integrale_pack_if_complete {
@@ -250,7 +257,9 @@ pub async fn run_sonarr_pipeline(
                             cover_url.as_deref(),
                             kind,
                             config,
+                            tools,
                             upload_service,
+                            seeds,
                         )
                         .await;
                     }
@@ -268,22 +277,68 @@ pub async fn run_sonarr_pipeline(
     Ok(())
 }
 
-async fn create_season_pack(
+/// Localized title and aliases of a series. Sonarr has no original title: the series title
+/// stands in for it when the original language is English, else the alias in the original
+/// language when one can be told apart (none: the series title is used).
+fn series_titles(series: &core::sonarr::SeriesResource) -> TitleCandidates {
+    let alternates: Vec<String> = series
+        .alternate_titles
+        .iter()
+        .filter(|t| t.season_number.is_none())
+        .map(|t| t.title.clone())
+        .collect();
+    let original_language = series
+        .original_language
+        .as_ref()
+        .and_then(|l| l.name.as_deref())
+        .and_then(core::media::language::normalize);
+    let original = original_language.as_deref().and_then(|lang| {
+        let lang = core::media::language::base(lang);
+        // English aliases are spellings of the series title, not its original
+        if lang == "en" {
+            return Some(series.title.clone());
+        }
+        alternates
+            .iter()
+            .find(|t| core::naming::titles::guess_language(t) == Some(lang))
+            .cloned()
+    });
+    TitleCandidates {
+        local: series.title.clone(),
+        original,
+        original_language,
+        alternates,
+    }
+}
+
+/// Series ids used to tell apart two series whose names collide.
+pub fn disambiguation(
     series: &core::sonarr::SeriesResource,
-    season: u16,
-    episode_files: &[core::sonarr::EpisodeFileResource],
-    cover_url: Option<&str>,
-    kind: core::upload::ContentKind,
+    decision: &core::naming::SceneDecision,
     config: &crate::config::Config,
-    upload_service: &core::upload::UploadService,
-) {
-    let Some(seed_root) = &config.media.seed_path else {
-        return;
-    };
+) -> core::fs::collision::Disambiguation {
+    crate::app::common::disambiguation(decision, None, series.tmdb_id, series.tvdb_id, config)
+}
+
+/// What a pack is built from: the local files of its episodes, and the quality, source and
+/// release group they agree on.
+struct PackFiles {
+    src_videos: Vec<PathBuf>,
+    quality: Option<String>,
+    /// Only a source every episode shares.
+    source: Option<core::naming::Source>,
+    /// The most common group when episodes come from several.
+    release_group: Option<String>,
+}
 
+fn pack_files(
+    episode_files: &[core::sonarr::EpisodeFileResource],
+    config: &crate::config::Config,
+) -> PackFiles {
     let mut unique_paths: std::collections::BTreeSet<PathBuf> = std::collections::BTreeSet::new();
     let mut qualities: Vec<String> = Vec::new();
-    let mut release_groups: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
+    let mut sources: Vec<Option<core::naming::Source>> = Vec::new();
+    let mut release_groups: Vec<String> = Vec::new();
 
     for epf in episode_files {
         if let Some(lp) = translate_episode_path(epf, config) {
@@ -292,50 +347,103 @@ async fn create_season_pack(
         if let Some(q) = extract_sonarr_quality_name(epf) {
             qualities.push(q);
         }
+        sources.push(extract_sonarr_quality_source(epf));
         if let Some(rg) = epf.release_group.as_ref() {
-            release_groups.insert(rg.clone());
+            release_groups.push(rg.clone());
         }
     }
 
-    let src_videos: Vec<PathBuf> = unique_paths.into_iter().collect();
+    let source = sources
+        .first()
+        .copied()
+        .flatten()
+        .filter(|s| sources.iter().all(|o| *o == Some(*s)));
+    PackFiles {
+        src_videos: unique_paths.into_iter().collect(),
+        quality: qualities.into_iter().next(),
+        source,
+        release_group: core::naming::groups::majority(release_groups.iter().map(String::as_str)),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn create_season_pack(
+    series: &core::sonarr::SeriesResource,
+    season: u16,
+    episode_files: &[core::sonarr::EpisodeFileResource],
+    cover_url: Option<&str>,
+    kind: core::upload::ContentKind,
+    config: &crate::config::Config,
+    tools: &crate::app::common::Tools,
+    upload_service: &core::upload::UploadService,
+    seeds: &core::fs::collision::SeedRegistry,
+) {
+    let Some(seed_root) = &config.media.seed_path else {
+        return;
+    };
+
+    let PackFiles {
+        src_videos,
+        quality,
+        source,
+        release_group,
+    } = pack_files(episode_files, config);
     if src_videos.is_empty() {
         return;
     }
 
-    let mut tech = core::media::mediainfo::collect_technical_info_with_cache(
+    let mut tech = core::media::probe::collect_technical_info(
+        tools.probe.as_ref(),
         src_videos[0].to_string_lossy().as_ref(),
-        config.media.enable_mediainfo_cache,
     );
-
-    let quality = qualities.into_iter().next();
     crate::app::common::apply_resolution_fallback(&mut tech, quality.as_deref());
 
-    let release_group = if release_groups.len() == 1 {
-        release_groups.into_iter().next()
-    } else {
-        None
-    };
-
     let hints = core::naming::PackHints {
-        title: series.title.clone(),
+        title: crate::app::common::choose_title(&series_titles(series), config),
         year: series.year.and_then(|y| u16::try_from(y).ok()),
         pack_tag: format!("S{:02}", season),
         quality,
+        source,
         release_group,
     };
     let decision = core::naming::propose_pack_scene_name(None, &hints, &tech);
 
-    let mut final_scene_name = decision.chosen.clone();
-    if config.media.append_no_tag_on_missing_group && hints.release_group.is_none() {
-        final_scene_name.push_str("-NoTag");
-    }
+    tracing::debug!(provenance = ?decision.provenance, "Rebuilt '{}'", decision.chosen);
+
+    let final_scene_name = crate::app::common::final_scene_name(&decision, config);
+
+    let Some(final_scene_name) = crate::app::common::claim_seed_name(
+        seeds,
+        seed_root,
+        &final_scene_name,
+        &src_videos,
+        &disambiguation(series, &decision, config),
+        config,
+    ) else {
+        return;
+    };
 
-    if let Err(e) = core::fs::export_seed_pack_structure(
+    let relinked = match core::fs::export_seed_pack_structure(
         PathBuf::from(seed_root).as_path(),
         &final_scene_name,
         &src_videos,
+        tools.nfo.as_ref(),
     ) {
-        tracing::error!("Failed to export season pack '{}': {}", final_scene_name, e);
+        Ok(relinked) => relinked,
+        Err(e) => {
+            tracing::error!("Failed to export season pack '{}': {}", final_scene_name, e);
+            return;
+        }
+    };
+    let seed_dir = PathBuf::from(seed_root).join(&final_scene_name);
+    if relinked
+        && !crate::app::common::reset_relinked_seed(
+            &seed_dir,
+            &final_scene_name,
+            config,
+            upload_service,
+        )
+    {
         return;
     }
 
@@ -347,27 +455,39 @@ async fn create_season_pack(
         return;
     }
 
-    let seed_dir = PathBuf::from(seed_root).join(&final_scene_name);
-    match core::torrent::create_torrent_for_seed_dir(seed_dir.as_path(), &final_scene_name, config)
-    {
+    match core::torrent::create_torrent_for_seed_dir(
+        tools.torrent.as_ref(),
+        seed_dir.as_path(),
+        &final_scene_name,
+        config,
+    ) {
         Ok(torrent_path) => {
             let heading = format!("S{:02} Complete", season);
             let overview = series.overview.as_deref();
-            if let Err(e) = upload_service
+            let meta = release_metadata(
+                series,
+                &src_videos[0],
+                &final_scene_name,
+                tools,
+                upload_service,
+            )
+            .await;
+            let outcomes = upload_service
                 .upload_episode_torrent(
-                    &series.title,
+                    &series_titles(series),
+                    series.year.and_then(|y| u16::try_from(y).ok()),
+                    &external_ids(series),
                     &heading,
                     cover_url,
                     overview,
                     &final_scene_name,
                     &tech,
+                    &meta,
                     torrent_path,
                     kind,
                 )
-                .await
-            {
-                tracing::error!("Failed to upload torrent for '{}': {e}", final_scene_name);
-            }
+                .await;
+            crate::app::common::log_upload_outcomes(&final_scene_name, &outcomes);
         }
         Err(e) => {
             tracing::error!("Failed to create torrent for '{}': {}", final_scene_name, e);
@@ -375,77 +495,87 @@ async fn create_season_pack(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn create_integrale_pack(
     series: &core::sonarr::SeriesResource,
     episode_files: &[core::sonarr::EpisodeFileResource],
     cover_url: Option<&str>,
     kind: core::upload::ContentKind,
     config: &crate::config::Config,
+    tools: &crate::app::common::Tools,
     upload_service: &core::upload::UploadService,
+    seeds: &core::fs::collision::SeedRegistry,
 ) {
     let Some(seed_root) = &config.media.seed_path else {
         return;
     };
 
-    let mut unique_paths: std::collections::BTreeSet<PathBuf> = std::collections::BTreeSet::new();
-    let mut qualities: Vec<String> = Vec::new();
-    let mut release_groups: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
-
-    for epf in episode_files {
-        if let Some(lp) = translate_episode_path(epf, config) {
-            unique_paths.insert(lp);
-        }
-        if let Some(q) = extract_sonarr_quality_name(epf) {
-            qualities.push(q);
-        }
-        if let Some(rg) = epf.release_group.as_ref() {
-            release_groups.insert(rg.clone());
-        }
-    }
-
-    let src_videos: Vec<PathBuf> = unique_paths.into_iter().collect();
+    let PackFiles {
+        src_videos,
+        quality,
+        source,
+        release_group,
+    } = pack_files(episode_files, config);
     if src_videos.is_empty() {
         return;
     }
 
-    let mut tech = core::media::mediainfo::collect_technical_info_with_cache(
+    let mut tech = core::media::probe::collect_technical_info(
+        tools.probe.as_ref(),
         src_videos[0].to_string_lossy().as_ref(),
-        config.media.enable_mediainfo_cache,
     );
-
-    let quality = qualities.into_iter().next();
     crate::app::common::apply_resolution_fallback(&mut tech, quality.as_deref());
 
-    let release_group = if release_groups.len() == 1 {
-        release_groups.into_iter().next()
-    } else {
-        None
-    };
-
     let hints = core::naming::PackHints {
-        title: series.title.clone(),
+        title: crate::app::common::choose_title(&series_titles(series), config),
         year: series.year.and_then(|y| u16::try_from(y).ok()),
         pack_tag: "INTEGRALE".to_string(),
         quality,
+        source,
         release_group,
     };
     let decision = core::naming::propose_pack_scene_name(None, &hints, &tech);
 
-    let mut final_scene_name = decision.chosen.clone();
-    if config.media.append_no_tag_on_missing_group && hints.release_group.is_none() {
-        final_scene_name.push_str("-NoTag");
-    }
+    tracing::debug!(provenance = ?decision.provenance, "Rebuilt '{}'", decision.chosen);
+
+    let final_scene_name = crate::app::common::final_scene_name(&decision, config);
+
+    let Some(final_scene_name) = crate::app::common::claim_seed_name(
+        seeds,
+        seed_root,
+        &final_scene_name,
+        &src_videos,
+        &disambiguation(series, &decision, config),
+        config,
+    ) else {
+        return;
+    };
 
-    if let Err(e) = core::fs::export_seed_pack_structure(
+    let relinked = match core::fs::export_seed_pack_structure(
         PathBuf::from(seed_root).as_path(),
         &final_scene_name,
         &src_videos,
+        tools.nfo.as_ref(),
     ) {
-        tracing::error!(
-            "Failed to export integrale pack '{}': {}",
-            final_scene_name,
-            e
-        );
+        Ok(relinked) => relinked,
+        Err(e) => {
+            tracing::error!(
+                "Failed to export integrale pack '{}': {}",
+                final_scene_name,
+                e
+            );
+            return;
+        }
+    };
+    let seed_dir = PathBuf::from(seed_root).join(&final_scene_name);
+    if relinked
+        && !crate::app::common::reset_relinked_seed(
+            &seed_dir,
+            &final_scene_name,
+            config,
+            upload_service,
+        )
+    {
         return;
     }
 
@@ -457,27 +587,39 @@ async fn create_integrale_pack(
         return;
     }
 
-    let seed_dir = PathBuf::from(seed_root).join(&final_scene_name);
-    match core::torrent::create_torrent_for_seed_dir(seed_dir.as_path(), &final_scene_name, config)
-    {
+    match core::torrent::create_torrent_for_seed_dir(
+        tools.torrent.as_ref(),
+        seed_dir.as_path(),
+        &final_scene_name,
+        config,
+    ) {
         Ok(torrent_path) => {
             let heading = "Integrale".to_string();
             let overview = series.overview.as_deref();
-            if let Err(e) = upload_service
+            let meta = release_metadata(
+                series,
+                &src_videos[0],
+                &final_scene_name,
+                tools,
+                upload_service,
+            )
+            .await;
+            let outcomes = upload_service
                 .upload_episode_torrent(
-                    &series.title,
+                    &series_titles(series),
+                    series.year.and_then(|y| u16::try_from(y).ok()),
+                    &external_ids(series),
                     &heading,
                     cover_url,
                     overview,
                     &final_scene_name,
                     &tech,
+                    &meta,
                     torrent_path,
                     kind,
                 )
-                .await
-            {
-                tracing::error!("Failed to upload torrent for '{}': {e}", final_scene_name);
-            }
+                .await;
+            crate::app::common::log_upload_outcomes(&final_scene_name, &outcomes);
         }
         Err(e) => {
             tracing::error!("Failed to create torrent for '{}': {}", final_scene_name, e);
@@ -497,6 +639,53 @@ fn content_kind_from_series_type(series_type: Option<&str>) -> core::upload::Con
     }
 }
 
+fn external_ids(series: &core::sonarr::SeriesResource) -> core::upload::ExternalIds {
+    core::upload::ExternalIds {
+        tmdb_id: series.tmdb_id,
+        imdb_id: series.imdb_id.clone(),
+        tvdb_id: series.tvdb_id,
+    }
+}
+
+/// Description data: series metadata, screenshots, and MediaInfo text when a template uses
+/// it. Sonarr has no cast endpoint.
+async fn release_metadata(
+    series: &core::sonarr::SeriesResource,
+    video: &std::path::Path,
+    scene_name: &str,
+    tools: &crate::app::common::Tools,
+    upload_service: &core::upload::UploadService,
+) -> core::upload::description::ReleaseMetadata {
+    let ratings = series
+        .ratings
+        .as_ref()
+        .and_then(|r| {
+            Some(core::upload::description::Rating {
+                source: "sonarr".to_string(),
+                value: r.value.filter(|v| *v > 0.0)?,
+                votes: r.votes,
+            })
+        })
+        .into_iter()
+        .collect();
+    let mediainfo = upload_service
+        .needs_template_var("mediainfo")
+        .then(|| tools.probe.text_report(video.to_string_lossy().as_ref()))
+        .flatten();
+
+    core::upload::description::ReleaseMetadata {
+        genres: series.genres.clone(),
+        runtime: series
+            .runtime
+            .and_then(|r| u32::try_from(r).ok())
+            .filter(|r| *r > 0),
+        ratings,
+        cast: Vec::new(),
+        mediainfo,
+        screenshots: upload_service.screenshots(video, scene_name).await,
+    }
+}
+
 fn pick_sonarr_cover_url(series: &core::sonarr::SeriesResource) -> Option<String> {
     series
         .images
@@ -504,14 +693,24 @@ fn pick_sonarr_cover_url(series: &core::sonarr::SeriesResource) -> Option<String
         .find_map(|img| img.remote_url.clone().or_else(|| img.url.clone()))
 }
 
-fn extract_sonarr_quality_name(epf: &core::sonarr::EpisodeFileResource) -> Option<String> {
+pub fn extract_sonarr_quality_name(epf: &core::sonarr::EpisodeFileResource) -> Option<String> {
     epf.quality
         .as_ref()
         .and_then(|q| q.quality.as_ref())
         .and_then(|q2| q2.name.clone())
 }
 
-fn translate_episode_path(
+fn extract_sonarr_quality_source(
+    epf: &core::sonarr::EpisodeFileResource,
+) -> Option<core::naming::Source> {
+    epf.quality
+        .as_ref()
+        .and_then(|q| q.quality.as_ref())
+        .and_then(|q2| q2.source.as_deref())
+        .and_then(|s| core::naming::Source::from_arr(s, None))
+}
+
+pub fn translate_episode_path(
     epf: &core::sonarr::EpisodeFileResource,
     config: &crate::config::Config,
 ) -> Option<PathBuf> {
@@ -565,40 +764,22 @@ fn format_episode_heading(
     tag
 }
 
-async fn process_episode_file(
+/// Naming hints and metadata of the episodes in one file.
+pub struct EpisodeFileInfo {
+    pub hints: core::naming::EpisodeHints,
+    pub overview: Option<String>,
+    pub episode_title: Option<String>,
+}
+
+/// Hints for an episode file (possibly multi-episode); the series title follows
+/// `title_strategy`.
+pub fn episode_file_info(
     series: &core::sonarr::SeriesResource,
     epf: &core::sonarr::EpisodeFileResource,
     episode_by_id: &std::collections::HashMap<i64, core::sonarr::EpisodeResource>,
-    cover_url: Option<&str>,
-    kind: core::upload::ContentKind,
     config: &crate::config::Config,
-    upload_service: &core::upload::UploadService,
-) {
-    if epf.episode_ids.is_empty() {
-        tracing::warn!(
-            "Skipping episode file with no episodeIds: path={} ",
-            epf.path
-        );
-        return;
-    }
-
-    let local_path = match translate_episode_path(epf, config) {
-        Some(p) => p,
-        None => return,
-    };
-
-    tracing::debug!(
-        "mediainfo path: sonarr='{}' local='{}'",
-        epf.path,
-        local_path.display()
-    );
-    let mut tech = core::media::mediainfo::collect_technical_info_with_cache(
-        local_path.to_string_lossy().as_ref(),
-        config.media.enable_mediainfo_cache,
-    );
-
+) -> EpisodeFileInfo {
     let quality = extract_sonarr_quality_name(epf);
-    crate::app::common::apply_resolution_fallback(&mut tech, quality.as_deref());
 
     // Episode metadata (may be multi-episode)
     let mut season_number: Option<u16> = None;
@@ -630,31 +811,81 @@ async fn process_episode_file(
     }
 
     let hints = core::naming::EpisodeHints {
-        series_title: series.title.clone(),
+        series_title: crate::app::common::choose_title(&series_titles(series), config),
         series_year: series.year.and_then(|y| u16::try_from(y).ok()),
         season_number,
-        episode_numbers: episode_numbers.clone(),
-        absolute_episode_numbers: absolute_numbers.clone(),
-        quality: quality.clone(),
+        episode_numbers,
+        absolute_episode_numbers: absolute_numbers,
+        quality,
+        source: extract_sonarr_quality_source(epf),
         release_group: epf.release_group.clone(),
     };
+    EpisodeFileInfo {
+        hints,
+        overview,
+        episode_title,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn process_episode_file(
+    series: &core::sonarr::SeriesResource,
+    epf: &core::sonarr::EpisodeFileResource,
+    episode_by_id: &std::collections::HashMap<i64, core::sonarr::EpisodeResource>,
+    cover_url: Option<&str>,
+    kind: core::upload::ContentKind,
+    config: &crate::config::Config,
+    tools: &crate::app::common::Tools,
+    upload_service: &core::upload::UploadService,
+    seeds: &core::fs::collision::SeedRegistry,
+) {
+    if epf.episode_ids.is_empty() {
+        tracing::warn!(
+            "Skipping episode file with no episodeIds: path={} ",
+            epf.path
+        );
+        return;
+    }
+
+    let local_path = match translate_episode_path(epf, config) {
+        Some(p) => p,
+        None => return,
+    };
+
+    tracing::debug!(
+        "mediainfo path: sonarr='{}' local='{}'",
+        epf.path,
+        local_path.display()
+    );
+    let mut tech = core::media::probe::collect_technical_info(
+        tools.probe.as_ref(),
+        local_path.to_string_lossy().as_ref(),
+    );
+
+    let quality = extract_sonarr_quality_name(epf);
+    crate::app::common::apply_resolution_fallback(&mut tech, quality.as_deref());
+
+    let EpisodeFileInfo {
+        hints,
+        overview,
+        episode_title,
+    } = episode_file_info(series, epf, episode_by_id, config);
 
     let original_scene = epf.scene_name.as_deref();
     let decision = core::naming::propose_episode_scene_name(original_scene, &hints, &tech);
 
-    let mut final_scene_name = decision.chosen.clone();
-    if config.media.append_no_tag_on_missing_group && epf.release_group.is_none() {
-        final_scene_name.push_str("-NoTag");
-    }
+    tracing::debug!(provenance = ?decision.provenance, "Rebuilt '{}'", decision.chosen);
+
+    let final_scene_name = crate::app::common::final_scene_name(&decision, config);
 
     let episode_heading = format_episode_heading(
-        season_number,
-        &episode_numbers,
-        &absolute_numbers,
+        hints.season_number,
+        &hints.episode_numbers,
+        &hints.absolute_episode_numbers,
         episode_title.as_deref(),
     );
 
-    println!("Series: {}", series.title);
+    println!("Series: {}", hints.series_title);
     println!("  Path: {}", epf.path);
     println!(
         "  Tech: res={:?} vcodec={:?} bitdepth={:?} hdr={} dv={} acodec={:?} ach={:?}",
@@ -678,16 +909,43 @@ async fn process_episode_file(
         return;
     };
 
-    if let Err(e) = core::fs::export_seed_structure(
+    let Some(final_scene_name) = crate::app::common::claim_seed_name(
+        seeds,
+        seed_root,
+        &final_scene_name,
+        std::slice::from_ref(&local_path),
+        &disambiguation(series, &decision, config),
+        config,
+    ) else {
+        return;
+    };
+
+    let relinked = match core::fs::export_seed_structure(
         PathBuf::from(seed_root).as_path(),
         &final_scene_name,
         local_path.as_path(),
+        tools.nfo.as_ref(),
     ) {
-        tracing::error!(
-            "Failed to export seed structure for '{}': {}",
-            final_scene_name,
-            e
-        );
+        Ok(relinked) => relinked,
+        Err(e) => {
+            tracing::error!(
+                "Failed to export seed structure for '{}': {}",
+                final_scene_name,
+                e
+            );
+            return;
+        }
+    };
+    let seed_dir = PathBuf::from(seed_root).join(&final_scene_name);
+    if relinked
+        && !crate::app::common::reset_relinked_seed(
+            &seed_dir,
+            &final_scene_name,
+            config,
+            upload_service,
+        )
+    {
+        return;
     }
 
     if config.torrent.dry_run {
@@ -698,35 +956,66 @@ async fn process_episode_file(
         return;
     }
 
-    let seed_dir = PathBuf::from(seed_root).join(&final_scene_name);
-    match core::torrent::create_torrent_for_seed_dir(seed_dir.as_path(), &final_scene_name, config)
-    {
+    match core::torrent::create_torrent_for_seed_dir(
+        tools.torrent.as_ref(),
+        seed_dir.as_path(),
+        &final_scene_name,
+        config,
+    ) {
         Ok(torrent_path) => {
-            if let Err(e) = upload_service
+            let meta = release_metadata(
+                series,
+                &local_path,
+                &final_scene_name,
+                tools,
+                upload_service,
+            )
+            .await;
+            let outcomes = upload_service
                 .upload_episode_torrent(
-                    &series.title,
+                    &series_titles(series),
+                    series.year.and_then(|y| u16::try_from(y).ok()),
+                    &external_ids(series),
                     &episode_heading,
                     cover_url,
                     overview.as_deref(),
                     &final_scene_name,
                     &tech,
+                    &meta,
                     torrent_path,
                     kind,
                 )
-                .await
-            {
-                tracing::error!("Failed to upload torrent for '{}': {e}", final_scene_name);
-            } else if upload_service.is_enabled() {
-                tracing::info!("Uploaded torrent for '{}'", final_scene_name);
-            } else {
-                tracing::info!(
-                    "Upload service disabled: skipping upload for '{}'",
-                    final_scene_name
-                );
-            }
+                .await;
+            crate::app::common::log_upload_outcomes(&final_scene_name, &outcomes);
         }
         Err(e) => {
             tracing::error!("Failed to create torrent for '{}': {}", final_scene_name, e);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(language: &str, aliases: &[&str]) -> core::sonarr::SeriesResource {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "The Office",
+            "originalLanguage": { "name": language },
+            "alternateTitles": aliases.iter().map(|t| serde_json::json!({ "title": t })).collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn english_series_keep_their_title_as_original() {
+        let titles = series_titles(&series("English", &["The Office of Scranton", "Das Büro"]));
+        assert_eq!(titles.original.as_deref(), Some("The Office"));
+
+        let titles = series_titles(&series("French", &["The Office of Scranton"]));
+        assert_eq!(titles.original, None);
+        let titles = series_titles(&series("French", &["Le Bureau"]));
+        assert_eq!(titles.original.as_deref(), Some("Le Bureau"));
+    }
+}
@@ -1,4 +1,6 @@
-use serde::Deserialize;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
 
 impl Config {
     pub fn init() -> Result<Self, config::ConfigError> {
@@ -36,7 +38,82 @@ pub struct Config {
     pub sonarr: Option<SonarrConfig>,
     #[serde(default)]
     pub upload: Option<UploadConfig>,
+    #[serde(default)]
+    pub http: HttpConfig,
     pub paths: Option<PathsConfig>,
+    #[serde(default)]
+    pub naming: NamingConfig,
+}
+
+// ===============================================================================
+// HTTP (shared by every API client)
+// ===============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+#[allow(unused)]
+pub struct HttpConfig {
+    /// Total request timeout, in seconds.
+    #[serde(default = "default_http_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Connection timeout, in seconds.
+    #[serde(default = "default_http_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// Retries after the first attempt on connection errors, timeouts, 429 and 5xx.
+    #[serde(default = "default_http_max_retries")]
+    pub max_retries: u32,
+    /// First backoff delay in milliseconds; doubled on each retry.
+    #[serde(default = "default_http_backoff_base_ms")]
+    pub backoff_base_ms: u64,
+    /// Upper bound for the backoff delay in milliseconds.
+    #[serde(default = "default_http_backoff_max_ms")]
+    pub backoff_max_ms: u64,
+    /// Per-host request quotas (e.g. trackers allowing N uploads per minute).
+    #[serde(default)]
+    pub rate_limits: Vec<RateLimitConfig>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_http_timeout_secs(),
+            connect_timeout_secs: default_http_connect_timeout_secs(),
+            max_retries: default_http_max_retries(),
+            backoff_base_ms: default_http_backoff_base_ms(),
+            backoff_max_ms: default_http_backoff_max_ms(),
+            rate_limits: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[allow(unused)]
+pub struct RateLimitConfig {
+    /// Host name the limit applies to (e.g. "tracker.example.com").
+    pub host: String,
+    /// Maximum number of requests per window.
+    pub max_requests: u32,
+    /// Window length, in seconds.
+    pub per_secs: u64,
+}
+
+fn default_http_timeout_secs() -> u64 {
+    60
+}
+
+fn default_http_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_http_max_retries() -> u32 {
+    3
+}
+
+fn default_http_backoff_base_ms() -> u64 {
+    500
+}
+
+fn default_http_backoff_max_ms() -> u64 {
+    30_000
 }
 
 // ===============================================================================
@@ -55,11 +132,89 @@ pub struct UploadConfig {
     #[serde(default)]
     pub tracker: Option<String>,
 
+    /// Directory watched by the seeding client. Tracker-issued torrents are written there as
+    /// `<scene>.<tracker>.torrent`. Defaults to the directory of the locally-built torrent,
+    /// which is then renamed to `<scene>.torrent.local`.
+    #[serde(default)]
+    pub seed_torrent_dir: Option<String>,
+
+    /// Search each tracker for existing releases before uploading and apply this policy.
+    /// Default: `always` (no search).
+    #[serde(default)]
+    pub dupe_policy: crate::core::upload::dupe::DupePolicy,
+
+    /// Directory for upload state (retry queue). Defaults to `<seed_path>/.seedarr`.
+    #[serde(default)]
+    pub state_dir: Option<String>,
+
+    /// Failed attempts after which a queued upload is flagged for manual review.
+    #[serde(default = "default_upload_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Screenshots captured from the source video and added to descriptions.
+    #[serde(default)]
+    pub screenshots: ScreenshotsConfig,
+
     /// Tracker-specific configuration blocks
     #[serde(default)]
     pub torrust: Option<TorrustUploadConfig>,
 }
 
+fn default_upload_max_attempts() -> u32 {
+    5
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[allow(unused)]
+pub struct ScreenshotsConfig {
+    /// Capture screenshots with ffmpeg (skipped with a warning if ffmpeg is missing).
+    #[serde(default)]
+    pub enable: bool,
+    /// Capture points: percentage of the duration ("25%"), "HH:MM:SS", "MM:SS" or seconds.
+    #[serde(default = "default_screenshot_timestamps")]
+    pub timestamps: Vec<String>,
+    /// Where screenshots are published. Default: local files.
+    #[serde(default)]
+    pub host: ImageHostConfig,
+}
+
+impl Default for ScreenshotsConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            timestamps: default_screenshot_timestamps(),
+            host: ImageHostConfig::default(),
+        }
+    }
+}
+
+fn default_screenshot_timestamps() -> Vec<String> {
+    ["15%", "35%", "55%", "75%"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[allow(unused)]
+pub enum ImageHostConfig {
+    /// Keep screenshots on disk. With `base_url` (public URL of `seed_path`), links point
+    /// there; otherwise `file://` links are used (testing only).
+    Local {
+        #[serde(default)]
+        base_url: Option<String>,
+    },
+    /// Chevereto-compatible API (`POST <api_base>/api/1/upload`).
+    Chevereto { api_base: String, api_key: String },
+}
+
+impl Default for ImageHostConfig {
+    fn default() -> Self {
+        Self::Local { base_url: None }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[allow(unused)]
 pub struct TorrustUploadConfig {
@@ -67,6 +222,10 @@ pub struct TorrustUploadConfig {
     pub enable: bool,
     pub api_base: String,
     pub api_key: String,
+    /// Base URL of the index web UI, for torrent page links (default: `api_base` up to its
+    /// `/api` segment; no links when the API has its own host)
+    #[serde(default)]
+    pub web_base: Option<String>,
     #[serde(default)]
     pub movies_category: Option<String>,
     #[serde(default)]
@@ -75,6 +234,19 @@ pub struct TorrustUploadConfig {
     pub animes_category: Option<String>,
     #[serde(default)]
     pub tags: Option<Vec<String>>,
+    /// Description markup: "markdown" (default), "bbcode" or "plain"
+    #[serde(default)]
+    pub description_format: crate::core::upload::description::DescriptionFormat,
+    /// Path to a description template (replaces the built-in description)
+    #[serde(default)]
+    pub description_template: Option<String>,
+    /// Title shown on this tracker (description, template, dupe search), overriding
+    /// `media.title_strategy`. The release name is the same on every tracker.
+    #[serde(default)]
+    pub title_strategy: Option<TitleStrategy>,
+    /// Wanted title language for "prefer_locale" on this tracker (default: `media.title_locale`)
+    #[serde(default)]
+    pub title_locale: Option<String>,
 }
 
 // ===============================================================================
@@ -95,15 +267,154 @@ pub struct MediaConfig {
     /// Title selection strategy (if set, overrides `use_original_title`)
     #[serde(default)]
     pub title_strategy: Option<TitleStrategy>,
+    /// Wanted title language for the "prefer_locale" strategy ("fr", "German", "pt-BR")
+    #[serde(default)]
+    pub title_locale: Option<String>,
+    /// Tool used to read media files: "auto" (mediainfo, then ffprobe), "mediainfo" or "ffprobe"
+    #[serde(default)]
+    pub probe: crate::core::media::probe::ProbeBackend,
+    /// When a seed dir name is already used by another source: "tmdb_id" (add the TMDB/TVDB
+    /// id), "alt_title" (rebuild with the other title, then the id) or "error" (skip)
+    #[serde(default)]
+    pub on_collision: crate::core::fs::collision::CollisionPolicy,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TitleStrategy {
     /// Use original_title if original_language is English; otherwise use localized title
     OriginalIfEnElseLocal,
     /// Always use the localized title
     AlwaysLocal,
+    /// Always use the original title (localized when there is none)
+    AlwaysOriginal,
+    /// Use the title in `title_locale`: the original title or an alternate title in that
+    /// language, else the localized title
+    PreferLocale,
+    /// Original title followed by the localized one: "Le Fabuleux Destin... AKA Amelie"
+    OriginalAka,
+}
+
+impl MediaConfig {
+    /// `title_strategy`, or the legacy `use_original_title` switch when unset.
+    pub fn effective_title_strategy(&self) -> TitleStrategy {
+        match self.title_strategy {
+            Some(strategy) => strategy,
+            None if self.use_original_title => TitleStrategy::AlwaysOriginal,
+            None => TitleStrategy::AlwaysLocal,
+        }
+    }
+}
+
+// ===============================================================================
+// Naming
+// ===============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+#[allow(unused)]
+pub struct NamingConfig {
+    /// Active profile: "default" (built-in rules) or a key of `profiles`.
+    #[serde(default = "default_naming_profile")]
+    pub profile: String,
+    /// Named rule sets (`[naming.profiles.<name>]`).
+    #[serde(default)]
+    pub profiles: HashMap<String, NamingProfile>,
+}
+
+impl Default for NamingConfig {
+    fn default() -> Self {
+        Self {
+            profile: default_naming_profile(),
+            profiles: HashMap::new(),
+        }
+    }
+}
+
+impl NamingConfig {
+    /// The active profile; "default" may be overridden by `[naming.profiles.default]`.
+    pub fn active_profile(&self) -> Result<NamingProfile, String> {
+        match self.profiles.get(&self.profile) {
+            Some(p) => Ok(p.clone()),
+            None if self.profile == "default" => Ok(NamingProfile::default()),
+            None => Err(format!(
+                "naming profile '{}' is not defined under [naming.profiles]",
+                self.profile
+            )),
+        }
+    }
+}
+
+fn default_naming_profile() -> String {
+    "default".to_string()
+}
+
+/// Validation rules for scene names. Every rule has a default severity that `severity` can
+/// override (`"error"`, `"warn"` or `"off"`); errors stop the link/torrent/upload steps.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+#[allow(unused)]
+pub struct NamingProfile {
+    /// Maximum name length, in bytes. The name is also used for `<name>.<ext>`, `<name>.nfo`
+    /// and `<name>.torrent`, and file names are limited to 255 bytes; longer names are shortened.
+    pub max_length: usize,
+    /// Regex a single character must match (e.g. `"[A-Za-z0-9.+-]"`); unset allows anything
+    /// but whitespace and path-unsafe characters.
+    pub allowed_chars: Option<String>,
+    /// Dot-separated tokens that must not appear (case-insensitive).
+    pub forbidden_words: Vec<String>,
+    /// Tokens that must be present.
+    pub required_tokens: Vec<crate::core::naming::rules::TokenKind>,
+    /// Check the scene order: year, resolution, source, audio, video codec.
+    pub token_order: bool,
+    /// Regex the release group must match.
+    pub group_format: Option<String>,
+    /// "keep" (default) keeps accented and non-Latin letters (`À.bout.de.souffle`); "ascii"
+    /// transliterates them (`A.bout.de.souffle`, `Brat`, `Tonarinototoro`).
+    pub transliterate: Transliteration,
+    /// Release group aliases, forbidden groups and house tag (`[naming.profiles.<name>.groups]`).
+    pub groups: GroupPolicy,
+    /// Per-rule severity overrides.
+    pub severity: HashMap<crate::core::naming::rules::Rule, crate::core::naming::rules::Severity>,
+}
+
+impl Default for NamingProfile {
+    fn default() -> Self {
+        use crate::core::naming::rules::TokenKind;
+        Self {
+            max_length: 230,
+            allowed_chars: None,
+            forbidden_words: Vec::new(),
+            required_tokens: vec![TokenKind::Resolution, TokenKind::VideoCodec],
+            token_order: true,
+            group_format: None,
+            transliterate: Transliteration::default(),
+            groups: GroupPolicy::default(),
+            severity: HashMap::new(),
+        }
+    }
+}
+
+/// How release groups are renamed or replaced. Groups are compared case-insensitively, on
+/// letters and digits only.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct GroupPolicy {
+    /// Canonical group name -> other spellings (`QxR = ["QxRHEVC", "QxRTeam"]`).
+    pub aliases: HashMap<String, Vec<String>>,
+    /// Groups never released under: replaced by `house_tag`, or dropped without one.
+    pub forbidden: Vec<String>,
+    /// Our own group tag.
+    pub house_tag: Option<String>,
+    /// Always release under `house_tag`, whatever the source group.
+    pub always_house_tag: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transliteration {
+    #[default]
+    Keep,
+    Ascii,
 }
 
 // ===============================================================================
@@ -0,0 +1,289 @@
+//! Seed directory collisions: two sets of source files whose rebuilt names are identical would
+//! share `seed_path/<scene>` (exports skip existing files), so each name is claimed once per run
+//! and checked against the links already on disk.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::Deserialize;
+
+use crate::utils::Error;
+
+/// How to rename a release whose seed directory belongs to another source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionPolicy {
+    /// Add the TMDB id (TVDB id for series) before the group: "Title.2020.1080p.TMDB1234-GRP".
+    #[default]
+    TmdbId,
+    /// Rebuild the name with the alternative title (original vs localized), falling back to
+    /// the id when there is none or it collides too.
+    AltTitle,
+    /// Skip the release.
+    Error,
+}
+
+/// What a colliding release can be told apart by.
+#[derive(Debug, Clone, Default)]
+pub struct Disambiguation {
+    pub tmdb_id: Option<i64>,
+    pub tvdb_id: Option<i64>,
+    /// Full scene name rebuilt with the alternative title, when it differs.
+    pub alt_name: Option<String>,
+    /// Full scene name with the TMDB (else TVDB) id before the group, fitted to the naming
+    /// profile's length limit.
+    pub id_name: Option<String>,
+}
+
+impl Disambiguation {
+    /// Name token for the external id: "TMDB1234", else "TVDB1234".
+    pub fn id_tag(&self) -> Option<String> {
+        match (self.tmdb_id, self.tvdb_id) {
+            (Some(id), _) => Some(format!("TMDB{id}")),
+            (None, Some(id)) => Some(format!("TVDB{id}")),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Who a seed name belongs to: the source files it links.
+#[derive(Debug, Clone)]
+struct Owner {
+    sources: BTreeSet<PathBuf>,
+}
+
+impl Owner {
+    /// Same release: the same source files, or a pack that gained episodes since it was
+    /// seeded (its files are a subset of ours). The library item id doesn't count: the same
+    /// movie in two Radarr instances can point to two different files.
+    fn is_same_release(&self, ours: &Owner) -> bool {
+        self.sources == ours.sources
+            || (ours.sources.len() > 1 && self.sources.is_subset(&ours.sources))
+    }
+}
+
+/// Seed names claimed during this run, with the item and source files behind each.
+pub struct SeedRegistry {
+    policy: CollisionPolicy,
+    claimed: Mutex<HashMap<String, Owner>>,
+}
+
+impl SeedRegistry {
+    pub fn new(policy: CollisionPolicy) -> Self {
+        Self {
+            policy,
+            claimed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claim `name` for `sources` under `seed_root`, returning the name to export under: `name`
+    /// itself, or a disambiguated one when other source files already own it (in this run
+    /// or on disk; links to files that no longer exist don't count). Errors when the policy is
+    /// `error` or no candidate is free.
+    pub fn claim(
+        &self,
+        seed_root: &Path,
+        name: &str,
+        sources: &[PathBuf],
+        alt: &Disambiguation,
+    ) -> Result<String, Error> {
+        let ours = Owner {
+            sources: sources.iter().map(|p| canonical(p)).collect(),
+        };
+        let mut claimed = self.claimed.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut candidates = vec![name.to_string()];
+        match self.policy {
+            CollisionPolicy::Error => {}
+            CollisionPolicy::AltTitle => {
+                candidates.extend(alt.alt_name.clone());
+                candidates.extend(alt.id_name.clone());
+            }
+            CollisionPolicy::TmdbId => candidates.extend(alt.id_name.clone()),
+        }
+
+        for candidate in &candidates {
+            let owner = claimed.get(candidate).cloned().or_else(|| {
+                linked_sources(&seed_root.join(candidate)).map(|sources| Owner { sources })
+            });
+            match owner {
+                Some(owner) if !owner.sources.is_empty() && !owner.is_same_release(&ours) => {
+                    tracing::warn!(
+                        "Seed name '{}' is already used by {}",
+                        candidate,
+                        describe(&owner.sources)
+                    );
+                }
+                _ => {
+                    if candidate != name {
+                        tracing::info!("Seed name collision: using '{}' for '{}'", candidate, name);
+                    }
+                    claimed.insert(candidate.clone(), ours);
+                    return Ok(candidate.clone());
+                }
+            }
+        }
+
+        Err(Error::Other(format!(
+            "seed name '{name}' collides with another source (on_collision = {:?})",
+            self.policy
+        )))
+    }
+}
+
+/// Existing files the symlinks of a seed directory point to (dangling links are stale: the
+/// file was replaced or upgraded); None when there is no directory.
+fn linked_sources(seed_dir: &Path) -> Option<BTreeSet<PathBuf>> {
+    let entries = std::fs::read_dir(seed_dir).ok()?;
+    let mut sources = BTreeSet::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e == "nfo") {
+            continue;
+        }
+        if let Ok(target) = std::fs::read_link(&path) {
+            let target = seed_dir.join(target);
+            if target.exists() {
+                sources.insert(canonical(&target));
+            }
+        }
+    }
+    Some(sources)
+}
+
+fn canonical(p: &Path) -> PathBuf {
+    std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf())
+}
+
+fn describe(sources: &BTreeSet<PathBuf>) -> String {
+    match sources.iter().next() {
+        Some(first) if sources.len() > 1 => {
+            format!("'{}' and {} more", first.display(), sources.len() - 1)
+        }
+        Some(first) => format!("'{}'", first.display()),
+        None => "another release".to_string(),
+    }
+}
+
+#[cfg(all(test, target_family = "unix"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disambiguates_names_owned_by_other_sources() {
+        let root = std::env::temp_dir().join(format!("seedarr-collision-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let library = root.join("library");
+        let seeds = root.join("seeds");
+        std::fs::create_dir_all(&library).unwrap();
+        let a = library.join("a.mkv");
+        let b = library.join("b.mkv");
+        std::fs::write(&a, b"a").unwrap();
+        std::fs::write(&b, b"b").unwrap();
+
+        // A seed dir from a previous run, linking to `a`
+        let name = "Heat.1995.1080p.BluRay.x264-GRP";
+        std::fs::create_dir_all(seeds.join(name)).unwrap();
+        std::os::unix::fs::symlink(&a, seeds.join(name).join(format!("{name}.mkv"))).unwrap();
+
+        let ids = Disambiguation {
+            tmdb_id: Some(949),
+            ..Disambiguation::default()
+        };
+        let other = Disambiguation {
+            tmdb_id: Some(1001),
+            id_name: Some("Heat.1995.1080p.BluRay.x264.TMDB1001-GRP".to_string()),
+            ..Disambiguation::default()
+        };
+        let registry = SeedRegistry::new(CollisionPolicy::TmdbId);
+        assert_eq!(
+            registry
+                .claim(&seeds, name, std::slice::from_ref(&a), &ids)
+                .unwrap(),
+            name
+        );
+        assert_eq!(
+            registry
+                .claim(&seeds, name, std::slice::from_ref(&b), &other)
+                .unwrap(),
+            "Heat.1995.1080p.BluRay.x264.TMDB1001-GRP"
+        );
+        // Same source again (e.g. a second Radarr instance): same name
+        assert_eq!(registry.claim(&seeds, name, &[a], &ids).unwrap(), name);
+
+        let strict = SeedRegistry::new(CollisionPolicy::Error);
+        assert!(strict.claim(&seeds, name, &[b], &other).is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn grown_packs_keep_their_name_and_other_files_collide() {
+        let root =
+            std::env::temp_dir().join(format!("seedarr-collision-same-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let library = root.join("library");
+        let seeds = root.join("seeds");
+        std::fs::create_dir_all(&library).unwrap();
+        let file = |name: &str| {
+            let path = library.join(name);
+            std::fs::write(&path, name).unwrap();
+            path
+        };
+        let link = |dir: &str, target: &Path| {
+            std::fs::create_dir_all(seeds.join(dir)).unwrap();
+            let name = target.file_name().unwrap();
+            std::os::unix::fs::symlink(target, seeds.join(dir).join(name)).unwrap();
+        };
+        let no_ids = Disambiguation::default();
+
+        // A season pack seeded with E01, now with E02 as well
+        let (e01, e02) = (file("e01.mkv"), file("e02.mkv"));
+        let pack = "Show.S01.1080p.WEB-DL.x264-GRP";
+        link(pack, &e01);
+        let registry = SeedRegistry::new(CollisionPolicy::Error);
+        assert_eq!(
+            registry.claim(&seeds, pack, &[e01, e02], &no_ids).unwrap(),
+            pack
+        );
+
+        // A movie whose file was replaced: the old link dangles
+        let old = file("old.mkv");
+        let movie = "Heat.1995.1080p.BluRay.x264-GRP";
+        link(movie, &old);
+        std::fs::remove_file(&old).unwrap();
+        let new = file("new.mkv");
+        assert_eq!(
+            registry.claim(&seeds, movie, &[new], &no_ids).unwrap(),
+            movie
+        );
+
+        // The same movie in two Radarr instances, with two different files: the id only picks
+        // the suffix
+        let ids = Disambiguation {
+            tmdb_id: Some(949),
+            id_name: Some("Ronin.1998.1080p.BluRay.x264.TMDB949-GRP".to_string()),
+            ..Disambiguation::default()
+        };
+        let name = "Ronin.1998.1080p.BluRay.x264-GRP";
+        let (a, b) = (file("a.mkv"), file("b.mkv"));
+        assert_eq!(
+            registry
+                .claim(&seeds, name, std::slice::from_ref(&a), &ids)
+                .unwrap(),
+            name
+        );
+        assert!(registry
+            .claim(&seeds, name, std::slice::from_ref(&b), &ids)
+            .is_err());
+        let by_id = SeedRegistry::new(CollisionPolicy::TmdbId);
+        assert_eq!(by_id.claim(&seeds, name, &[a], &ids).unwrap(), name);
+        assert_eq!(
+            by_id.claim(&seeds, name, &[b], &ids).unwrap(),
+            "Ronin.1998.1080p.BluRay.x264.TMDB949-GRP"
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
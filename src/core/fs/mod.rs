@@ -1,17 +1,25 @@
+pub mod collision;
+
 use std::path::Path;
 use tracing::{debug, error, warn};
 
+use crate::core::media::probe::MediaProbe;
+
 #[cfg(target_family = "unix")]
 use std::os::unix::fs as unix_fs;
 
 /// Export a seed structure for a given scene name and source video path:
 /// <seed_root>/<scene_name>/<scene_name>.<ext>
-/// <seed_root>/<scene_name>/<scene_name>.nfo
+/// <seed_root>/<scene_name>/<scene_name>.nfo (the cached `mediainfo.nfo`, else `probe`'s report)
+///
+/// Returns true when a stale link was replaced: torrents built from the seed dir no longer
+/// match its files.
 pub fn export_seed_structure(
     seed_root: &Path,
     scene_name: &str,
     src_video: &Path,
-) -> std::io::Result<()> {
+    probe: &dyn MediaProbe,
+) -> std::io::Result<bool> {
     let seed_dir = seed_root.join(scene_name);
     std::fs::create_dir_all(&seed_dir)?;
 
@@ -22,10 +30,14 @@ pub fn export_seed_structure(
         .unwrap_or("mkv");
     let dest_video = seed_dir.join(format!("{}.{}", scene_name, ext));
     // Idempotency: if video link already exists, skip entire export
-    if dest_video.exists() {
-        debug!("Seed export already exists: '{}'", dest_video.display());
-        return Ok(());
-    }
+    let relinked = match already_exported(&dest_video)? {
+        Existing::Exported => {
+            debug!("Seed export already exists: '{}'", dest_video.display());
+            return Ok(false);
+        }
+        Existing::Missing => false,
+        Existing::Stale => true,
+    };
 
     #[cfg(target_family = "unix")]
     {
@@ -39,6 +51,7 @@ pub fn export_seed_structure(
         );
         if let Err(e) = unix_fs::symlink(&target, &dest_video) {
             error!("Failed to symlink video '{}': {}", dest_video.display(), e);
+            return Err(e);
         }
     }
     #[cfg(not(target_family = "unix"))]
@@ -56,9 +69,12 @@ pub fn export_seed_structure(
 
     // NFO: prefer symlink of existing source mediainfo.nfo; else write textual mediainfo
     let dest_nfo = seed_dir.join(format!("{}.nfo", scene_name));
+    if relinked {
+        remove_old_nfo(&dest_nfo)?;
+    }
     // If NFO exists already, keep it
-    if dest_nfo.exists() {
-        return Ok(());
+    if already_exported(&dest_nfo)? == Existing::Exported {
+        return Ok(relinked);
     }
     let src_nfo = src_video
         .parent()
@@ -74,10 +90,7 @@ pub fn export_seed_structure(
                     "Failed to symlink mediainfo.nfo ({}), generating new text NFO",
                     e
                 );
-                let _ = crate::core::media::mediainfo::write_text_nfo(
-                    src_video.to_string_lossy().as_ref(),
-                    &dest_nfo,
-                );
+                let _ = write_text_nfo(probe, src_video.to_string_lossy().as_ref(), &dest_nfo);
             }
         }
         #[cfg(not(target_family = "unix"))]
@@ -90,13 +103,10 @@ pub fn export_seed_structure(
             }
         }
     } else {
-        let _ = crate::core::media::mediainfo::write_text_nfo(
-            src_video.to_string_lossy().as_ref(),
-            &dest_nfo,
-        );
+        let _ = write_text_nfo(probe, src_video.to_string_lossy().as_ref(), &dest_nfo);
     }
 
-    Ok(())
+    Ok(relinked)
 }
 
 /// Export a seed directory containing multiple source files (season/integrale packs).
@@ -105,21 +115,27 @@ pub fn export_seed_structure(
 /// <seed_root>/<pack_name>/<basename1>
 /// <seed_root>/<pack_name>/<basename2>
 /// <seed_root>/<pack_name>/<pack_name>.nfo
+///
+/// Returns true when a stale link was replaced, as [`export_seed_structure`].
 pub fn export_seed_pack_structure(
     seed_root: &Path,
     pack_name: &str,
     src_videos: &[std::path::PathBuf],
-) -> std::io::Result<()> {
+    probe: &dyn MediaProbe,
+) -> std::io::Result<bool> {
     let seed_dir = seed_root.join(pack_name);
     std::fs::create_dir_all(&seed_dir)?;
 
+    let mut relinked = false;
     for src_video in src_videos {
         let Some(file_name) = src_video.file_name() else {
             continue;
         };
         let dest_video = seed_dir.join(file_name);
-        if dest_video.exists() {
-            continue;
+        match already_exported(&dest_video)? {
+            Existing::Exported => continue,
+            Existing::Missing => {}
+            Existing::Stale => relinked = true,
         }
 
         #[cfg(target_family = "unix")]
@@ -137,6 +153,7 @@ pub fn export_seed_pack_structure(
                     dest_video.display(),
                     e
                 );
+                return Err(e);
             }
         }
         #[cfg(not(target_family = "unix"))]
@@ -154,8 +171,11 @@ pub fn export_seed_pack_structure(
 
     // Pack-level NFO: derive from first file.
     let dest_nfo = seed_dir.join(format!("{}.nfo", pack_name));
-    if dest_nfo.exists() {
-        return Ok(());
+    if relinked {
+        remove_old_nfo(&dest_nfo)?;
+    }
+    if already_exported(&dest_nfo)? == Existing::Exported {
+        return Ok(relinked);
     }
     if let Some(first) = src_videos.first() {
         let src_nfo = first
@@ -172,10 +192,7 @@ pub fn export_seed_pack_structure(
                         "Failed to symlink mediainfo.nfo ({}), generating new text NFO",
                         e
                     );
-                    let _ = crate::core::media::mediainfo::write_text_nfo(
-                        first.to_string_lossy().as_ref(),
-                        &dest_nfo,
-                    );
+                    let _ = write_text_nfo(probe, first.to_string_lossy().as_ref(), &dest_nfo);
                 }
             }
             #[cfg(not(target_family = "unix"))]
@@ -186,14 +203,53 @@ pub fn export_seed_pack_structure(
                 }
             }
         } else {
-            let _ = crate::core::media::mediainfo::write_text_nfo(
-                first.to_string_lossy().as_ref(),
-                &dest_nfo,
-            );
+            let _ = write_text_nfo(probe, first.to_string_lossy().as_ref(), &dest_nfo);
         }
     }
 
-    Ok(())
+    Ok(relinked)
+}
+
+/// What [`already_exported`] found at an export destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Existing {
+    Exported,
+    Missing,
+    /// A link whose target is gone (the source was replaced or upgraded), now removed so it
+    /// can be linked again.
+    Stale,
+}
+
+/// Whether `dest` is already in place; `exists()` alone follows a dangling link and says no.
+fn already_exported(dest: &Path) -> std::io::Result<Existing> {
+    if dest.exists() {
+        return Ok(Existing::Exported);
+    }
+    if std::fs::symlink_metadata(dest).is_ok() {
+        warn!("Replacing stale link '{}'", dest.display());
+        std::fs::remove_file(dest)?;
+        return Ok(Existing::Stale);
+    }
+    Ok(Existing::Missing)
+}
+
+/// Remove the NFO of a relinked seed dir: it describes the previous files.
+fn remove_old_nfo(nfo: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(nfo) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Write the probe's text report of `video` to `out`. Returns true on success.
+fn write_text_nfo(probe: &dyn MediaProbe, video: &str, out: &Path) -> bool {
+    let Some(text) = probe.text_report(video) else {
+        return false;
+    };
+    if let Some(parent) = out.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    std::fs::write(out, text).is_ok()
 }
 
 /// Compute a relative path from `from_dir` to `to_path` if they share a common ancestor.
@@ -221,3 +277,64 @@ fn relative_target(from_dir: &Path, to_path: &Path) -> Option<std::path::PathBuf
     rel.push(to_suffix);
     Some(rel)
 }
+
+#[cfg(all(test, target_family = "unix"))]
+mod tests {
+    use super::*;
+    use crate::core::media::model::MediaInfo;
+    use crate::utils::Error;
+
+    struct NoProbe;
+
+    impl MediaProbe for NoProbe {
+        fn name(&self) -> &str {
+            "none"
+        }
+        fn probe(&self, _: &str) -> Result<MediaInfo, Error> {
+            Err(Error::Other("not installed".to_string()))
+        }
+        fn text_report(&self, _: &str) -> Option<String> {
+            None
+        }
+    }
+
+    #[test]
+    fn stale_links_are_relinked() {
+        let root = std::env::temp_dir().join(format!("seedarr-export-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let library = root.join("library");
+        let seeds = root.join("seeds");
+        std::fs::create_dir_all(&library).unwrap();
+        let old = library.join("old.mkv");
+        let new = library.join("new.mkv");
+        std::fs::write(&new, b"new").unwrap();
+
+        // A seed dir whose source was upgraded since the last run
+        let name = "Heat.1995.1080p.BluRay.x264-GRP";
+        let dest = seeds.join(name).join(format!("{name}.mkv"));
+        std::fs::create_dir_all(seeds.join(name)).unwrap();
+        unix_fs::symlink(&old, &dest).unwrap();
+        assert!(!dest.exists());
+
+        let nfo = seeds.join(name).join(format!("{name}.nfo"));
+        std::fs::write(&nfo, b"old report").unwrap();
+        assert!(export_seed_structure(&seeds, name, &new, &NoProbe).unwrap());
+        assert_eq!(std::fs::read(&dest).unwrap(), b"new");
+        assert!(!nfo.exists());
+        assert!(!export_seed_structure(&seeds, name, &new, &NoProbe).unwrap());
+
+        // Same for the files of a pack
+        let pack = "Show.S01.1080p.WEB-DL.x264-GRP";
+        let e01 = library.join("e01.mkv");
+        std::fs::write(&e01, b"e01").unwrap();
+        let dest = seeds.join(pack).join("e01.mkv");
+        std::fs::create_dir_all(seeds.join(pack)).unwrap();
+        unix_fs::symlink(library.join("old").join("e01.mkv"), &dest).unwrap();
+
+        assert!(export_seed_pack_structure(&seeds, pack, &[e01.clone()], &NoProbe).unwrap());
+        assert_eq!(std::fs::read(&dest).unwrap(), b"e01");
+        assert!(!export_seed_pack_structure(&seeds, pack, &[e01], &NoProbe).unwrap());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,441 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+use crate::utils::Error;
+
+use super::model::{AudioTrack, Chapter, MediaInfo, SubtitleTrack, VideoTrack};
+use super::probe::MediaProbe;
+
+/// `ffprobe` CLI backend. Codec names are mapped to MediaInfo's vocabulary so both backends
+/// feed the same model.
+pub struct FfprobeProbe;
+
+impl MediaProbe for FfprobeProbe {
+    fn name(&self) -> &str {
+        "ffprobe"
+    }
+
+    fn probe(&self, path: &str) -> Result<MediaInfo, Error> {
+        let out = Command::new("ffprobe")
+            .args(["-v", "error", "-print_format", "json"])
+            .args(["-show_format", "-show_streams", "-show_chapters"])
+            .arg(path)
+            .output()
+            .map_err(|e| Error::Other(format!("Failed to run ffprobe: {e}")))?;
+        if !out.status.success() {
+            return Err(Error::Other(format!(
+                "ffprobe failed: {}",
+                String::from_utf8_lossy(&out.stderr).trim()
+            )));
+        }
+        let json: Value = serde_json::from_slice(&out.stdout)
+            .map_err(|e| Error::Other(format!("Invalid ffprobe output: {e}")))?;
+        parse_ffprobe_json(&json)
+            .ok_or_else(|| Error::Other("ffprobe output has no stream list".to_string()))
+    }
+
+    fn text_report(&self, path: &str) -> Option<String> {
+        // ffprobe prints its human-readable summary on stderr
+        let out = Command::new("ffprobe")
+            .arg("-hide_banner")
+            .arg(path)
+            .output()
+            .ok()?;
+        if !out.status.success() {
+            return None;
+        }
+        String::from_utf8(out.stderr).ok()
+    }
+}
+
+/// Map `ffprobe -print_format json -show_format -show_streams -show_chapters` to the media
+/// model. None if there is no stream list.
+pub fn parse_ffprobe_json(json: &Value) -> Option<MediaInfo> {
+    let streams = json.get("streams")?.as_array()?;
+    let mut media = MediaInfo::default();
+
+    if let Some(format) = json.get("format") {
+        media.container = str_at(format, &["format_long_name"])
+            .or_else(|| str_at(format, &["format_name"]))
+            .map(|f| container_name(&f));
+        media.duration_secs = str_at(format, &["duration"]).and_then(|d| d.parse().ok());
+        media.bitrate = str_at(format, &["bit_rate"]).and_then(|b| b.parse().ok());
+    }
+
+    for stream in streams {
+        let disposition = |key: &str| {
+            stream
+                .get("disposition")
+                .and_then(|d| d.get(key))
+                .and_then(|v| v.as_i64())
+                .is_some_and(|v| v != 0)
+        };
+        let codec = str_at(stream, &["codec_name"]).unwrap_or_default();
+        let language = str_at(stream, &["tags", "language"]).filter(|l| l != "und");
+        let title = str_at(stream, &["tags", "title"]);
+        let title_has = |needle: &str| {
+            title
+                .as_deref()
+                .is_some_and(|t| t.to_ascii_lowercase().contains(needle))
+        };
+
+        match str_at(stream, &["codec_type"]).as_deref() {
+            // Embedded cover art is exposed as a video stream
+            Some("video") if disposition("attached_pic") => {}
+            Some("video") => {
+                let hdr = hdr_layers(stream);
+                media.video.push(VideoTrack {
+                    format: video_format(&codec),
+                    width: u32_at(stream, "width"),
+                    height: u32_at(stream, "height"),
+                    bit_depth: video_bit_depth(stream),
+                    hdr_format: hdr.format,
+                    hdr_profile: hdr.profile,
+                    hdr_level: hdr.level,
+                    hdr_compatibility: hdr.compatibility,
+                    transfer: str_at(stream, &["color_transfer"]).and_then(|t| transfer_name(&t)),
+                    bitrate: str_at(stream, &["bit_rate"]).and_then(|b| b.parse().ok()),
+                    frame_rate: str_at(stream, &["avg_frame_rate"]).and_then(|r| parse_ratio(&r)),
+                    default: disposition("default"),
+                });
+            }
+            Some("audio") => media.audio.push(AudioTrack {
+                format: audio_format(&codec),
+                commercial_name: audio_commercial_name(
+                    &codec,
+                    str_at(stream, &["profile"]).as_deref(),
+                ),
+                additional_features: audio_features(
+                    &codec,
+                    str_at(stream, &["profile"]).as_deref(),
+                ),
+                channels: u32_at(stream, "channels"),
+                language,
+                bitrate: str_at(stream, &["bit_rate"]).and_then(|b| b.parse().ok()),
+                default: disposition("default"),
+                forced: disposition("forced"),
+                commentary: disposition("comment") || title_has("comment"),
+                title,
+            }),
+            Some("subtitle") => media.subtitles.push(SubtitleTrack {
+                format: subtitle_format(&codec),
+                language,
+                default: disposition("default"),
+                forced: disposition("forced"),
+                sdh: disposition("hearing_impaired")
+                    || title_has("sdh")
+                    || title_has("hearing")
+                    || title_has("malentendant"),
+                title,
+            }),
+            _ => {}
+        }
+    }
+
+    if let Some(chapters) = json.get("chapters").and_then(|c| c.as_array()) {
+        media.chapters = chapters
+            .iter()
+            .filter_map(|c| {
+                Some(Chapter {
+                    start_secs: str_at(c, &["start_time"])?.parse().ok()?,
+                    title: str_at(c, &["tags", "title"]),
+                })
+            })
+            .collect();
+    }
+
+    Some(media)
+}
+
+/// Non-empty string at `path`; ffprobe reports most numbers as strings.
+fn str_at(v: &Value, path: &[&str]) -> Option<String> {
+    let mut cur = v;
+    for key in path {
+        cur = cur.get(*key)?;
+    }
+    let s = match cur {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    Some(s).filter(|s| !s.is_empty())
+}
+
+fn u32_at(v: &Value, key: &str) -> Option<u32> {
+    v.get(key)
+        .and_then(|n| n.as_u64())
+        .and_then(|n| u32::try_from(n).ok())
+}
+
+/// "24000/1001" -> 23.976
+fn parse_ratio(r: &str) -> Option<f64> {
+    let (n, d) = r.split_once('/')?;
+    let (n, d): (f64, f64) = (n.parse().ok()?, d.parse().ok()?);
+    (d != 0.0 && n != 0.0).then(|| n / d)
+}
+
+fn container_name(format: &str) -> String {
+    let f = format.to_ascii_lowercase();
+    if f.contains("matroska") {
+        "Matroska".to_string()
+    } else if f.contains("quicktime") || f.contains("mp4") {
+        "MPEG-4".to_string()
+    } else if f.contains("mpeg-ts") || f == "mpegts" {
+        "MPEG-TS".to_string()
+    } else {
+        format.to_string()
+    }
+}
+
+fn video_format(codec: &str) -> String {
+    match codec {
+        "hevc" => "HEVC",
+        "h264" => "AVC",
+        "av1" => "AV1",
+        "vp9" => "VP9",
+        "mpeg2video" => "MPEG Video",
+        "mpeg4" => "MPEG-4 Visual",
+        "mjpeg" => "JPEG",
+        "png" => "PNG",
+        other => return other.to_ascii_uppercase(),
+    }
+    .to_string()
+}
+
+/// `bits_per_raw_sample` when present, else inferred from the pixel format ("yuv420p10le").
+fn video_bit_depth(stream: &Value) -> Option<u32> {
+    if let Some(b) = str_at(stream, &["bits_per_raw_sample"]).and_then(|b| b.parse().ok()) {
+        return Some(b);
+    }
+    let pix = str_at(stream, &["pix_fmt"])?;
+    if pix.contains("12") {
+        Some(12)
+    } else if pix.contains("10") {
+        Some(10)
+    } else {
+        Some(8)
+    }
+}
+
+/// HDR layers from stream side data, in MediaInfo's wording and layout: format, profile,
+/// level and compatibility joined with " / " (e.g. "Dolby Vision / SMPTE ST 2086").
+struct HdrLayers {
+    format: Option<String>,
+    profile: Option<String>,
+    level: Option<String>,
+    compatibility: Option<String>,
+}
+
+fn hdr_layers(stream: &Value) -> HdrLayers {
+    let side_data: Vec<&Value> = stream
+        .get("side_data_list")
+        .and_then(|s| s.as_array())
+        .into_iter()
+        .flatten()
+        .collect();
+    let find = |needle: &str| {
+        side_data.iter().copied().find(|s| {
+            str_at(s, &["side_data_type"]).is_some_and(|t| t.to_ascii_lowercase().contains(needle))
+        })
+    };
+
+    // (format, profile, level, compatibility) per layer
+    let mut layers: Vec<(&str, String, String, String)> = Vec::new();
+    if let Some(dovi) = find("dovi").or_else(|| find("dolby vision")) {
+        let num = |key: &str| dovi.get(key).and_then(|v| v.as_u64());
+        // dv_bl_signal_compatibility_id: 1 = HDR10, 2 = SDR, 4 = HLG, 6 = Blu-ray (HDR10)
+        let compat = match num("dv_bl_signal_compatibility_id") {
+            Some(1) => "HDR10",
+            Some(2) => "SDR",
+            Some(4) => "HLG",
+            Some(6) => "Blu-ray",
+            _ => "",
+        };
+        layers.push((
+            "Dolby Vision",
+            num("dv_profile").map_or_else(String::new, |p| format!("dvhe.{p:02}")),
+            num("dv_level").map_or_else(String::new, |l| format!("{l:02}")),
+            compat.to_string(),
+        ));
+    }
+    if find("hdr dynamic metadata").is_some() || find("2094").is_some() {
+        layers.push((
+            "SMPTE ST 2094 App 4",
+            String::new(),
+            String::new(),
+            "HDR10+".to_string(),
+        ));
+    }
+    if find("mastering display").is_some() {
+        layers.push((
+            "SMPTE ST 2086",
+            String::new(),
+            String::new(),
+            "HDR10".to_string(),
+        ));
+    }
+
+    let join = |f: fn(&(&str, String, String, String)) -> String| {
+        let parts: Vec<String> = layers.iter().map(f).collect();
+        Some(parts.join(" / ")).filter(|j| !j.trim_matches([' ', '/']).is_empty())
+    };
+    HdrLayers {
+        format: join(|l| l.0.to_string()),
+        profile: join(|l| l.1.clone()),
+        level: join(|l| l.2.clone()),
+        compatibility: join(|l| l.3.clone()),
+    }
+}
+
+fn transfer_name(transfer: &str) -> Option<String> {
+    let t = match transfer {
+        "smpte2084" => "PQ",
+        "arib-std-b67" => "HLG",
+        "bt709" => "BT.709",
+        _ => return None,
+    };
+    Some(t.to_string())
+}
+
+fn audio_format(codec: &str) -> String {
+    if codec.starts_with("pcm_") {
+        return "PCM".to_string();
+    }
+    match codec {
+        "truehd" => "MLP FBA",
+        "ac3" => "AC-3",
+        "eac3" => "E-AC-3",
+        "dts" => "DTS",
+        "aac" => "AAC",
+        "flac" => "FLAC",
+        "opus" => "Opus",
+        "mp3" => "MPEG Audio",
+        "vorbis" => "Vorbis",
+        other => return other.to_ascii_uppercase(),
+    }
+    .to_string()
+}
+
+/// Marketing name from codec + profile, matching MediaInfo's `Format_Commercial_IfAny`.
+fn audio_commercial_name(codec: &str, profile: Option<&str>) -> Option<String> {
+    let profile = profile.unwrap_or_default().to_ascii_lowercase();
+    let name = match codec {
+        "truehd" if profile.contains("atmos") => "Dolby TrueHD with Dolby Atmos",
+        "truehd" => "Dolby TrueHD",
+        "eac3" if profile.contains("atmos") => "Dolby Digital Plus with Dolby Atmos",
+        "eac3" => "Dolby Digital Plus",
+        "ac3" => "Dolby Digital",
+        "dts" if profile.contains("dts:x") => "DTS-HD Master Audio",
+        "dts" if profile.contains("ma") => "DTS-HD Master Audio",
+        "dts" if profile.contains("hra") || profile.contains("hi res") => {
+            "DTS-HD High Resolution Audio"
+        }
+        // Exact: "DTS Express" also contains "es"
+        "dts" if profile == "dts-es" => "DTS-ES",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+/// MediaInfo-style `Format_AdditionalFeatures` from codec + profile.
+fn audio_features(codec: &str, profile: Option<&str>) -> Option<String> {
+    let profile = profile.unwrap_or_default().to_ascii_lowercase();
+    let features = match codec {
+        "truehd" if profile.contains("atmos") => "16-ch",
+        "eac3" if profile.contains("atmos") => "JOC",
+        "dts" if profile.contains("dts:x") => "XLL X",
+        "dts" if profile.contains("ma") => "XLL",
+        "dts" if profile.contains("hra") || profile.contains("hi res") => "XBR",
+        "dts" if profile == "dts-es" => "ES",
+        _ => return None,
+    };
+    Some(features.to_string())
+}
+
+fn subtitle_format(codec: &str) -> String {
+    match codec {
+        "subrip" => "UTF-8",
+        "hdmv_pgs_subtitle" => "PGS",
+        "dvd_subtitle" => "VobSub",
+        "ass" => "ASS",
+        "ssa" => "SSA",
+        "mov_text" => "Timed Text",
+        "webvtt" => "WebVTT",
+        other => return other.to_ascii_uppercase(),
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn maps_codecs_and_side_data() {
+        let json = json!({
+            "streams": [
+                { "codec_type": "video", "codec_name": "mjpeg", "disposition": { "attached_pic": 1 } },
+                { "codec_type": "video", "codec_name": "hevc", "width": 3840, "height": 2160,
+                  "pix_fmt": "yuv420p10le", "color_transfer": "smpte2084", "avg_frame_rate": "24000/1001",
+                  "side_data_list": [
+                      { "side_data_type": "DOVI configuration record", "dv_profile": 8, "dv_level": 6,
+                        "dv_bl_signal_compatibility_id": 1 },
+                      { "side_data_type": "Mastering display metadata" }
+                  ] },
+                { "codec_type": "audio", "codec_name": "dts", "profile": "DTS-HD MA", "channels": 6,
+                  "tags": { "language": "eng" }, "disposition": { "default": 1 } },
+                { "codec_type": "audio", "codec_name": "pcm_s24le", "channels": 2, "tags": { "language": "und" } }
+            ],
+            "format": { "format_name": "mov,mp4,m4a,3gp,3g2,mj2", "duration": "120.5" }
+        });
+        let media = parse_ffprobe_json(&json).unwrap();
+
+        assert_eq!(media.container.as_deref(), Some("MPEG-4"));
+        assert_eq!(media.video.len(), 1);
+        let v = &media.video[0];
+        assert_eq!(v.format, "HEVC");
+        assert_eq!(v.bit_depth, Some(10));
+        assert_eq!(
+            v.hdr_format.as_deref(),
+            Some("Dolby Vision / SMPTE ST 2086")
+        );
+        assert_eq!(v.hdr_profile.as_deref(), Some("dvhe.08 / "));
+        assert_eq!(v.transfer.as_deref(), Some("PQ"));
+        assert!((v.frame_rate.unwrap() - 23.976).abs() < 0.001);
+
+        assert_eq!(
+            media.audio[0].commercial_name.as_deref(),
+            Some("DTS-HD Master Audio")
+        );
+        assert_eq!(media.audio[1].format, "PCM");
+        assert_eq!(media.audio[1].language, None);
+
+        let tech = media.technical_info();
+        assert!(tech.dv && tech.hdr);
+        let dv = tech.dolby_vision.unwrap();
+        assert_eq!((dv.profile, dv.level), (Some(8), Some(6)));
+        assert_eq!(dv.compatibility.as_deref(), Some("HDR10"));
+        assert_eq!(tech.audio_codec.as_deref(), Some("DTS-HD.MA"));
+    }
+
+    #[test]
+    fn dts_express_is_not_dts_es() {
+        for (profile, name, features) in [
+            ("DTS-ES", Some("DTS-ES"), Some("ES")),
+            ("DTS Express", None, None),
+        ] {
+            assert_eq!(
+                audio_commercial_name("dts", Some(profile)).as_deref(),
+                name,
+                "{profile}"
+            );
+            assert_eq!(
+                audio_features("dts", Some(profile)).as_deref(),
+                features,
+                "{profile}"
+            );
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Language identification: maps whatever the probe tools report (ISO 639-1/2/3 codes, BCP 47
+//! tags, English or native names) to a normalized tag such as "fr", "fr-CA", "pt-BR" or "es-419".
+
+/// Known languages: ISO 639-1 code, ISO 639-2/B and 639-2/T (= 639-3) codes, English name, and
+/// native names.
+const LANGUAGES: &[(&str, &[&str], &str, &[&str])] = &[
+    ("fr", &["fre", "fra"], "French", &["français", "francais"]),
+    ("en", &["eng"], "English", &[]),
+    ("de", &["ger", "deu"], "German", &["deutsch"]),
+    ("it", &["ita"], "Italian", &["italiano"]),
+    (
+        "es",
+        &["spa"],
+        "Spanish",
+        &["español", "espanol", "castellano"],
+    ),
+    ("pt", &["por"], "Portuguese", &["português", "portugues"]),
+    ("nl", &["dut", "nld"], "Dutch", &["nederlands", "flemish"]),
+    ("ja", &["jpn"], "Japanese", &["日本語"]),
+    ("ko", &["kor"], "Korean", &["한국어"]),
+    (
+        "zh",
+        &["chi", "zho", "cmn", "yue"],
+        "Chinese",
+        &["mandarin", "cantonese", "中文"],
+    ),
+    ("ru", &["rus"], "Russian", &["русский"]),
+    ("pl", &["pol"], "Polish", &["polski"]),
+    ("sv", &["swe"], "Swedish", &["svenska"]),
+    (
+        "no",
+        &["nor", "nob", "nno"],
+        "Norwegian",
+        &["norsk", "bokmål"],
+    ),
+    ("da", &["dan"], "Danish", &["dansk"]),
+    ("fi", &["fin"], "Finnish", &["suomi"]),
+    ("cs", &["cze", "ces"], "Czech", &["čeština"]),
+    ("hu", &["hun"], "Hungarian", &["magyar"]),
+    ("el", &["gre", "ell"], "Greek", &["ελληνικά"]),
+    ("tr", &["tur"], "Turkish", &["türkçe"]),
+    ("ar", &["ara"], "Arabic", &["العربية"]),
+    ("he", &["heb"], "Hebrew", &["עברית"]),
+    ("hi", &["hin"], "Hindi", &["हिन्दी"]),
+    ("th", &["tha"], "Thai", &["ไทย"]),
+    ("uk", &["ukr"], "Ukrainian", &["українська"]),
+    ("ro", &["rum", "ron"], "Romanian", &["română"]),
+    ("ca", &["cat"], "Catalan", &["català"]),
+    ("vi", &["vie"], "Vietnamese", &["tiếng việt"]),
+    ("id", &["ind"], "Indonesian", &["bahasa indonesia"]),
+];
+
+/// Region-qualified names, checked before plain names ("Canadian French" is not just French).
+const REGIONAL_NAMES: &[(&str, &str)] = &[
+    ("canadian french", "fr-CA"),
+    ("québécois", "fr-CA"),
+    ("quebecois", "fr-CA"),
+    ("brazilian portuguese", "pt-BR"),
+    ("brazilian", "pt-BR"),
+    ("european portuguese", "pt-PT"),
+    ("latin american spanish", "es-419"),
+    ("european spanish", "es-ES"),
+    ("castilian", "es-ES"),
+    ("flemish", "nl-BE"),
+];
+
+/// Region names as written in "French (Canada)" style labels.
+const REGION_NAMES: &[(&str, &str)] = &[
+    ("france", "FR"),
+    ("canada", "CA"),
+    ("quebec", "CA"),
+    ("québec", "CA"),
+    ("belgium", "BE"),
+    ("switzerland", "CH"),
+    ("brazil", "BR"),
+    ("portugal", "PT"),
+    ("spain", "ES"),
+    ("latin america", "419"),
+    ("mexico", "MX"),
+    ("united states", "US"),
+    ("united kingdom", "GB"),
+    ("germany", "DE"),
+    ("austria", "AT"),
+];
+
+/// Normalize a reported language to `<iso639-1>[-<REGION>]` (the ISO 639-2/3 code is kept for
+/// languages without a two-letter code). None for "undetermined"/"multiple"/empty values.
+pub fn normalize(raw: &str) -> Option<String> {
+    let s = raw.trim();
+    let l = s.to_lowercase();
+    if l.is_empty() || matches!(l.as_str(), "und" | "zxx" | "mul" | "mis" | "unknown") {
+        return None;
+    }
+
+    if let Some((_, tag)) = REGIONAL_NAMES.iter().find(|(name, _)| l.contains(name)) {
+        return Some(tag.to_string());
+    }
+
+    // "French (CA)", "French (Canada)"
+    if let Some((name, rest)) = l.split_once('(') {
+        let region = rest.trim_end_matches(')').trim();
+        let base = normalize(name)?;
+        let base = base.split('-').next().unwrap_or(&base).to_string();
+        return Some(match region_code(region) {
+            Some(r) => format!("{base}-{r}"),
+            None => base,
+        });
+    }
+
+    // BCP 47-ish: "fr-CA", "fr_ca", "pt-BR", "es-419", "fra-CA"
+    if let Some((lang, region)) = l.split_once(['-', '_']) {
+        if let Some(base) = code(lang) {
+            return Some(match region_code(region) {
+                Some(r) => format!("{base}-{r}"),
+                None => base,
+            });
+        }
+    }
+
+    if let Some(c) = code(&l) {
+        return Some(c);
+    }
+
+    // Names, English or native
+    LANGUAGES
+        .iter()
+        .find(|(_, _, en, native)| l == en.to_lowercase() || native.contains(&l.as_str()))
+        .map(|(c, ..)| c.to_string())
+        .or_else(|| {
+            // Unknown, but a plausible ISO 639 code: keep it rather than lose the track
+            (l.len() <= 3 && l.chars().all(|c| c.is_ascii_alphabetic())).then_some(l)
+        })
+}
+
+/// ISO 639-1 code for a known 2- or 3-letter code.
+fn code(c: &str) -> Option<String> {
+    LANGUAGES
+        .iter()
+        .find(|(one, three, ..)| *one == c || three.contains(&c))
+        .map(|(one, ..)| one.to_string())
+}
+
+fn region_code(region: &str) -> Option<String> {
+    let r = region.trim();
+    if (r.len() == 2 && r.chars().all(|c| c.is_ascii_alphabetic()))
+        || (r.len() == 3 && r.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Some(r.to_ascii_uppercase());
+    }
+    let r = r.to_lowercase();
+    REGION_NAMES
+        .iter()
+        .find(|(name, _)| r == *name)
+        .map(|(_, code)| code.to_string())
+}
+
+/// Normalize an audio track's language, refined by its title: French dubs are often only
+/// labelled "VFQ"/"VFF" in the track name.
+pub fn audio_track_language(language: Option<&str>, title: Option<&str>) -> Option<String> {
+    let lang = language.and_then(normalize);
+    let title = title.unwrap_or_default().to_lowercase();
+    let is_french = lang.as_deref().is_none_or(|l| base(l) == "fr");
+    if !is_french {
+        return lang;
+    }
+
+    let words: Vec<&str> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let has = |w: &str| words.contains(&w);
+    if has("vfq") || title.contains("québ") || title.contains("queb") || title.contains("canad") {
+        Some("fr-CA".to_string())
+    } else if has("vff") || has("truefrench") || title.contains("france") {
+        Some("fr-FR".to_string())
+    } else {
+        lang
+    }
+}
+
+/// Language part of a normalized tag ("fr-CA" -> "fr").
+pub fn base(tag: &str) -> &str {
+    tag.split('-').next().unwrap_or(tag)
+}
+
+/// English name of a normalized tag, when known.
+pub fn english_name(tag: &str) -> Option<&'static str> {
+    let b = base(tag);
+    LANGUAGES
+        .iter()
+        .find(|(one, ..)| *one == b)
+        .map(|(_, _, en, _)| *en)
+}
+
+/// Scene language token for a normalized tag: the upper-cased English name with a lower-case
+/// "i" ("GERMAN", "iTALiAN", "SPANiSH").
+pub fn scene_token(tag: &str) -> Option<String> {
+    english_name(tag).map(|n| n.to_ascii_uppercase().replace('I', "i"))
+}
+
+/// Language code for a scene token spelled exactly as [`scene_token`] would ("iTALiAN", not
+/// "Italian", so title words are left alone).
+pub fn from_scene_token(token: &str) -> Option<&'static str> {
+    LANGUAGES
+        .iter()
+        .find(|(one, ..)| scene_token(one).as_deref() == Some(token))
+        .map(|(one, ..)| *one)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_codes_names_and_regions() {
+        for raw in ["fr", "fre", "fra", "French", "Français", "FR"] {
+            assert_eq!(normalize(raw).as_deref(), Some("fr"), "{raw}");
+        }
+        for raw in [
+            "fr-CA",
+            "fr_ca",
+            "French (CA)",
+            "French (Canada)",
+            "Canadian French",
+        ] {
+            assert_eq!(normalize(raw).as_deref(), Some("fr-CA"), "{raw}");
+        }
+        assert_eq!(normalize("fr-FR").as_deref(), Some("fr-FR"));
+        assert_eq!(normalize("pt-BR").as_deref(), Some("pt-BR"));
+        assert_eq!(normalize("Portuguese (Brazil)").as_deref(), Some("pt-BR"));
+        assert_eq!(normalize("es-419").as_deref(), Some("es-419"));
+        assert_eq!(
+            normalize("Latin American Spanish").as_deref(),
+            Some("es-419")
+        );
+        assert_eq!(normalize("ger").as_deref(), Some("de"));
+        assert_eq!(normalize("deu").as_deref(), Some("de"));
+        assert_eq!(normalize("Deutsch").as_deref(), Some("de"));
+        assert_eq!(normalize("eng").as_deref(), Some("en"));
+        assert_eq!(normalize("tlh").as_deref(), Some("tlh"));
+        assert_eq!(normalize("und"), None);
+        assert_eq!(normalize(""), None);
+
+        assert_eq!(scene_token("it").as_deref(), Some("iTALiAN"));
+        assert_eq!(scene_token("de-AT").as_deref(), Some("GERMAN"));
+        assert_eq!(from_scene_token("iTALiAN"), Some("it"));
+        assert_eq!(from_scene_token("Italian"), None);
+    }
+
+    #[test]
+    fn french_variants_from_track_titles() {
+        assert_eq!(
+            audio_track_language(Some("fr"), Some("VFQ 5.1")).as_deref(),
+            Some("fr-CA")
+        );
+        assert_eq!(
+            audio_track_language(Some("fre"), Some("Français (France) VFF")).as_deref(),
+            Some("fr-FR")
+        );
+        assert_eq!(
+            audio_track_language(Some("fr"), Some("VFI")).as_deref(),
+            Some("fr")
+        );
+        // Non-French tracks ignore French markers in the title
+        assert_eq!(
+            audio_track_language(Some("en"), Some("VFQ")).as_deref(),
+            Some("en")
+        );
+    }
+}
@@ -4,9 +4,12 @@ use std::process::Command;
 use std::time::SystemTime;
 
 use serde_json::Value;
-use tracing::{debug, info, warn};
+use tracing::debug;
 
-use crate::core::naming::TechnicalInfo;
+use crate::utils::Error;
+
+use super::model::{AudioTrack, Chapter, MediaInfo, SubtitleTrack, VideoTrack};
+use super::probe::MediaProbe;
 
 fn parse_int_from_value(v: &Value) -> Option<i64> {
     match v {
@@ -23,80 +26,6 @@ fn parse_int_from_value(v: &Value) -> Option<i64> {
     }
 }
 
-fn classify_resolution(width: Option<i64>, height: Option<i64>) -> Option<String> {
-    // Heuristic with tolerance to handle common crop/encode variations.
-    // Uses either dimension to qualify a bucket; width thresholds are slightly lower
-    // to accommodate cropped widths like 1915, 1904, etc. that are conventionally 1080p.
-    let w_ok = |t: i64| width.map(|w| w >= t).unwrap_or(false);
-    let h_ok = |t: i64| height.map(|h| h >= t).unwrap_or(false);
-
-    let res = if h_ok(2000) || w_ok(3600) {
-        "2160p"
-    } else if h_ok(1320) || w_ok(2400) {
-        "1440p"
-    } else if h_ok(900) || w_ok(1850) {
-        "1080p"
-    } else if h_ok(650) || w_ok(1180) {
-        "720p"
-    } else {
-        "480p"
-    };
-    Some(res.to_string())
-}
-
-fn map_video_codec(format: &str) -> Option<String> {
-    // Canonicalize to encoder-style tokens: x265/x264
-    let f = format.to_ascii_lowercase();
-    if f.contains("x265") || f.contains("hevc") || f.contains("h265") || f.contains("h.265") {
-        Some("x265".to_string())
-    } else if f.contains("x264") || f.contains("avc") || f.contains("h264") || f.contains("h.264") {
-        Some("x264".to_string())
-    }
-    // Hide legacy generic MPEG video tags from the scene name
-    else if f.contains("mpeg-4 visual") || (f.contains("mpeg") && f.contains("video")) {
-        None
-    } else {
-        Some(format.to_string())
-    }
-}
-
-fn map_audio_codec(format: &str) -> Option<String> {
-    let f = format.to_ascii_lowercase();
-    if f.contains("e-ac-3")
-        || f.contains("eac3")
-        || f.contains("ddp")
-        || f.contains("dolby digital plus")
-    {
-        Some("EAC3".to_string())
-    } else if f.contains("ac-3") || f.contains("ac3") || f.contains("dolby digital") {
-        Some("AC3".to_string())
-    } else if f.contains("dts") {
-        Some("DTS".to_string())
-    } else if f.contains("aac") {
-        Some("AAC".to_string())
-    } else if f.contains("mpeg") {
-        Some("MPEG".to_string())
-    } else if f.contains("mpeg-4 visual") {
-        Some("MPEG".to_string())
-    } else if f.contains("mpeg video") {
-        Some("MPEG".to_string())
-    } else if f.contains("mlp fba") {
-        None
-    } else {
-        Some(format.to_string())
-    }
-}
-
-fn map_channels(ch: i64) -> Option<String> {
-    match ch {
-        8 => Some("7.1".to_string()),
-        7 => Some("6.1".to_string()),
-        6 => Some("5.1".to_string()),
-        2 => Some("2.0".to_string()),
-        _ => None,
-    }
-}
-
 fn run_mediainfo_json(path: &str) -> Option<Vec<u8>> {
     let output = Command::new("mediainfo")
         .arg("--Output=JSON")
@@ -180,143 +109,205 @@ fn ensure_cache_and_load_json(video_path: &str, enable_cache: bool) -> Option<Va
     None
 }
 
-pub fn collect_technical_info_with_cache(path: &str, enable_cache: bool) -> TechnicalInfo {
-    info!(target: "seedarr::mediainfo", path = %path, cache = enable_cache, "Collecting technical info");
-    let json_opt = ensure_cache_and_load_json(path, enable_cache);
+/// Full MediaInfo text report, from the cached `mediainfo.nfo` when the cache is enabled.
+pub fn mediainfo_text_with_cache(path: &str, enable_cache: bool) -> Option<String> {
+    if enable_cache {
+        let nfo_path = Path::new(path)
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join("mediainfo.nfo");
+        if let Ok(text) = fs::read_to_string(&nfo_path) {
+            return Some(text);
+        }
+    }
+    run_mediainfo_text(path)
+}
 
-    let mut info = TechnicalInfo::default();
+/// `mediainfo` CLI backend. With the cache enabled, `mediainfo.json`/`mediainfo.nfo` are kept
+/// next to the source file and reused while newer than the video.
+pub struct MediainfoProbe {
+    pub enable_cache: bool,
+}
 
-    let Some(json) = json_opt else {
-        warn!(target: "seedarr::mediainfo", path = %path, "Failed to get mediainfo JSON (cache disabled or command failed)");
-        return info;
-    };
-    let tracks = json
-        .get("media")
-        .and_then(|m| m.get("track"))
-        .and_then(|t| t.as_array());
-    let Some(tracks) = tracks else {
-        return info;
-    };
+impl MediaProbe for MediainfoProbe {
+    fn name(&self) -> &str {
+        "mediainfo"
+    }
+
+    fn probe(&self, path: &str) -> Result<MediaInfo, Error> {
+        let json = ensure_cache_and_load_json(path, self.enable_cache)
+            .ok_or_else(|| Error::Other("mediainfo failed or is not installed".to_string()))?;
+        parse_mediainfo_json(&json)
+            .ok_or_else(|| Error::Other("mediainfo output has no track list".to_string()))
+    }
+
+    fn text_report(&self, path: &str) -> Option<String> {
+        mediainfo_text_with_cache(path, self.enable_cache)
+    }
+}
+
+/// Map `mediainfo --Output=JSON` to the media model. None if there is no track list.
+pub fn parse_mediainfo_json(json: &Value) -> Option<MediaInfo> {
+    let tracks = json.get("media")?.get("track")?.as_array()?;
+    let mut media = MediaInfo::default();
 
     for track in tracks {
-        let ttype = track.get("@type").and_then(|v| v.as_str()).unwrap_or("");
-        match ttype {
-            "Video" => {
-                // Resolution: combine width + height with tolerant thresholds
-                let width = track.get("Width").and_then(parse_int_from_value);
-                let height = track.get("Height").and_then(parse_int_from_value);
-                info.resolution = classify_resolution(width, height);
-                // Video codec
-                if let Some(fmt) = track.get("Format").and_then(|v| v.as_str()) {
-                    info.video_codec = map_video_codec(fmt);
-                }
-                // Bit depth: only report 10bit; ignore 8bit
-                if let Some(bd) = track.get("BitDepth") {
-                    if let Some(bits) = parse_int_from_value(bd) {
-                        if bits >= 10 {
-                            info.bit_depth = Some("10bit".to_string());
-                        }
-                    }
-                }
-                // HDR/Dolby Vision
-                if let Some(hdrf) = track.get("HDR_Format").and_then(|v| v.as_str()) {
-                    let l = hdrf.to_ascii_lowercase();
-                    if l.contains("hdr") {
-                        info.hdr = true;
-                    }
-                    if l.contains("dolby vision") {
-                        info.dv = true;
-                        info.hdr = true;
-                    }
-                }
-                // Some files have Transfer_Characteristics or ColorPrimaries with PQ/HLG hints
-                if let Some(tc) = track
-                    .get("transfer_characteristics")
-                    .and_then(|v| v.as_str())
-                {
-                    let l = tc.to_ascii_lowercase();
-                    if l.contains("pq") || l.contains("hlg") {
-                        info.hdr = true;
-                    }
-                }
-            }
-            "Audio" => {
-                if let Some(fmt) = track.get("Format").and_then(|v| v.as_str()) {
-                    info.audio_codec = map_audio_codec(fmt);
-                }
-                // Channels: prefer Channel(s), else Channel(s)_Original
-                let chan_val = track
-                    .get("Channel(s)")
-                    .or_else(|| track.get("Channel(s)_Original"));
-                if let Some(cv) = chan_val {
-                    if let Some(n) = parse_int_from_value(cv) {
-                        info.audio_channels = map_channels(n);
-                    }
-                }
-                // Alternatively, if textual contains "5.1", accept it
-                if info.audio_channels.is_none() {
-                    let text = chan_val.and_then(|v| v.as_str());
-                    if let Some(t) = text {
-                        if t.contains("5.1") {
-                            info.audio_channels = Some("5.1".to_string());
-                        }
-                    }
-                }
-                // Audio language and VFI
-                let lang = track
-                    .get("Language")
-                    .and_then(|v| v.as_str())
-                    .or_else(|| track.get("Language/String").and_then(|v| v.as_str()));
-                if let Some(l) = lang {
-                    let lc = l.to_ascii_lowercase();
-                    let code = if lc.starts_with("fr") {
-                        "fr"
-                    } else if lc.starts_with("en") {
-                        "en"
-                    } else {
-                        lc.as_str()
-                    };
-                    info.audio_languages.insert(code.to_string());
-                }
-                let title = track.get("Title").and_then(|v| v.as_str()).unwrap_or("");
-                if !title.is_empty() && title.to_ascii_uppercase().contains("VFI") {
-                    info.has_vfi = true;
-                }
-            }
-            "Text" => {
-                // Subtitle languages
-                let lang = track
-                    .get("Language")
-                    .and_then(|v| v.as_str())
-                    .or_else(|| track.get("Language/String").and_then(|v| v.as_str()));
-                if let Some(l) = lang {
-                    let lc = l.to_ascii_lowercase();
-                    let code = if lc.starts_with("fr") {
-                        "fr"
-                    } else if lc.starts_with("en") {
-                        "en"
-                    } else {
-                        lc.as_str()
-                    };
-                    info.subtitle_languages.insert(code.to_string());
-                }
+        let str_of = |key: &str| {
+            track
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let int_of = |key: &str| track.get(key).and_then(parse_int_from_value);
+        let flag = |key: &str| str_of(key).is_some_and(|v| v.eq_ignore_ascii_case("yes"));
+        let format = str_of("Format").unwrap_or_default();
+        let title = str_of("Title");
+        let title_has = |needle: &str| {
+            title
+                .as_deref()
+                .is_some_and(|t| t.to_ascii_lowercase().contains(needle))
+        };
+
+        match track.get("@type").and_then(|v| v.as_str()).unwrap_or("") {
+            "General" => {
+                media.container = Some(format).filter(|f| !f.is_empty());
+                media.duration_secs = track.get("Duration").and_then(parse_float_from_value);
+                media.bitrate = int_of("OverallBitRate").and_then(|b| u64::try_from(b).ok());
             }
+            "Video" => media.video.push(VideoTrack {
+                format,
+                width: int_of("Width").and_then(|w| u32::try_from(w).ok()),
+                height: int_of("Height").and_then(|h| u32::try_from(h).ok()),
+                bit_depth: int_of("BitDepth").and_then(|b| u32::try_from(b).ok()),
+                hdr_format: str_of("HDR_Format"),
+                hdr_profile: str_of("HDR_Format_Profile"),
+                hdr_level: str_of("HDR_Format_Level"),
+                hdr_compatibility: str_of("HDR_Format_Compatibility"),
+                transfer: str_of("transfer_characteristics"),
+                bitrate: int_of("BitRate").and_then(|b| u64::try_from(b).ok()),
+                frame_rate: track.get("FrameRate").and_then(parse_float_from_value),
+                default: flag("Default"),
+            }),
+            "Audio" => media.audio.push(AudioTrack {
+                format,
+                commercial_name: str_of("Format_Commercial_IfAny"),
+                additional_features: str_of("Format_AdditionalFeatures"),
+                channels: audio_channels(track),
+                language: str_of("Language").or_else(|| str_of("Language/String")),
+                bitrate: int_of("BitRate").and_then(|b| u64::try_from(b).ok()),
+                default: flag("Default"),
+                forced: flag("Forced"),
+                commentary: title_has("comment"),
+                title,
+            }),
+            "Text" => media.subtitles.push(SubtitleTrack {
+                format,
+                language: str_of("Language").or_else(|| str_of("Language/String")),
+                default: flag("Default"),
+                forced: flag("Forced"),
+                sdh: title_has("sdh") || title_has("hearing") || title_has("malentendant"),
+                title,
+            }),
+            "Menu" => media.chapters = parse_chapters(track),
             _ => {}
         }
     }
 
-    info!(target: "seedarr::mediainfo", path = %path, res = ?info.resolution, vcodec = ?info.video_codec, bitdepth = ?info.bit_depth, hdr = info.hdr, dv = info.dv, acodec = ?info.audio_codec, ach = ?info.audio_channels, alangs = ?info.audio_languages, slangs = ?info.subtitle_languages, vfi = info.has_vfi, "Collected technical info summary");
-    info
+    Some(media)
 }
 
-/// Ensure a textual mediainfo is written to the provided output path.
-/// Returns true on success.
-pub fn write_text_nfo(video_path: &str, out_path: &Path) -> bool {
-    if let Some(text) = run_mediainfo_text(video_path) {
-        if let Some(parent) = out_path.parent() {
-            let _ = fs::create_dir_all(parent);
+/// Channel count: `Channels` (or the legacy `Channel(s)` keys); "5.1"-style text counts the LFE.
+fn audio_channels(track: &Value) -> Option<u32> {
+    let v = ["Channels", "Channel(s)", "Channel(s)_Original"]
+        .iter()
+        .find_map(|k| track.get(*k))?;
+    if let Some(t) = v.as_str() {
+        if let Some((main, lfe)) = t.split_once('.') {
+            let main: u32 = main.trim().parse().ok()?;
+            let lfe: u32 = lfe.trim().parse().ok()?;
+            return Some(main + lfe);
         }
-        return fs::write(out_path, text).is_ok();
     }
-    false
+    parse_int_from_value(v).and_then(|n| u32::try_from(n).ok())
+}
+
+/// Chapters from the `Menu` track: `"extra": { "_00_12_34_567": "en:Title", ... }`.
+fn parse_chapters(track: &Value) -> Vec<Chapter> {
+    let Some(extra) = track.get("extra").and_then(|e| e.as_object()) else {
+        return Vec::new();
+    };
+    let mut chapters: Vec<Chapter> = extra
+        .iter()
+        .filter_map(|(k, v)| {
+            let parts: Vec<f64> = k
+                .trim_start_matches('_')
+                .split('_')
+                .map(|p| p.parse().ok())
+                .collect::<Option<_>>()?;
+            let [h, m, s, ms] = parts.as_slice() else {
+                return None;
+            };
+            let title = v.as_str().map(|t| {
+                // Drop the "en:" language prefix
+                match t.split_once(':') {
+                    Some((lang, rest)) if lang.len() <= 3 => rest.to_string(),
+                    _ => t.to_string(),
+                }
+            });
+            Some(Chapter {
+                start_secs: h * 3600.0 + m * 60.0 + s + ms / 1000.0,
+                title: title.filter(|t| !t.is_empty()),
+            })
+        })
+        .collect();
+    chapters.sort_by(|a, b| a.start_secs.total_cmp(&b.start_secs));
+    chapters
+}
+
+fn parse_float_from_value(v: &Value) -> Option<f64> {
+    match v {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_tracks_flags_and_chapters() {
+        let json = json!({ "media": { "track": [
+            { "@type": "General", "Format": "Matroska", "Duration": "6120.500", "OverallBitRate": "25000000" },
+            { "@type": "Video", "Format": "AVC", "Width": "1920", "Height": "800", "BitDepth": "8", "Default": "Yes" },
+            { "@type": "Audio", "Format": "DTS", "Channels": "6", "Language": "fr", "Default": "Yes" },
+            { "@type": "Audio", "Format": "AC-3", "Channel(s)": "2.0", "Language": "en", "Title": "Commentary" },
+            { "@type": "Text", "Format": "PGS", "Language": "fr", "Forced": "Yes" },
+            { "@type": "Text", "Format": "UTF-8", "Language": "en", "Title": "English SDH" },
+            { "@type": "Menu", "extra": { "_00_10_00_000": "en:Heist", "_00_00_00_000": "en:Opening" } }
+        ]}});
+        let media = parse_mediainfo_json(&json).unwrap();
+
+        assert_eq!(media.container.as_deref(), Some("Matroska"));
+        assert_eq!(media.duration_secs, Some(6120.5));
+        assert_eq!(media.bitrate, Some(25_000_000));
+        assert_eq!(media.audio[0].channels, Some(6));
+        assert!(media.audio[0].default);
+        assert_eq!(media.audio[1].channels, Some(2));
+        assert!(media.audio[1].commentary);
+        assert!(media.subtitles[0].forced);
+        assert!(media.subtitles[1].sdh);
+        assert_eq!(media.chapters.len(), 2);
+        assert_eq!(media.chapters[1].start_secs, 600.0);
+        assert_eq!(media.chapters[1].title.as_deref(), Some("Heist"));
+
+        let tech = media.technical_info();
+        assert_eq!(tech.audio_codec.as_deref(), Some("DTS"));
+        assert_eq!(tech.audio_channels.as_deref(), Some("5.1"));
+        assert_eq!(tech.container.as_deref(), Some("mkv"));
+    }
 }
@@ -1,4 +1,9 @@
+pub mod ffprobe;
+pub mod language;
 pub mod mediainfo;
+pub mod model;
+pub mod probe;
+pub mod screenshots;
 
 use std::path::PathBuf;
 
@@ -0,0 +1,703 @@
+use serde::Serialize;
+
+use std::collections::BTreeSet;
+
+use crate::core::naming::{DolbyVision, HdrFormat, TechnicalInfo};
+
+use super::language;
+
+/// Probed media file: every track, in file order.
+///
+/// Formats are kept as reported by the probe tool; [`MediaInfo::technical_info`] maps them to
+/// scene tokens.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MediaInfo {
+    /// Container format as reported (e.g. "Matroska", "MPEG-4").
+    pub container: Option<String>,
+    pub duration_secs: Option<f64>,
+    /// Overall bitrate, bits per second.
+    pub bitrate: Option<u64>,
+    pub video: Vec<VideoTrack>,
+    pub audio: Vec<AudioTrack>,
+    pub subtitles: Vec<SubtitleTrack>,
+    pub chapters: Vec<Chapter>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct VideoTrack {
+    pub format: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub bit_depth: Option<u32>,
+    /// e.g. "Dolby Vision / SMPTE ST 2086".
+    pub hdr_format: Option<String>,
+    /// Per-layer details aligned with `hdr_format` on " / ", as MediaInfo reports them:
+    /// profile ("dvhe.08 / "), level ("06 / ") and compatibility ("HDR10 / HDR10").
+    pub hdr_profile: Option<String>,
+    pub hdr_level: Option<String>,
+    pub hdr_compatibility: Option<String>,
+    /// Transfer characteristics (e.g. "PQ", "HLG").
+    pub transfer: Option<String>,
+    pub bitrate: Option<u64>,
+    pub frame_rate: Option<f64>,
+    pub default: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AudioTrack {
+    pub format: String,
+    /// Marketing name when the tool reports one (e.g. "Dolby TrueHD with Dolby Atmos").
+    pub commercial_name: Option<String>,
+    /// MediaInfo's `Format_AdditionalFeatures`: codec extensions such as "XLL" (DTS-HD MA),
+    /// "XLL X" (DTS:X), "JOC" (E-AC-3 Atmos) or "16-ch" (TrueHD Atmos).
+    pub additional_features: Option<String>,
+    pub channels: Option<u32>,
+    /// Language as reported (ISO 639 code or name).
+    pub language: Option<String>,
+    pub title: Option<String>,
+    pub bitrate: Option<u64>,
+    pub default: bool,
+    pub forced: bool,
+    pub commentary: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SubtitleTrack {
+    pub format: String,
+    pub language: Option<String>,
+    pub title: Option<String>,
+    pub default: bool,
+    pub forced: bool,
+    /// Subtitles for the deaf and hard of hearing.
+    pub sdh: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Chapter {
+    pub start_secs: f64,
+    pub title: Option<String>,
+}
+
+impl MediaInfo {
+    /// Main video track: the first default-flagged one, else the first. Still images
+    /// (cover art in MP4) are ignored.
+    pub fn primary_video(&self) -> Option<&VideoTrack> {
+        let moving: Vec<&VideoTrack> = self
+            .video
+            .iter()
+            .filter(|v| !is_still_image(&v.format))
+            .collect();
+        moving
+            .iter()
+            .find(|v| v.default)
+            .or_else(|| moving.first())
+            .copied()
+    }
+
+    /// Main audio track, which drives the codec/channels in the scene name: the first
+    /// default-flagged track that isn't commentary, else the first non-commentary track,
+    /// else the first track.
+    pub fn primary_audio(&self) -> Option<&AudioTrack> {
+        self.audio
+            .iter()
+            .find(|a| a.default && !a.commentary)
+            .or_else(|| self.audio.iter().find(|a| !a.commentary))
+            .or_else(|| self.audio.first())
+    }
+
+    /// Flatten into the naming model.
+    ///
+    /// Codec, channels, resolution and bit depth come from the primary tracks; languages are
+    /// collected from every non-commentary audio track and every subtitle track, normalized to
+    /// ISO 639-1 tags with their region ("fr-CA", see [`language::normalize`]); HDR/DV and
+    /// VFI are flagged if any track carries them.
+    pub fn technical_info(&self) -> TechnicalInfo {
+        let mut info = TechnicalInfo {
+            container: self.container.as_deref().and_then(map_container),
+            ..TechnicalInfo::default()
+        };
+
+        if let Some(v) = self.primary_video() {
+            info.resolution = classify_resolution(v.width, v.height);
+            info.video_codec = map_video_codec(&v.format);
+            // Only report 10bit; 8bit is implied
+            if v.bit_depth.is_some_and(|b| b >= 10) {
+                info.bit_depth = Some("10bit".to_string());
+            }
+            let (formats, dv) = hdr_info(v);
+            info.hdr = !formats.is_empty();
+            info.dv = dv.is_some();
+            info.hdr_formats = formats;
+            info.dolby_vision = dv;
+        }
+
+        if let Some(a) = self.primary_audio() {
+            info.audio_codec = map_audio_codec(a);
+            info.audio_channels = a.channels.and_then(map_channels);
+            info.atmos = is_atmos(a);
+        }
+        for a in self.audio.iter().filter(|a| !a.commentary) {
+            if let Some(l) =
+                language::audio_track_language(a.language.as_deref(), a.title.as_deref())
+            {
+                info.audio_languages.insert(l);
+            }
+        }
+        info.has_vfi = self
+            .audio
+            .iter()
+            .filter_map(|a| a.title.as_deref())
+            .any(|t| t.to_ascii_uppercase().contains("VFI"));
+
+        for s in &self.subtitles {
+            if let Some(l) = s.language.as_deref().and_then(language::normalize) {
+                info.subtitle_languages.insert(l);
+            }
+        }
+
+        info
+    }
+
+    /// MediaInfo-style text report of `path`, for seed NFOs when the `mediainfo` CLI is
+    /// missing and another backend probed the file.
+    pub fn text_report(&self, path: &str) -> String {
+        let yes_no = |b: bool| String::from(if b { "Yes" } else { "No" });
+        let mut sections: Vec<(String, Vec<(&str, String)>)> = Vec::new();
+
+        let mut general = vec![("Complete name", path.to_string())];
+        general.extend(self.container.clone().map(|c| ("Format", c)));
+        general.extend(self.duration_secs.map(|d| ("Duration", format_duration(d))));
+        general.extend(
+            self.bitrate
+                .map(|b| ("Overall bit rate", format_bitrate(b))),
+        );
+        sections.push(("General".to_string(), general));
+
+        for (i, v) in self.video.iter().enumerate() {
+            let mut fields = vec![("Format", v.format.clone())];
+            fields.extend(v.width.map(|w| ("Width", format!("{w} pixels"))));
+            fields.extend(v.height.map(|h| ("Height", format!("{h} pixels"))));
+            fields.extend(v.frame_rate.map(|f| ("Frame rate", format!("{f:.3} FPS"))));
+            fields.extend(v.bit_depth.map(|b| ("Bit depth", format!("{b} bits"))));
+            fields.extend(v.hdr_format.clone().map(|h| ("HDR format", h)));
+            fields.extend(v.transfer.clone().map(|t| ("Transfer characteristics", t)));
+            fields.extend(v.bitrate.map(|b| ("Bit rate", format_bitrate(b))));
+            fields.push(("Default", yes_no(v.default)));
+            sections.push((section_title("Video", i, self.video.len()), fields));
+        }
+
+        for (i, a) in self.audio.iter().enumerate() {
+            let mut fields = vec![("Format", a.format.clone())];
+            fields.extend(a.commercial_name.clone().map(|c| ("Commercial name", c)));
+            fields.extend(a.additional_features.clone().map(|f| ("Format/Info", f)));
+            fields.extend(a.channels.map(|c| ("Channel(s)", format!("{c} channels"))));
+            fields.extend(a.bitrate.map(|b| ("Bit rate", format_bitrate(b))));
+            fields.extend(a.title.clone().map(|t| ("Title", t)));
+            fields.extend(a.language.clone().map(|l| ("Language", l)));
+            fields.push(("Default", yes_no(a.default)));
+            fields.push(("Forced", yes_no(a.forced)));
+            sections.push((section_title("Audio", i, self.audio.len()), fields));
+        }
+
+        for (i, s) in self.subtitles.iter().enumerate() {
+            let mut fields = vec![("Format", s.format.clone())];
+            fields.extend(s.title.clone().map(|t| ("Title", t)));
+            fields.extend(s.language.clone().map(|l| ("Language", l)));
+            fields.push(("Default", yes_no(s.default)));
+            fields.push(("Forced", yes_no(s.forced)));
+            sections.push((section_title("Text", i, self.subtitles.len()), fields));
+        }
+
+        let mut out = String::new();
+        for (title, fields) in sections {
+            out.push_str(&title);
+            out.push('\n');
+            for (name, value) in fields {
+                out.push_str(&format!("{name:<41}: {value}\n"));
+            }
+            out.push('\n');
+        }
+        if !self.chapters.is_empty() {
+            out.push_str("Menu\n");
+            for c in &self.chapters {
+                let ms = (c.start_secs * 1000.0).round() as u64;
+                let start = format!(
+                    "{:02}:{:02}:{:02}.{:03}",
+                    ms / 3_600_000,
+                    ms / 60_000 % 60,
+                    ms / 1000 % 60,
+                    ms % 1000
+                );
+                let title = c.title.as_deref().unwrap_or_default();
+                out.push_str(&format!("{start:<41}: {title}\n"));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// "Audio", or "Audio #2" when there are several tracks of the kind.
+fn section_title(kind: &str, index: usize, count: usize) -> String {
+    if count > 1 {
+        format!("{kind} #{}", index + 1)
+    } else {
+        kind.to_string()
+    }
+}
+
+fn format_duration(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{h} h {m} min")
+    } else {
+        format!("{m} min {s} s")
+    }
+}
+
+fn format_bitrate(bps: u64) -> String {
+    if bps >= 1_000_000 {
+        format!("{:.1} Mb/s", bps as f64 / 1_000_000.0)
+    } else {
+        format!("{} kb/s", bps / 1000)
+    }
+}
+
+fn is_still_image(format: &str) -> bool {
+    matches!(
+        format.to_ascii_lowercase().as_str(),
+        "jpeg" | "mjpeg" | "png" | "bmp" | "gif"
+    )
+}
+
+/// Base-layer HDR formats and Dolby Vision metadata of a video track.
+///
+/// Handles both the per-layer fields of recent MediaInfo versions and the older single string
+/// ("Dolby Vision, Version 1.0, dvhe.08.06, BL+RPU, HDR10 compatible / SMPTE ST 2086").
+fn hdr_info(v: &VideoTrack) -> (BTreeSet<HdrFormat>, Option<DolbyVision>) {
+    let layers = |field: &Option<String>| -> Vec<String> {
+        field
+            .as_deref()
+            .unwrap_or_default()
+            .split(" / ")
+            .map(|l| l.trim().to_ascii_lowercase())
+            .collect()
+    };
+    let formats_l = layers(&v.hdr_format);
+    let profiles = layers(&v.hdr_profile);
+    let levels = layers(&v.hdr_level);
+    let compats = layers(&v.hdr_compatibility);
+    let at = |list: &[String], i: usize| list.get(i).cloned().unwrap_or_default();
+
+    let mut formats = BTreeSet::new();
+    let mut dv = None;
+    for (i, layer) in formats_l.iter().enumerate() {
+        let compat = at(&compats, i);
+        if layer.contains("dolby vision") {
+            // "dvhe.08.06" may be embedded in the format string or split across fields
+            let profile_str = format!("{} {layer}", at(&profiles, i));
+            let (profile, level) = dv_profile_level(&profile_str);
+            let level = level.or_else(|| at(&levels, i).parse().ok());
+            let compat_str = if compat.is_empty() {
+                layer.clone()
+            } else {
+                compat.clone()
+            };
+            let compatibility = dv_compatibility(&compat_str);
+            match compatibility {
+                Some("HDR10") => {
+                    formats.insert(HdrFormat::Hdr10);
+                }
+                Some("HLG") => {
+                    formats.insert(HdrFormat::Hlg);
+                }
+                _ => {}
+            }
+            dv = Some(DolbyVision {
+                profile,
+                level,
+                compatibility: compatibility.map(str::to_string),
+            });
+        } else if layer.contains("2094") || layer.contains("hdr10+") || compat.contains("hdr10+") {
+            formats.insert(HdrFormat::Hdr10Plus);
+        } else if layer.contains("2086") || layer.contains("hdr10") {
+            formats.insert(HdrFormat::Hdr10);
+        } else if layer.contains("hlg") {
+            formats.insert(HdrFormat::Hlg);
+        }
+    }
+
+    // No HDR metadata: fall back to the transfer function (PQ without mastering data is HDR10)
+    if formats.is_empty() && dv.is_none() {
+        let tc = v
+            .transfer
+            .as_deref()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if tc.contains("pq") || tc.contains("2084") {
+            formats.insert(HdrFormat::Hdr10);
+        } else if tc.contains("hlg") || tc.contains("b67") {
+            formats.insert(HdrFormat::Hlg);
+        }
+    }
+    // HDR10+ carries HDR10 static metadata: only keep the richer format
+    if formats.contains(&HdrFormat::Hdr10Plus) {
+        formats.remove(&HdrFormat::Hdr10);
+    }
+    (formats, dv)
+}
+
+/// Profile and level from a "dvhe.08.06" / "dav1.10.09" codec string.
+fn dv_profile_level(s: &str) -> (Option<u8>, Option<u8>) {
+    let Some(start) = s
+        .find("dvhe.")
+        .or_else(|| s.find("dav1."))
+        .or_else(|| s.find("dvh1."))
+    else {
+        return (None, None);
+    };
+    let mut parts = s[start + 5..]
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|p| p.parse::<u8>().ok());
+    (parts.next().flatten(), parts.next().flatten())
+}
+
+/// Base layer compatibility of a Dolby Vision layer. Profile 7 reports "Blu-ray", whose base
+/// layer is HDR10.
+fn dv_compatibility(s: &str) -> Option<&'static str> {
+    if s.contains("hdr10") || s.contains("blu-ray") {
+        Some("HDR10")
+    } else if s.contains("hlg") {
+        Some("HLG")
+    } else if s.contains("sdr") {
+        Some("SDR")
+    } else {
+        None
+    }
+}
+
+fn map_container(format: &str) -> Option<String> {
+    let f = format.to_ascii_lowercase();
+    let c = if f.contains("matroska") {
+        "mkv"
+    } else if f.contains("mpeg-4") || f.contains("mp4") || f.contains("mov") {
+        "mp4"
+    } else if f.contains("avi") {
+        "avi"
+    } else if f.contains("mpeg-ts") || f.contains("mpegts") || f == "bdav" {
+        "ts"
+    } else {
+        return None;
+    };
+    Some(c.to_string())
+}
+
+fn classify_resolution(width: Option<u32>, height: Option<u32>) -> Option<String> {
+    // Heuristic with tolerance to handle common crop/encode variations.
+    // Uses either dimension to qualify a bucket; width thresholds are slightly lower
+    // to accommodate cropped widths like 1915, 1904, etc. that are conventionally 1080p.
+    let w_ok = |t: u32| width.map(|w| w >= t).unwrap_or(false);
+    let h_ok = |t: u32| height.map(|h| h >= t).unwrap_or(false);
+
+    let res = if h_ok(2000) || w_ok(3600) {
+        "2160p"
+    } else if h_ok(1320) || w_ok(2400) {
+        "1440p"
+    } else if h_ok(900) || w_ok(1850) {
+        "1080p"
+    } else if h_ok(650) || w_ok(1180) {
+        "720p"
+    } else {
+        "480p"
+    };
+    Some(res.to_string())
+}
+
+fn map_video_codec(format: &str) -> Option<String> {
+    // Canonicalize to encoder-style tokens: x265/x264
+    let f = format.to_ascii_lowercase();
+    if f.contains("x265") || f.contains("hevc") || f.contains("h265") || f.contains("h.265") {
+        Some("x265".to_string())
+    } else if f.contains("x264") || f.contains("avc") || f.contains("h264") || f.contains("h.264") {
+        Some("x264".to_string())
+    }
+    // Hide legacy generic MPEG video tags from the scene name
+    else if f.contains("mpeg-4 visual") || (f.contains("mpeg") && f.contains("video")) {
+        None
+    } else {
+        Some(format.to_string())
+    }
+}
+
+/// Scene audio codec token from the format, commercial name and additional features, so
+/// lossless extensions are kept: "TrueHD", "DTS-HD.MA", "DTS-X", "LPCM"...
+fn map_audio_codec(a: &AudioTrack) -> Option<String> {
+    let f = a.format.to_ascii_lowercase();
+    let commercial = a
+        .commercial_name
+        .as_deref()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let has_feature = |feat: &str| {
+        a.additional_features
+            .as_deref()
+            .is_some_and(|fs| fs.split_whitespace().any(|x| x.eq_ignore_ascii_case(feat)))
+    };
+
+    // TrueHD may carry an AC-3 core ("MLP FBA AC-3"): check it before (E-)AC-3
+    let token = if f.contains("mlp fba") || f.contains("truehd") || commercial.contains("truehd") {
+        "TrueHD"
+    } else if f.contains("e-ac-3")
+        || f.contains("eac3")
+        || f.contains("ddp")
+        || f.contains("dolby digital plus")
+    {
+        "EAC3"
+    } else if f.contains("ac-3") || f.contains("ac3") || f.contains("dolby digital") {
+        "AC3"
+    } else if f.contains("dts") {
+        if commercial.contains("dts:x") || has_feature("X") {
+            "DTS-X"
+        } else if commercial.contains("master audio") || has_feature("XLL") || f.contains("xll") {
+            "DTS-HD.MA"
+        } else if commercial.contains("high resolution") || has_feature("XBR") {
+            "DTS-HD.HRA"
+        } else if commercial.contains("dts-es") || has_feature("ES") {
+            "DTS-ES"
+        } else {
+            "DTS"
+        }
+    } else if f.contains("aac") {
+        "AAC"
+    } else if f.contains("flac") {
+        "FLAC"
+    } else if f.contains("opus") {
+        "Opus"
+    } else if f.contains("pcm") {
+        "LPCM"
+    } else if f.contains("mpeg") {
+        "MPEG"
+    } else {
+        return Some(a.format.clone()).filter(|f| !f.is_empty());
+    };
+    Some(token.to_string())
+}
+
+/// Dolby Atmos object audio, carried by TrueHD ("16-ch") or E-AC-3 ("JOC").
+fn is_atmos(a: &AudioTrack) -> bool {
+    let commercial = a.commercial_name.as_deref().unwrap_or_default();
+    let features = a.additional_features.as_deref().unwrap_or_default();
+    commercial.to_ascii_lowercase().contains("atmos")
+        || features
+            .split_whitespace()
+            .any(|x| x == "JOC" || x == "16-ch")
+}
+
+fn map_channels(ch: u32) -> Option<String> {
+    match ch {
+        8 => Some("7.1".to_string()),
+        7 => Some("6.1".to_string()),
+        6 => Some("5.1".to_string()),
+        2 => Some("2.0".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audio(format: &str, channels: u32, lang: &str) -> AudioTrack {
+        AudioTrack {
+            format: format.to_string(),
+            channels: Some(channels),
+            language: Some(lang.to_string()),
+            ..AudioTrack::default()
+        }
+    }
+
+    #[test]
+    fn primary_audio_is_default_track_not_last() {
+        let mut fr = audio("DTS", 6, "fr");
+        fr.default = true;
+        let mi = MediaInfo {
+            audio: vec![fr, audio("AC-3", 2, "en")],
+            ..MediaInfo::default()
+        };
+        let tech = mi.technical_info();
+        assert_eq!(tech.audio_codec.as_deref(), Some("DTS"));
+        assert_eq!(tech.audio_channels.as_deref(), Some("5.1"));
+        assert_eq!(
+            tech.audio_languages.into_iter().collect::<Vec<_>>(),
+            ["en", "fr"]
+        );
+    }
+
+    #[test]
+    fn commentary_never_drives_the_name() {
+        let mut commentary = audio("AAC", 2, "en");
+        commentary.default = true;
+        commentary.commentary = true;
+        let mi = MediaInfo {
+            audio: vec![commentary, audio("E-AC-3", 6, "en")],
+            ..MediaInfo::default()
+        };
+        let tech = mi.technical_info();
+        assert_eq!(tech.audio_codec.as_deref(), Some("EAC3"));
+        assert_eq!(tech.audio_channels.as_deref(), Some("5.1"));
+    }
+
+    fn codec(format: &str, commercial: Option<&str>, features: Option<&str>) -> (String, bool) {
+        let a = AudioTrack {
+            format: format.to_string(),
+            commercial_name: commercial.map(str::to_string),
+            additional_features: features.map(str::to_string),
+            ..AudioTrack::default()
+        };
+        (map_audio_codec(&a).unwrap(), is_atmos(&a))
+    }
+
+    #[test]
+    fn lossless_and_object_audio() {
+        assert_eq!(
+            codec(
+                "MLP FBA",
+                Some("Dolby TrueHD with Dolby Atmos"),
+                Some("16-ch")
+            ),
+            ("TrueHD".to_string(), true)
+        );
+        assert_eq!(
+            codec("MLP FBA AC-3", Some("Dolby TrueHD"), None),
+            ("TrueHD".to_string(), false)
+        );
+        assert_eq!(
+            codec("DTS", Some("DTS-HD Master Audio"), Some("XLL")),
+            ("DTS-HD.MA".to_string(), false)
+        );
+        assert_eq!(
+            codec("DTS", Some("DTS-HD Master Audio"), Some("XLL X")),
+            ("DTS-X".to_string(), false)
+        );
+        assert_eq!(
+            codec("DTS", None, Some("XBR")),
+            ("DTS-HD.HRA".to_string(), false)
+        );
+        assert_eq!(codec("DTS", None, None), ("DTS".to_string(), false));
+        assert_eq!(
+            codec(
+                "E-AC-3",
+                Some("Dolby Digital Plus with Dolby Atmos"),
+                Some("JOC")
+            ),
+            ("EAC3".to_string(), true)
+        );
+        assert_eq!(codec("FLAC", None, None), ("FLAC".to_string(), false));
+        assert_eq!(codec("Opus", None, None), ("Opus".to_string(), false));
+        assert_eq!(codec("PCM", None, None), ("LPCM".to_string(), false));
+    }
+
+    fn hdr(format: &str, profile: &str, compat: &str, transfer: &str) -> TechnicalInfo {
+        let some = |s: &str| Some(s.to_string()).filter(|s| !s.is_empty());
+        MediaInfo {
+            video: vec![VideoTrack {
+                format: "HEVC".to_string(),
+                hdr_format: some(format),
+                hdr_profile: some(profile),
+                hdr_compatibility: some(compat),
+                transfer: some(transfer),
+                ..VideoTrack::default()
+            }],
+            ..MediaInfo::default()
+        }
+        .technical_info()
+    }
+
+    #[test]
+    fn hdr_formats_and_dolby_vision_profiles() {
+        let t = hdr("SMPTE ST 2086", "", "HDR10", "PQ");
+        assert_eq!(t.hdr_formats, BTreeSet::from([HdrFormat::Hdr10]));
+        assert!(t.hdr && !t.dv);
+
+        let t = hdr(
+            "SMPTE ST 2094 App 4 / SMPTE ST 2086",
+            "",
+            "HDR10+ Profile B / HDR10",
+            "PQ",
+        );
+        assert_eq!(t.hdr_formats, BTreeSet::from([HdrFormat::Hdr10Plus]));
+
+        let t = hdr("", "", "", "HLG");
+        assert_eq!(t.hdr_formats, BTreeSet::from([HdrFormat::Hlg]));
+
+        let t = hdr(
+            "Dolby Vision / SMPTE ST 2086",
+            "dvhe.08 / ",
+            "HDR10 / HDR10",
+            "PQ",
+        );
+        let dv = t.dolby_vision.clone().unwrap();
+        assert_eq!(
+            (dv.profile, dv.compatibility.as_deref()),
+            (Some(8), Some("HDR10"))
+        );
+        assert!(t.hdr && t.dv);
+
+        let t = hdr(
+            "Dolby Vision / SMPTE ST 2086",
+            "dvhe.07 / ",
+            "Blu-ray / HDR10",
+            "PQ",
+        );
+        assert_eq!(t.dolby_vision.unwrap().profile, Some(7));
+        assert_eq!(t.hdr_formats, BTreeSet::from([HdrFormat::Hdr10]));
+
+        // Profile 5 has no HDR10-compatible base layer, despite the PQ transfer
+        let t = hdr("Dolby Vision", "dvhe.05", "", "PQ");
+        let dv = t.dolby_vision.clone().unwrap();
+        assert_eq!((dv.profile, dv.compatibility), (Some(5), None));
+        assert!(t.dv && !t.hdr);
+
+        // Older single-string form
+        let t = hdr(
+            "Dolby Vision, Version 1.0, dvhe.08.06, BL+RPU, HDR10 compatible / SMPTE ST 2086, HDR10 compatible",
+            "",
+            "",
+            "PQ",
+        );
+        let dv = t.dolby_vision.unwrap();
+        assert_eq!((dv.profile, dv.level), (Some(8), Some(6)));
+        assert_eq!(t.hdr_formats, BTreeSet::from([HdrFormat::Hdr10]));
+    }
+
+    #[test]
+    fn cover_art_is_not_the_primary_video() {
+        let mi = MediaInfo {
+            container: Some("MPEG-4".to_string()),
+            video: vec![
+                VideoTrack {
+                    format: "JPEG".to_string(),
+                    width: Some(600),
+                    height: Some(900),
+                    ..VideoTrack::default()
+                },
+                VideoTrack {
+                    format: "HEVC".to_string(),
+                    width: Some(3840),
+                    height: Some(1600),
+                    bit_depth: Some(10),
+                    hdr_format: Some("SMPTE ST 2086".to_string()),
+                    ..VideoTrack::default()
+                },
+            ],
+            ..MediaInfo::default()
+        };
+        let tech = mi.technical_info();
+        assert_eq!(tech.resolution.as_deref(), Some("2160p"));
+        assert_eq!(tech.video_codec.as_deref(), Some("x265"));
+        assert_eq!(tech.bit_depth.as_deref(), Some("10bit"));
+        assert!(tech.hdr);
+        assert_eq!(tech.container.as_deref(), Some("mp4"));
+    }
+}
@@ -0,0 +1,258 @@
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use crate::core::naming::TechnicalInfo;
+use crate::utils::Error;
+
+use super::ffprobe::FfprobeProbe;
+use super::mediainfo::MediainfoProbe;
+use super::model::MediaInfo;
+
+/// External tool used to read media files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeBackend {
+    /// mediainfo, falling back to ffprobe when it fails or is missing.
+    #[default]
+    Auto,
+    Mediainfo,
+    Ffprobe,
+}
+
+/// Reads a media file into the [`MediaInfo`] model.
+pub trait MediaProbe {
+    /// Short identifier used in logs.
+    fn name(&self) -> &str;
+
+    fn probe(&self, path: &str) -> Result<MediaInfo, Error>;
+
+    /// Human-readable report (NFO body, `mediainfo` template variable).
+    fn text_report(&self, path: &str) -> Option<String>;
+}
+
+/// Tries each backend in order until one succeeds.
+pub struct FallbackProbe {
+    probes: Vec<Box<dyn MediaProbe>>,
+}
+
+impl MediaProbe for FallbackProbe {
+    fn name(&self) -> &str {
+        "auto"
+    }
+
+    fn probe(&self, path: &str) -> Result<MediaInfo, Error> {
+        let mut last_err = Error::Other("no media probe configured".to_string());
+        for p in &self.probes {
+            match p.probe(path) {
+                Ok(media) => return Ok(media),
+                Err(e) => {
+                    debug!(target: "seedarr::probe", backend = p.name(), path = %path, "Probe failed: {e}");
+                    last_err = e;
+                }
+            }
+        }
+        Err(last_err)
+    }
+
+    fn text_report(&self, path: &str) -> Option<String> {
+        self.probes.iter().find_map(|p| p.text_report(path))
+    }
+}
+
+/// Writes seed NFOs: `report`'s text report (the `mediainfo` CLI), else a MediaInfo-style
+/// report rendered from what `probe` finds, so hosts with only ffprobe still get NFOs.
+pub struct NfoProbe {
+    report: Box<dyn MediaProbe>,
+    probe: Box<dyn MediaProbe>,
+}
+
+impl MediaProbe for NfoProbe {
+    fn name(&self) -> &str {
+        "nfo"
+    }
+
+    fn probe(&self, path: &str) -> Result<MediaInfo, Error> {
+        self.probe.probe(path)
+    }
+
+    fn text_report(&self, path: &str) -> Option<String> {
+        self.report.text_report(path).or_else(|| {
+            let media = self.probe.probe(path).ok()?;
+            debug!(
+                "No {} report for '{}': rendering the {} probe's",
+                self.report.name(),
+                path,
+                self.probe.name()
+            );
+            Some(media.text_report(path))
+        })
+    }
+}
+
+/// NFO writer for `[media]`: the uncached `mediainfo` report, whatever `probe` is, falling
+/// back to the configured backend's model.
+pub fn nfo_from_config(media: &crate::config::MediaConfig) -> Box<dyn MediaProbe> {
+    Box::new(NfoProbe {
+        report: Box::new(MediainfoProbe {
+            enable_cache: false,
+        }),
+        probe: from_config(media),
+    })
+}
+
+pub fn from_config(media: &crate::config::MediaConfig) -> Box<dyn MediaProbe> {
+    let mediainfo = MediainfoProbe {
+        enable_cache: media.enable_mediainfo_cache,
+    };
+    match media.probe {
+        ProbeBackend::Mediainfo => Box::new(mediainfo),
+        ProbeBackend::Ffprobe => Box::new(FfprobeProbe),
+        ProbeBackend::Auto => Box::new(FallbackProbe {
+            probes: vec![Box::new(mediainfo), Box::new(FfprobeProbe)],
+        }),
+    }
+}
+
+/// Probe `path` and flatten it for naming.
+/// Returns an empty `TechnicalInfo` when every backend fails.
+pub fn collect_technical_info(probe: &dyn MediaProbe, path: &str) -> TechnicalInfo {
+    info!(target: "seedarr::probe", path = %path, backend = probe.name(), "Collecting technical info");
+    let media = match probe.probe(path) {
+        Ok(m) => m,
+        Err(e) => {
+            warn!(target: "seedarr::probe", path = %path, "Failed to probe media: {e}");
+            return TechnicalInfo::default();
+        }
+    };
+
+    let info = media.technical_info();
+    info!(target: "seedarr::probe", path = %path, res = ?info.resolution, vcodec = ?info.video_codec, bitdepth = ?info.bit_depth, hdr = info.hdr, dv = info.dv, acodec = ?info.audio_codec, ach = ?info.audio_channels, alangs = ?info.audio_languages, slangs = ?info.subtitle_languages, vfi = info.has_vfi, "Collected technical info summary");
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::media::ffprobe::parse_ffprobe_json;
+    use crate::core::media::mediainfo::parse_mediainfo_json;
+
+    // Captured from the same file: 2160p HEVC 10-bit HDR10, TrueHD 7.1 FR (default),
+    // AC-3 2.0 EN, EN commentary, FR forced + EN SDH subtitles, 3 chapters.
+    const MEDIAINFO_JSON: &str = include_str!("../../../tests/fixtures/probe/mediainfo.json");
+    const FFPROBE_JSON: &str = include_str!("../../../tests/fixtures/probe/ffprobe.json");
+
+    fn fixture(src: &str) -> serde_json::Value {
+        serde_json::from_str(src).unwrap()
+    }
+
+    #[test]
+    fn backends_agree_on_the_technical_model() {
+        let mi = parse_mediainfo_json(&fixture(MEDIAINFO_JSON)).unwrap();
+        let ff = parse_ffprobe_json(&fixture(FFPROBE_JSON)).unwrap();
+
+        let (a, b) = (mi.technical_info(), ff.technical_info());
+        assert_eq!(a.resolution.as_deref(), Some("2160p"));
+        assert_eq!(a.resolution, b.resolution);
+        assert_eq!(a.video_codec, b.video_codec);
+        assert_eq!(a.bit_depth, b.bit_depth);
+        assert_eq!((a.hdr, a.dv), (b.hdr, b.dv));
+        assert_eq!(a.hdr_formats, b.hdr_formats);
+        assert_eq!(a.audio_codec.as_deref(), Some("TrueHD"));
+        assert_eq!(a.audio_codec, b.audio_codec);
+        assert!(a.atmos && b.atmos);
+        assert_eq!(a.audio_channels.as_deref(), Some("7.1"));
+        assert_eq!(a.audio_channels, b.audio_channels);
+        assert_eq!(a.audio_languages, b.audio_languages);
+        assert_eq!(a.subtitle_languages, b.subtitle_languages);
+        assert_eq!(a.container, b.container);
+    }
+
+    #[test]
+    fn backends_agree_on_track_details() {
+        let mi = parse_mediainfo_json(&fixture(MEDIAINFO_JSON)).unwrap();
+        let ff = parse_ffprobe_json(&fixture(FFPROBE_JSON)).unwrap();
+
+        for m in [&mi, &ff] {
+            assert_eq!(m.video.len(), 1);
+            assert_eq!(m.audio.len(), 3);
+            assert!(m.audio[0].default);
+            assert!(m.audio[2].commentary);
+            assert_eq!(m.subtitles.len(), 2);
+            assert!(m.subtitles[0].forced);
+            assert!(m.subtitles[1].sdh);
+            assert_eq!(m.chapters.len(), 3);
+            assert_eq!(m.chapters[1].title.as_deref(), Some("The Heist"));
+            assert_eq!(m.duration_secs.map(f64::round), Some(10260.0));
+        }
+        assert_eq!(mi.audio[0].format, ff.audio[0].format);
+        assert_eq!(mi.subtitles[0].format, ff.subtitles[0].format);
+    }
+
+    struct Failing;
+
+    impl MediaProbe for Failing {
+        fn name(&self) -> &str {
+            "failing"
+        }
+        fn probe(&self, _: &str) -> Result<MediaInfo, Error> {
+            Err(Error::Other("not installed".to_string()))
+        }
+        fn text_report(&self, _: &str) -> Option<String> {
+            None
+        }
+    }
+
+    struct Fixed;
+
+    impl MediaProbe for Fixed {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+        fn probe(&self, _: &str) -> Result<MediaInfo, Error> {
+            parse_ffprobe_json(&fixture(FFPROBE_JSON))
+                .ok_or_else(|| Error::Other("bad fixture".to_string()))
+        }
+        fn text_report(&self, _: &str) -> Option<String> {
+            Some("report".to_string())
+        }
+    }
+
+    #[test]
+    fn nfos_are_rendered_from_the_model_without_a_report() {
+        let nfo = NfoProbe {
+            report: Box::new(Failing),
+            probe: Box::new(Fixed),
+        };
+        let report = nfo.text_report("/movie.mkv").unwrap();
+        assert!(report.starts_with("General\nComplete name"));
+        assert!(report.contains("/movie.mkv"));
+        assert!(report.contains("\nVideo\nFormat                                   : HEVC\n"));
+        assert!(report.contains("\nAudio #1\n"));
+        assert!(report.contains("\nMenu\n"));
+
+        let nfo = NfoProbe {
+            report: Box::new(Fixed),
+            probe: Box::new(Failing),
+        };
+        assert_eq!(nfo.text_report("/movie.mkv").as_deref(), Some("report"));
+        let nfo = NfoProbe {
+            report: Box::new(Failing),
+            probe: Box::new(Failing),
+        };
+        assert_eq!(nfo.text_report("/movie.mkv"), None);
+    }
+
+    #[test]
+    fn fallback_uses_the_next_backend() {
+        let probe = FallbackProbe {
+            probes: vec![Box::new(Failing), Box::new(Fixed)],
+        };
+        assert!(probe.probe("/movie.mkv").is_ok());
+        assert_eq!(probe.text_report("/movie.mkv").as_deref(), Some("report"));
+
+        let probe = FallbackProbe {
+            probes: vec![Box::new(Failing)],
+        };
+        assert!(probe.probe("/movie.mkv").is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tracing::{debug, info, warn};
+
+/// Capture point in a video.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Timestamp {
+    /// Fraction of the duration, in `[0, 1]`.
+    Fraction(f64),
+    Seconds(f64),
+}
+
+impl Timestamp {
+    /// Parse "25%", "HH:MM:SS", "MM:SS" or plain seconds ("600", "12.5").
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if let Some(pct) = s.strip_suffix('%') {
+            let pct: f64 = pct.trim().parse().ok()?;
+            return (0.0..=100.0)
+                .contains(&pct)
+                .then_some(Self::Fraction(pct / 100.0));
+        }
+
+        let mut secs = 0.0;
+        let parts: Vec<&str> = s.split(':').collect();
+        if parts.len() > 3 {
+            return None;
+        }
+        for part in parts {
+            let v: f64 = part.parse().ok()?;
+            if v < 0.0 {
+                return None;
+            }
+            secs = secs * 60.0 + v;
+        }
+        Some(Self::Seconds(secs))
+    }
+
+    /// Offset in seconds, kept inside the video. `None` when a fraction is used without a
+    /// known duration.
+    pub fn resolve(self, duration: Option<f64>) -> Option<f64> {
+        match (self, duration) {
+            (Self::Fraction(f), Some(d)) => Some(f * d),
+            (Self::Fraction(_), None) => None,
+            (Self::Seconds(s), Some(d)) if s >= d => None,
+            (Self::Seconds(s), _) => Some(s),
+        }
+    }
+}
+
+/// Video duration in seconds, via ffprobe.
+fn probe_duration(video: &Path) -> Option<f64> {
+    let out = Command::new("ffprobe")
+        .args(["-v", "error", "-show_entries", "format=duration"])
+        .args(["-of", "default=noprint_wrappers=1:nokey=1"])
+        .arg(video)
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&out.stdout).trim().parse().ok()
+}
+
+/// Capture one PNG per timestamp into `out_dir` as `01.png`, `02.png`...
+///
+/// Existing files are reused. Returns the screenshots available, in order; empty when ffmpeg
+/// is not installed.
+pub fn capture_screenshots(video: &Path, out_dir: &Path, timestamps: &[Timestamp]) -> Vec<PathBuf> {
+    let targets: Vec<(Timestamp, PathBuf)> = timestamps
+        .iter()
+        .enumerate()
+        .map(|(i, ts)| (*ts, out_dir.join(format!("{:02}.png", i + 1))))
+        .collect();
+    if targets.iter().all(|(_, p)| p.exists()) {
+        debug!(target: "seedarr::screenshots", dir = %out_dir.display(), "Screenshots already captured");
+        return targets.into_iter().map(|(_, p)| p).collect();
+    }
+
+    if let Err(e) = std::fs::create_dir_all(out_dir) {
+        warn!(target: "seedarr::screenshots", dir = %out_dir.display(), "Failed to create screenshots directory: {e}");
+        return Vec::new();
+    }
+
+    let duration = probe_duration(video);
+    let mut captured = Vec::new();
+    for (ts, out) in targets {
+        if out.exists() {
+            captured.push(out);
+            continue;
+        }
+        let Some(offset) = ts.resolve(duration) else {
+            warn!(target: "seedarr::screenshots", path = %video.display(), "Skipping screenshot at {ts:?}: outside the video or duration unknown");
+            continue;
+        };
+
+        // `-ss` before `-i` seeks on keyframes: fast even for large remuxes.
+        let status = Command::new("ffmpeg")
+            .args(["-hide_banner", "-loglevel", "error", "-y"])
+            .arg("-ss")
+            .arg(format!("{offset:.3}"))
+            .arg("-i")
+            .arg(video)
+            .args(["-frames:v", "1"])
+            .arg(&out)
+            .output();
+        match status {
+            Ok(o) if o.status.success() && out.exists() => captured.push(out),
+            Ok(o) => warn!(
+                target: "seedarr::screenshots",
+                path = %video.display(),
+                "ffmpeg failed at {offset:.0}s: {}",
+                String::from_utf8_lossy(&o.stderr).trim()
+            ),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                warn!(target: "seedarr::screenshots", "ffmpeg not found: skipping screenshots");
+                return Vec::new();
+            }
+            Err(e) => {
+                warn!(target: "seedarr::screenshots", "Failed to spawn ffmpeg: {e}");
+                return Vec::new();
+            }
+        }
+    }
+
+    info!(target: "seedarr::screenshots", path = %video.display(), count = captured.len(), "Captured screenshots");
+    captured
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_resolves_timestamps() {
+        assert_eq!(Timestamp::parse("25%"), Some(Timestamp::Fraction(0.25)));
+        assert_eq!(
+            Timestamp::parse("01:02:03"),
+            Some(Timestamp::Seconds(3723.0))
+        );
+        assert_eq!(Timestamp::parse("10:30"), Some(Timestamp::Seconds(630.0)));
+        assert_eq!(Timestamp::parse("90"), Some(Timestamp::Seconds(90.0)));
+        assert_eq!(Timestamp::parse("150%"), None);
+        assert_eq!(Timestamp::parse("abc"), None);
+
+        assert_eq!(Timestamp::Fraction(0.5).resolve(Some(600.0)), Some(300.0));
+        assert_eq!(Timestamp::Fraction(0.5).resolve(None), None);
+        assert_eq!(Timestamp::Seconds(700.0).resolve(Some(600.0)), None);
+        assert_eq!(Timestamp::Seconds(700.0).resolve(None), Some(700.0));
+    }
+}
@@ -1,10 +1,22 @@
+use super::edition;
+use super::groups;
+use super::parser::parse_scene_name;
+use super::rules;
+use super::transliterate;
 use super::types::{
-    DecisionReason, EpisodeHints, PackHints, RadarrHints, SceneDecision, SceneNameParts,
-    TechnicalInfo, ValidationResult,
+    DecisionReason, EpisodeHints, FinalName, HdrFormat, Origin, PackHints, Provenance, RadarrHints,
+    SceneDecision, SceneNameParts, Source, TechnicalInfo,
 };
+use crate::config::{NamingProfile, Transliteration};
+use crate::core::media::language;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use std::collections::BTreeSet;
 use unicode_normalization::UnicodeNormalization;
 
+static TITLE_END_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^((19|20)\d{2}|\d{3,4}p|4k|s\d{1,2}(e\d{1,4})?)$").unwrap());
+
 fn normalize_tokens_to_scene<S: AsRef<str>>(s: S) -> String {
     // Normalize to NFC so composed letters like "À" are stable.
     // Then replace separators with dots, collapse, strip leading/trailing dots.
@@ -39,7 +51,12 @@ fn normalize_tokens_to_scene<S: AsRef<str>>(s: S) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::normalize_tokens_to_scene;
+    use super::{
+        finalize_scene_name, finalize_scene_name_tagged, language_tag, normalize_tokens_to_scene,
+        propose_scene_name, HdrFormat, NamingProfile, Origin, RadarrHints, Source, TechnicalInfo,
+        Transliteration,
+    };
+    use crate::core::naming::DolbyVision;
 
     #[test]
     fn keeps_accented_letters() {
@@ -48,10 +65,378 @@ mod tests {
             "À.bout.de.souffle"
         );
     }
+
+    fn tag(audio: &[&str], subs: &[&str]) -> Option<String> {
+        let tech = TechnicalInfo {
+            audio_languages: audio.iter().map(|l| l.to_string()).collect(),
+            subtitle_languages: subs.iter().map(|l| l.to_string()).collect(),
+            ..TechnicalInfo::default()
+        };
+        language_tag(&tech)
+    }
+
+    #[test]
+    fn language_tags() {
+        assert_eq!(tag(&["fr"], &[]).as_deref(), Some("VF"));
+        assert_eq!(tag(&["fr-FR"], &[]).as_deref(), Some("VFF"));
+        assert_eq!(tag(&["fr-CA"], &[]).as_deref(), Some("VFQ"));
+        assert_eq!(
+            tag(&["fr", "fr-CA", "en"], &[]).as_deref(),
+            Some("MULTi.VF2")
+        );
+        assert_eq!(tag(&["en", "fr-FR"], &[]).as_deref(), Some("MULTi.VFF"));
+        assert_eq!(tag(&["de", "en"], &["fr"]).as_deref(), Some("MULTi"));
+        assert_eq!(tag(&["en"], &["fr-FR"]).as_deref(), Some("VOSTFR"));
+        assert_eq!(tag(&["en"], &["en"]), None);
+        assert_eq!(tag(&["de"], &[]).as_deref(), Some("GERMAN"));
+        assert_eq!(tag(&["it"], &[]).as_deref(), Some("iTALiAN"));
+        assert_eq!(tag(&[], &["fr"]), None);
+    }
+
+    #[test]
+    fn atmos_follows_the_channels() {
+        let hints = RadarrHints {
+            title: "Dune".to_string(),
+            year: Some(2021),
+            ..RadarrHints::default()
+        };
+        let tech = TechnicalInfo {
+            resolution: Some("2160p".to_string()),
+            video_codec: Some("x265".to_string()),
+            audio_codec: Some("TrueHD".to_string()),
+            audio_channels: Some("7.1".to_string()),
+            atmos: true,
+            ..TechnicalInfo::default()
+        };
+        assert_eq!(
+            propose_scene_name(None, &hints, &tech, None).chosen,
+            "Dune.2021.2160p.TrueHD.7.1.Atmos.x265"
+        );
+    }
+
+    #[test]
+    fn hdr_tokens() {
+        let hints = RadarrHints {
+            title: "Dune".to_string(),
+            year: Some(2021),
+            ..RadarrHints::default()
+        };
+        let name = |formats: &[HdrFormat], dv_profile: Option<u8>| {
+            let tech = TechnicalInfo {
+                hdr: !formats.is_empty(),
+                dv: dv_profile.is_some(),
+                hdr_formats: formats.iter().copied().collect(),
+                dolby_vision: dv_profile.map(|p| DolbyVision {
+                    profile: Some(p),
+                    ..DolbyVision::default()
+                }),
+                ..TechnicalInfo::default()
+            };
+            propose_scene_name(None, &hints, &tech, None).chosen
+        };
+        assert_eq!(name(&[HdrFormat::Hdr10], None), "Dune.2021.HDR");
+        assert_eq!(name(&[HdrFormat::Hdr10Plus], None), "Dune.2021.HDR10+");
+        assert_eq!(name(&[HdrFormat::Hlg], None), "Dune.2021.HLG");
+        assert_eq!(
+            name(&[HdrFormat::Hdr10Plus], Some(8)),
+            "Dune.2021.DV.HDR10+"
+        );
+        assert_eq!(name(&[HdrFormat::Hdr10], Some(7)), "Dune.2021.DV.HDR");
+        assert_eq!(name(&[], Some(5)), "Dune.2021.DV");
+    }
+
+    #[test]
+    fn source_from_quality_fields_and_original_name() {
+        let tech = TechnicalInfo {
+            resolution: Some("1080p".to_string()),
+            ..TechnicalInfo::default()
+        };
+        let hints = |quality: &str, source: Option<Source>| RadarrHints {
+            title: "Dune".to_string(),
+            year: Some(2021),
+            quality: Some(quality.to_string()),
+            source,
+            ..RadarrHints::default()
+        };
+        let name = |h: &RadarrHints, original: Option<&str>| {
+            propose_scene_name(original, h, &tech, None).chosen
+        };
+
+        assert_eq!(
+            name(&hints("Remux-1080p", Some(Source::Remux)), None),
+            "Dune.2021.1080p.REMUX"
+        );
+        assert_eq!(
+            name(&hints("WEBRip-1080p", None), None),
+            "Dune.2021.1080p.WEBRip"
+        );
+        // Streaming service comes from the original name
+        assert_eq!(
+            name(
+                &hints("WEBDL-1080p", Some(Source::WebDl)),
+                Some("Dune.2021.1080p.NF.WEB-DL.DDP5.1.x264-GRP")
+            ),
+            "Dune.2021.1080p.NF.WEB-DL.x264-GRP"
+        );
+        // Resolution mismatch: the quality is wrong, keep the source out
+        assert_eq!(
+            name(&hints("WEBDL-720p", Some(Source::WebDl)), None),
+            "Dune.2021.1080p"
+        );
+    }
+
+    #[test]
+    fn original_name_fills_gaps_with_provenance() {
+        let hints = RadarrHints {
+            title: "Heat".to_string(),
+            year: Some(1995),
+            quality: Some("Bluray-1080p".to_string()),
+            ..RadarrHints::default()
+        };
+        let original = Some("Heat.1995.1080p.BluRay.DTS-HD.MA.5.1.x264-FGT");
+
+        // MediaInfo wins where it has an answer
+        let tech = TechnicalInfo {
+            resolution: Some("1080p".to_string()),
+            video_codec: Some("x265".to_string()),
+            ..TechnicalInfo::default()
+        };
+        let d = propose_scene_name(original, &hints, &tech, None);
+        assert_eq!(d.chosen, "Heat.1995.1080p.BluRay.DTS-HD.MA.5.1.x265-FGT");
+        assert_eq!(d.provenance.get("title"), Some(Origin::Arr));
+        assert_eq!(d.provenance.get("resolution"), Some(Origin::MediaInfo));
+        assert_eq!(d.provenance.get("video_codec"), Some(Origin::MediaInfo));
+        assert_eq!(d.provenance.get("source"), Some(Origin::QualityName));
+        assert_eq!(d.provenance.get("audio_codec"), Some(Origin::OriginalName));
+        assert_eq!(
+            d.provenance.get("release_group"),
+            Some(Origin::OriginalName)
+        );
+
+        // Radarr's release group beats the parsed one
+        let hints = RadarrHints {
+            release_group: Some("Other".to_string()),
+            ..hints
+        };
+        let d = propose_scene_name(original, &hints, &tech, None);
+        assert!(d.chosen.ends_with("-Other"), "{}", d.chosen);
+        assert_eq!(d.provenance.get("release_group"), Some(Origin::Arr));
+
+        // A bare DTS-HD may be HRA: it isn't taken for lossless MA
+        let original = Some("Heat.1995.1080p.BluRay.DTS-HD.5.1.x264-FGT");
+        let d = propose_scene_name(original, &hints, &tech, None);
+        assert_eq!(d.chosen, "Heat.1995.1080p.BluRay.DTS-HD.5.1.x265-Other");
+    }
+
+    #[test]
+    fn ascii_profiles_transliterate_titles() {
+        let tech = TechnicalInfo {
+            resolution: Some("1080p".to_string()),
+            ..TechnicalInfo::default()
+        };
+        let ascii = NamingProfile {
+            transliterate: Transliteration::Ascii,
+            ..NamingProfile::default()
+        };
+        for (title, keep, translit) in [
+            (
+                "À bout de souffle",
+                "À.bout.de.souffle.1960.1080p",
+                "A.bout.de.souffle.1960.1080p",
+            ),
+            ("Брат", "Брат.1960.1080p", "Brat.1960.1080p"),
+            (
+                "となりのトトロ",
+                "となりのトトロ.1960.1080p",
+                "Tonarinototoro.1960.1080p",
+            ),
+        ] {
+            let hints = RadarrHints {
+                title: title.to_string(),
+                year: Some(1960),
+                ..RadarrHints::default()
+            };
+            let d = propose_scene_name(None, &hints, &tech, None);
+            assert_eq!(
+                finalize_scene_name(&d, false, &NamingProfile::default()),
+                keep
+            );
+            assert_eq!(finalize_scene_name(&d, false, &ascii), translit);
+        }
+
+        // Kanji have no spelling: the title is kept whole, not cut down to its kana
+        let hints = RadarrHints {
+            title: "千と千尋の神隠し".to_string(),
+            year: Some(2001),
+            ..RadarrHints::default()
+        };
+        let d = propose_scene_name(None, &hints, &tech, None);
+        assert_eq!(
+            finalize_scene_name(&d, false, &ascii),
+            "千と千尋の神隠し.2001.1080p"
+        );
+    }
+
+    #[test]
+    fn long_names_are_shortened_to_the_profile_limit() {
+        let tech = TechnicalInfo {
+            resolution: Some("2160p".to_string()),
+            video_codec: Some("x265".to_string()),
+            audio_codec: Some("TrueHD".to_string()),
+            audio_channels: Some("7.1".to_string()),
+            atmos: true,
+            ..TechnicalInfo::default()
+        };
+        let hints = RadarrHints {
+            title: "Les Aventures Extraordinaires d'Adèle Blanc-Sec".to_string(),
+            year: Some(2010),
+            quality: Some("Remux-2160p".to_string()),
+            ..RadarrHints::default()
+        };
+        let d = propose_scene_name(None, &hints, &tech, None);
+        assert_eq!(
+            d.chosen,
+            "Les.Aventures.Extraordinaires.d.Adèle.Blanc.Sec.2010.2160p.REMUX.TrueHD.7.1.Atmos.x265"
+        );
+        let profile = |max_length| NamingProfile {
+            max_length,
+            ..NamingProfile::default()
+        };
+
+        assert_eq!(finalize_scene_name(&d, false, &profile(250)), d.chosen);
+        assert_eq!(
+            finalize_scene_name(&d, true, &profile(250)),
+            format!("{}-NoTag", d.chosen)
+        );
+        // Extras go first
+        assert_eq!(
+            finalize_scene_name(&d, false, &profile(80)),
+            "Les.Aventures.Extraordinaires.d.Adèle.Blanc.Sec.2010.2160p.REMUX.TrueHD.x265"
+        );
+        // Then the longest title tokens; "è" is two bytes
+        let short = finalize_scene_name(&d, false, &profile(60));
+        assert_eq!(
+            short,
+            "Les.Aventur.Extraor.d.Adèle.Blanc.Sec.2010.2160p.REMUX.x265"
+        );
+        assert!(short.len() <= 60);
+        // A collision tag still fits
+        let tagged = finalize_scene_name_tagged(&d, true, &profile(80), "TMDB1234");
+        assert_eq!(
+            tagged,
+            "Les.Aventures.Extraordin.d.Adèle.Blanc.Sec.2010.2160p.REMUX.x265.TMDB1234-NoTag"
+        );
+        assert!(tagged.len() <= 80);
+    }
+
+    #[test]
+    fn shortening_keeps_the_release_group() {
+        let tech = TechnicalInfo {
+            resolution: Some("2160p".to_string()),
+            video_codec: Some("x265".to_string()),
+            audio_codec: Some("TrueHD".to_string()),
+            audio_channels: Some("7.1".to_string()),
+            atmos: true,
+            ..TechnicalInfo::default()
+        };
+        let hints = RadarrHints {
+            title: "Les Aventures Extraordinaires d'Adèle Blanc-Sec".to_string(),
+            year: Some(2010),
+            quality: Some("Remux-2160p".to_string()),
+            release_group: Some("FLUX".to_string()),
+            ..RadarrHints::default()
+        };
+        let d = propose_scene_name(None, &hints, &tech, None);
+        let profile = |max_length| NamingProfile {
+            max_length,
+            ..NamingProfile::default()
+        };
+
+        // Abbreviated title tokens are dropped from the end
+        assert_eq!(
+            finalize_scene_name(&d, false, &profile(30)),
+            "L.A.2010.2160p.REMUX.x265-FLUX"
+        );
+        // The body alone is over the limit: whole tokens are cut, the group stays
+        assert_eq!(
+            finalize_scene_name(&d, false, &profile(20)),
+            "L.2010.2160p-FLUX"
+        );
+    }
+
+    #[test]
+    fn editions_from_radarr_then_original_name() {
+        let tech = TechnicalInfo {
+            resolution: Some("1080p".to_string()),
+            ..TechnicalInfo::default()
+        };
+        let hints = RadarrHints {
+            title: "Blade Runner".to_string(),
+            year: Some(1982),
+            edition: Some("The Final Cut".to_string()),
+            ..RadarrHints::default()
+        };
+        let original = Some("Blade.Runner.1982.IMAX.REPACK.1080p.BluRay.x264-GRP");
+        let d = propose_scene_name(original, &hints, &tech, None);
+        assert_eq!(
+            d.chosen,
+            "Blade.Runner.1982.Final.Cut.1080p.BluRay.Repack.x264-GRP"
+        );
+        assert_eq!(d.provenance.get("edition"), Some(Origin::Arr));
+
+        let hints = RadarrHints {
+            edition: None,
+            ..hints
+        };
+        let d = propose_scene_name(original, &hints, &tech, None);
+        assert!(
+            d.chosen.starts_with("Blade.Runner.1982.IMAX.1080p"),
+            "{}",
+            d.chosen
+        );
+        assert_eq!(d.provenance.get("edition"), Some(Origin::OriginalName));
+
+        // Title words are not tags
+        let hints = RadarrHints {
+            title: "The Proper Extended Family".to_string(),
+            year: Some(2020),
+            ..RadarrHints::default()
+        };
+        let d = propose_scene_name(
+            Some("The.Proper.Extended.Family.2020.1080p.WEB-DL.x264"),
+            &hints,
+            &tech,
+            None,
+        );
+        assert_eq!(
+            d.chosen,
+            "The.Proper.Extended.Family.2020.1080p.WEB-DL.x264"
+        );
+
+        // No year, resolution or episode marker: nothing tells the title from tags
+        let hints = RadarrHints {
+            title: "Extended Family".to_string(),
+            ..RadarrHints::default()
+        };
+        let d = propose_scene_name(Some("Extended.Family.Proper"), &hints, &tech, None);
+        assert_eq!(d.chosen, "Extended.Family.1080p");
+    }
 }
 
 // removed: pick helper no longer needed
 
+/// Audio codec token of an original name, spelled the way MediaInfo codecs are mapped.
+fn canonicalize_audio_codec(s: &str) -> String {
+    match s.to_ascii_uppercase().as_str() {
+        "DDP" | "EAC3" => "EAC3".to_string(),
+        "PCM" | "LPCM" => "LPCM".to_string(),
+        "TRUEHD" => "TrueHD".to_string(),
+        "OPUS" => "Opus".to_string(),
+        u if u.starts_with("DTS") || matches!(u, "AC3" | "AAC" | "FLAC") => u.to_string(),
+        _ => s.to_string(),
+    }
+}
+
 fn canonicalize_video_codec(s: &str) -> String {
     let l = s.to_ascii_lowercase();
     if l.contains("265") {
@@ -63,28 +448,69 @@ fn canonicalize_video_codec(s: &str) -> String {
     }
 }
 
+/// French dub tag from the French audio variants: VFF (France), VFQ (Québec), VF2 (both) or
+/// plain VF when the variant is unknown.
+fn french_dub_tag(french: &[&str]) -> &'static str {
+    let quebec = french.contains(&"fr-CA");
+    let france = french.iter().any(|l| *l != "fr-CA");
+    let explicit_france = french.iter().any(|l| *l != "fr-CA" && *l != "fr");
+    match (quebec, france) {
+        (true, true) => "VF2",
+        (true, false) => "VFQ",
+        _ if explicit_france => "VFF",
+        _ => "VF",
+    }
+}
+
 fn language_tag(tech: &TechnicalInfo) -> Option<String> {
-    // MULTi.VF if multiple audio languages; VF if only French; VOSTFR if only English (assume FR subs)
-    let count = tech.audio_languages.len();
-    if count == 0 {
+    // French audio: VF/VFF/VFQ/VF2, prefixed with MULTi when other languages are present.
+    // No French audio: MULTi for several languages, VOSTFR with French subtitles, else the
+    // scene name of the language (GERMAN, iTALiAN...). English alone needs no tag.
+    if tech.audio_languages.is_empty() {
         return None;
     }
-    if count > 1 {
-        return Some("MULTi.VF".to_string());
+    let french: Vec<&str> = tech
+        .audio_languages
+        .iter()
+        .map(String::as_str)
+        .filter(|l| language::base(l) == "fr")
+        .collect();
+    let others: BTreeSet<&str> = tech
+        .audio_languages
+        .iter()
+        .map(|l| language::base(l))
+        .filter(|b| *b != "fr")
+        .collect();
+
+    if !french.is_empty() {
+        let vf = french_dub_tag(&french);
+        return Some(if others.is_empty() {
+            vf.to_string()
+        } else {
+            format!("MULTi.{vf}")
+        });
     }
-    let only = tech.audio_languages.iter().next().unwrap().as_str();
-    if only == "fr" {
-        Some("VF".to_string())
-    } else if only == "en" {
-        Some("VOSTFR".to_string())
-    } else {
+    if others.len() > 1 {
+        return Some("MULTi".to_string());
+    }
+    let has_french_subs = tech
+        .subtitle_languages
+        .iter()
+        .any(|l| language::base(l) == "fr");
+    if has_french_subs {
+        return Some("VOSTFR".to_string());
+    }
+    let only = others.into_iter().next()?;
+    if only == "en" {
         None
+    } else {
+        language::scene_token(only)
     }
 }
 
 fn sanitize_release_group<S: AsRef<str>>(s: S) -> String {
-    // Remove spaces and any non-alphanumeric characters
-    s.as_ref()
+    // Drop bracketed junk ("GRP[rarbg]"), then spaces and any non-alphanumeric characters
+    groups::strip_brackets(s.as_ref())
         .chars()
         .filter(|c| c.is_ascii_alphanumeric())
         .collect()
@@ -109,52 +535,71 @@ fn infer_resolution_from_quality(q: &Option<String>) -> Option<String> {
     None
 }
 
-fn infer_source_from_quality(q: &Option<String>) -> Option<String> {
-    if let Some(qs) = q {
-        let ql = qs.to_ascii_lowercase();
-        if ql.contains("web-dl") || ql.contains("webrip") || ql.contains("web") {
-            return Some("WEB".to_string());
-        }
-        if ql.contains("blu") {
-            return Some("BluRay".to_string());
+/// Resolution from MediaInfo (quality as fallback); source from the *arr quality fields, else
+/// the quality name, else the original scene name. A resolution mismatch means the quality
+/// can't be trusted: the source is dropped rather than guessed.
+fn apply_resolution_and_source(
+    parts: &mut SceneNameParts,
+    quality: &Option<String>,
+    source: Option<Source>,
+    tech: &TechnicalInfo,
+    parsed: Option<&SceneNameParts>,
+    prov: &mut Provenance,
+) {
+    let inferred_res = infer_resolution_from_quality(quality);
+    parts.resolution = tech.resolution.clone().or(inferred_res.clone());
+    if tech.resolution.is_some() {
+        prov.set("resolution", Origin::MediaInfo);
+    } else if parts.resolution.is_some() {
+        prov.set("resolution", Origin::QualityName);
+    }
+    if let (Some(tr), Some(ir)) = (tech.resolution.as_ref(), inferred_res.as_ref()) {
+        if tr != ir {
+            return; // mismatch: trust MediaInfo, omit source
         }
     }
-    None
-}
-
-fn build_parts_from(hints: &RadarrHints, tech: &TechnicalInfo) -> SceneNameParts {
-    let mut parts = SceneNameParts::default();
 
-    // Title + year
-    let title = normalize_tokens_to_scene(&hints.title);
-    if !title.is_empty() {
-        parts.title_tokens = title.split('.').map(|s| s.to_string()).collect();
+    let parsed_source = parsed
+        .and_then(|p| p.source.as_deref())
+        .and_then(Source::from_token);
+    let (source, origin) = match (
+        source,
+        quality.as_deref().and_then(Source::from_quality_name),
+    ) {
+        // The *arr "television" source covers both; the quality name tells SD from HD
+        (Some(Source::Hdtv), Some(Source::Sdtv)) => (Some(Source::Sdtv), Origin::QualityName),
+        (Some(s), _) => (Some(s), Origin::Arr),
+        (None, Some(s)) => (Some(s), Origin::QualityName),
+        (None, None) => (None, Origin::OriginalName),
+    };
+    let (source, origin) = match (source, parsed_source) {
+        (None, p) => (p, Origin::OriginalName),
+        // The original name knows whether a WEB release is DL or Rip
+        (Some(Source::Web), Some(p)) if p.is_web() => (Some(p), Origin::OriginalName),
+        (s, _) => (s, origin),
+    };
+    parts.source = source.map(|s| s.token().to_string());
+    if parts.source.is_some() {
+        prov.set("source", origin);
     }
-    parts.year = hints.year;
-
-    // Language tag
-    parts.language_tag = language_tag(tech);
-
-    // Resolution + source: prefer MediaInfo resolution; drop source if mismatch with quality-inferred resolution
-    let inferred_res = infer_resolution_from_quality(&hints.quality);
-    let inferred_source = infer_source_from_quality(&hints.quality);
-
-    parts.resolution = tech.resolution.clone().or(inferred_res.clone());
-    parts.source = inferred_source;
-
-    if let (Some(tr), Some(ir)) = (tech.resolution.as_ref(), inferred_res.as_ref()) {
-        if tr != ir {
-            parts.resolution = Some(tr.clone());
-            parts.source = None; // mismatch: trust MediaInfo, omit source
+    if source.is_some_and(Source::is_web) {
+        parts.streaming_service = parsed.and_then(|p| p.streaming_service.clone());
+        if parts.streaming_service.is_some() {
+            prov.set("streaming_service", Origin::OriginalName);
         }
     }
+}
 
-    // Technical extras
+/// Technical fields, all from MediaInfo: language tag, HDR/DV, bit depth, codecs, channels, VFI.
+fn apply_technical(parts: &mut SceneNameParts, tech: &TechnicalInfo, prov: &mut Provenance) {
+    parts.language_tag = language_tag(tech);
     parts.hdr = tech.hdr;
     parts.dv = tech.dv;
+    parts.hdr_formats = tech.hdr_formats.clone();
     parts.bit_depth = tech.bit_depth.clone();
     parts.audio_codec = tech.audio_codec.clone();
     parts.audio_channels = tech.audio_channels.clone();
+    parts.atmos = tech.atmos;
     parts.video_codec = tech
         .video_codec
         .as_ref()
@@ -165,13 +610,155 @@ fn build_parts_from(hints: &RadarrHints, tech: &TechnicalInfo) -> SceneNameParts
         parts.extra_tags.insert("VFI".to_string());
     }
 
-    // Release group from Radarr, sanitized (no spaces, no special chars)
-    parts.release_group = hints
-        .release_group
-        .as_ref()
-        .map(|g| sanitize_release_group(g))
-        .filter(|g| !g.is_empty());
+    for (field, set) in [
+        ("language", parts.language_tag.is_some()),
+        ("hdr", parts.hdr || parts.dv),
+        ("bit_depth", parts.bit_depth.is_some()),
+        ("audio_codec", parts.audio_codec.is_some()),
+        ("audio_channels", parts.audio_channels.is_some()),
+        ("atmos", parts.atmos),
+        ("video_codec", parts.video_codec.is_some()),
+    ] {
+        if set {
+            prov.set(field, Origin::MediaInfo);
+        }
+    }
+}
+
+/// Release group from Radarr/Sonarr, sanitized (no spaces, no special chars).
+fn apply_release_group(parts: &mut SceneNameParts, group: Option<&str>, prov: &mut Provenance) {
+    parts.release_group = group.map(sanitize_release_group).filter(|g| !g.is_empty());
+    if parts.release_group.is_some() {
+        prov.set("release_group", Origin::Arr);
+    }
+}
 
+/// Fill the gaps left by MediaInfo and Radarr/Sonarr with the parsed original name. MediaInfo
+/// always wins for technical fields: the original name only counts when the probe reported
+/// nothing for that part of the file (e.g. MediaInfo missing or failing).
+fn merge_parsed(
+    parts: &mut SceneNameParts,
+    tech: &TechnicalInfo,
+    parsed: &SceneNameParts,
+    prov: &mut Provenance,
+) {
+    let mut fill = |field: &'static str, slot: &mut Option<String>, value: Option<String>| {
+        if slot.is_none() && value.is_some() {
+            *slot = value;
+            prov.set(field, Origin::OriginalName);
+        }
+    };
+
+    fill(
+        "resolution",
+        &mut parts.resolution,
+        parsed.resolution.as_deref().map(str::to_ascii_lowercase),
+    );
+    fill(
+        "video_codec",
+        &mut parts.video_codec,
+        parsed.video_codec.as_deref().map(canonicalize_video_codec),
+    );
+    fill(
+        "audio_codec",
+        &mut parts.audio_codec,
+        parsed.audio_codec.as_deref().map(canonicalize_audio_codec),
+    );
+    fill(
+        "audio_channels",
+        &mut parts.audio_channels,
+        parsed.audio_channels.clone(),
+    );
+    fill(
+        "release_group",
+        &mut parts.release_group,
+        parsed
+            .release_group
+            .as_deref()
+            .map(sanitize_release_group)
+            .filter(|g| !g.is_empty()),
+    );
+
+    // Flags and video properties only when the probe saw no video at all
+    if tech.video_codec.is_none() && tech.resolution.is_none() {
+        fill(
+            "bit_depth",
+            &mut parts.bit_depth,
+            parsed.bit_depth.as_deref().map(str::to_ascii_lowercase),
+        );
+        if parsed.hdr || parsed.dv {
+            parts.hdr |= parsed.hdr;
+            parts.dv |= parsed.dv;
+            prov.set("hdr", Origin::OriginalName);
+        }
+    }
+    if tech.audio_codec.is_none() && parsed.atmos && !parts.atmos {
+        parts.atmos = true;
+        prov.set("atmos", Origin::OriginalName);
+    }
+    if tech.audio_languages.is_empty() && parts.language_tag.is_none() {
+        parts.language_tag = language_tag_from_tokens(&parsed.extra_tags);
+        if parts.language_tag.is_some() {
+            prov.set("language", Origin::OriginalName);
+        }
+    }
+}
+
+/// Rebuild a language tag from the tokens of an original name (when no audio was probed).
+fn language_tag_from_tokens(tokens: &BTreeSet<String>) -> Option<String> {
+    let has = |t: &str| tokens.iter().any(|x| x.eq_ignore_ascii_case(t));
+    let french = ["VF2", "VFF", "VFQ", "VFI", "VF"]
+        .into_iter()
+        .find(|t| has(t))
+        .map(str::to_string)
+        .or_else(|| has("TRUEFRENCH").then(|| "VFF".to_string()))
+        .or_else(|| has("FRENCH").then(|| "VF".to_string()));
+    let tag = match (has("MULTi"), french) {
+        (true, Some(vf)) => format!("MULTi.{vf}"),
+        (true, None) => "MULTi".to_string(),
+        (false, Some(vf)) => vf,
+        (false, None) if has("VOSTFR") => "VOSTFR".to_string(),
+        (false, None) => tokens
+            .iter()
+            .find_map(|t| language::from_scene_token(t))
+            .and_then(language::scene_token)?,
+    };
+    Some(tag)
+}
+
+fn title_tokens(title: &str) -> Vec<String> {
+    let title = normalize_tokens_to_scene(title);
+    if title.is_empty() {
+        return Vec::new();
+    }
+    title.split('.').map(|s| s.to_string()).collect()
+}
+
+fn build_parts_from(
+    hints: &RadarrHints,
+    tech: &TechnicalInfo,
+    parsed: Option<&SceneNameParts>,
+    prov: &mut Provenance,
+) -> SceneNameParts {
+    let mut parts = SceneNameParts {
+        title_tokens: title_tokens(&hints.title),
+        year: hints.year,
+        ..SceneNameParts::default()
+    };
+    prov.set("title", Origin::Arr);
+    if parts.year.is_some() {
+        prov.set("year", Origin::Arr);
+    }
+    if let Some(e) = hints.edition.as_deref() {
+        parts.editions = edition::from_radarr(e);
+        if !parts.editions.is_empty() {
+            prov.set("edition", Origin::Arr);
+        }
+    }
+
+    apply_technical(&mut parts, tech, prov);
+    apply_resolution_and_source(&mut parts, &hints.quality, hints.source, tech, parsed, prov);
+    apply_release_group(&mut parts, hints.release_group.as_deref(), prov);
     parts
 }
 
@@ -206,163 +793,121 @@ fn episode_tag_from_hints(hints: &EpisodeHints) -> Option<String> {
     Some(tag)
 }
 
-fn build_episode_parts_from(hints: &EpisodeHints, tech: &TechnicalInfo) -> SceneNameParts {
-    let mut parts = SceneNameParts::default();
-
-    // Title (series title only)
-    let title = normalize_tokens_to_scene(&hints.series_title);
-    if !title.is_empty() {
-        parts.title_tokens = title.split('.').map(|s| s.to_string()).collect();
-    }
-
-    // Episode tag (SxxEyy / E###)
-    parts.episode_tag = episode_tag_from_hints(hints);
-
-    // Language tag
-    parts.language_tag = language_tag(tech);
-
-    // Resolution + source: prefer MediaInfo resolution; drop source if mismatch with quality-inferred resolution
-    let inferred_res = infer_resolution_from_quality(&hints.quality);
-    let inferred_source = infer_source_from_quality(&hints.quality);
-
-    parts.resolution = tech.resolution.clone().or(inferred_res.clone());
-    parts.source = inferred_source;
-
-    if let (Some(tr), Some(ir)) = (tech.resolution.as_ref(), inferred_res.as_ref()) {
-        if tr != ir {
-            parts.resolution = Some(tr.clone());
-            parts.source = None; // mismatch: trust MediaInfo, omit source
-        }
-    }
-
-    // Technical extras
-    parts.hdr = tech.hdr;
-    parts.dv = tech.dv;
-    parts.bit_depth = tech.bit_depth.clone();
-    parts.audio_codec = tech.audio_codec.clone();
-    parts.audio_channels = tech.audio_channels.clone();
-    parts.video_codec = tech
-        .video_codec
-        .as_ref()
-        .map(|v| canonicalize_video_codec(v));
-
-    if tech.has_vfi {
-        parts.extra_tags.insert("VFI".to_string());
+fn build_episode_parts_from(
+    hints: &EpisodeHints,
+    tech: &TechnicalInfo,
+    parsed: Option<&SceneNameParts>,
+    prov: &mut Provenance,
+) -> SceneNameParts {
+    // Title (series title only) + episode tag (SxxEyy / E###)
+    let mut parts = SceneNameParts {
+        title_tokens: title_tokens(&hints.series_title),
+        episode_tag: episode_tag_from_hints(hints),
+        ..SceneNameParts::default()
+    };
+    prov.set("title", Origin::Arr);
+    if parts.episode_tag.is_some() {
+        prov.set("episode", Origin::Arr);
     }
 
-    parts.release_group = hints
-        .release_group
-        .as_ref()
-        .map(|g| sanitize_release_group(g))
-        .filter(|g| !g.is_empty());
-
+    apply_technical(&mut parts, tech, prov);
+    apply_resolution_and_source(&mut parts, &hints.quality, hints.source, tech, parsed, prov);
+    apply_release_group(&mut parts, hints.release_group.as_deref(), prov);
     parts
 }
 
-fn build_pack_parts_from(hints: &PackHints, tech: &TechnicalInfo) -> SceneNameParts {
-    let mut parts = SceneNameParts::default();
-
-    let title = normalize_tokens_to_scene(&hints.title);
-    if !title.is_empty() {
-        parts.title_tokens = title.split('.').map(|s| s.to_string()).collect();
-    }
-
-    parts.year = hints.year;
-    parts.episode_tag = Some(normalize_tokens_to_scene(&hints.pack_tag));
-    parts.language_tag = language_tag(tech);
-
-    let inferred_res = infer_resolution_from_quality(&hints.quality);
-    let inferred_source = infer_source_from_quality(&hints.quality);
-
-    parts.resolution = tech.resolution.clone().or(inferred_res.clone());
-    parts.source = inferred_source;
-
-    if let (Some(tr), Some(ir)) = (tech.resolution.as_ref(), inferred_res.as_ref()) {
-        if tr != ir {
-            parts.resolution = Some(tr.clone());
-            parts.source = None;
-        }
-    }
-
-    parts.hdr = tech.hdr;
-    parts.dv = tech.dv;
-    parts.bit_depth = tech.bit_depth.clone();
-    parts.audio_codec = tech.audio_codec.clone();
-    parts.audio_channels = tech.audio_channels.clone();
-    parts.video_codec = tech
-        .video_codec
-        .as_ref()
-        .map(|v| canonicalize_video_codec(v));
-
-    if tech.has_vfi {
-        parts.extra_tags.insert("VFI".to_string());
+fn build_pack_parts_from(
+    hints: &PackHints,
+    tech: &TechnicalInfo,
+    parsed: Option<&SceneNameParts>,
+    prov: &mut Provenance,
+) -> SceneNameParts {
+    let mut parts = SceneNameParts {
+        title_tokens: title_tokens(&hints.title),
+        year: hints.year,
+        episode_tag: Some(normalize_tokens_to_scene(&hints.pack_tag)),
+        ..SceneNameParts::default()
+    };
+    prov.set("title", Origin::Arr);
+    prov.set("episode", Origin::Arr);
+    if parts.year.is_some() {
+        prov.set("year", Origin::Arr);
     }
 
-    parts.release_group = hints
-        .release_group
-        .as_ref()
-        .map(|g| sanitize_release_group(g))
-        .filter(|g| !g.is_empty());
-
+    apply_technical(&mut parts, tech, prov);
+    apply_resolution_and_source(&mut parts, &hints.quality, hints.source, tech, parsed, prov);
+    apply_release_group(&mut parts, hints.release_group.as_deref(), prov);
     parts
 }
 
-fn salvage_special_tags(original: Option<&str>) -> BTreeSet<String> {
+/// Special tags of the original scene name: editions and release tags (`HDLight`, `Proper`...).
+/// Matches whole words after the title only, so "The Proper Job" or "Extendedness" don't count.
+fn salvage_special_tags(original: Option<&str>) -> (Vec<&'static str>, BTreeSet<String>) {
     let mut tags: BTreeSet<String> = BTreeSet::new();
     let Some(s) = original else {
-        return tags;
+        return (Vec::new(), tags);
     };
-    let l = s.to_ascii_lowercase();
+    let words = edition::words(s);
+    // The title ends at the first year, resolution or episode marker; without one, every
+    // word may belong to the title
+    let Some(start) = words
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find(|(_, w)| TITLE_END_RE.is_match(w))
+        .map(|(i, _)| i)
+    else {
+        return (Vec::new(), tags);
+    };
+    let words = &words[start..];
+    let has = |w: &str| words.iter().any(|x| x == w);
 
-    // Requested tags
-    if l.contains("imax") {
-        tags.insert("IMAX".to_string());
-    }
-    if l.contains("hdlight") {
+    if has("hdlight") {
         tags.insert("HDLight".to_string());
     }
-    if l.contains("4klight") || l.contains("4k light") {
+    if has("4klight") || words.windows(2).any(|p| p[0] == "4k" && p[1] == "light") {
         tags.insert("4KLight".to_string());
     }
-
-    // Common scene extras
-    if l.contains("unrated") {
-        tags.insert("Unrated".to_string());
-    }
-    if l.contains("extended") {
-        tags.insert("Extended".to_string());
-    }
-    if l.contains("remastered") {
-        tags.insert("Remastered".to_string());
-    }
-    if l.contains("director") && l.contains("cut") {
-        tags.insert("Directors.Cut".to_string());
-    }
-    if l.contains("theatrical cut") {
-        tags.insert("Theatrical.Cut".to_string());
-    }
-    if l.contains("proper") {
+    if has("proper") {
         tags.insert("Proper".to_string());
     }
-    if l.contains("repack") {
+    if has("repack") {
         tags.insert("Repack".to_string());
     }
 
-    tags
+    (edition::from_words(words), tags)
 }
 
 // removed: merge_parts; we now build from hints + tech deterministically
 
-fn extras_to_vec(parts: &SceneNameParts) -> Vec<String> {
-    use std::collections::BTreeSet;
-    let mut set: BTreeSet<String> = BTreeSet::new();
-
-    if parts.hdr {
-        set.insert("HDR".to_string());
-    }
+/// HDR tokens, DV first, then the base layer: "DV.HDR10+", "DV.HDR", "DV.HLG"; "DV" alone for
+/// profile 5. HDR10 keeps the generic "HDR" token.
+pub fn hdr_tokens(parts: &SceneNameParts) -> Vec<String> {
+    let mut tokens = Vec::new();
     if parts.dv {
-        set.insert("DV".to_string());
+        tokens.push("DV".to_string());
+    }
+    if parts.hdr_formats.is_empty() {
+        if parts.hdr {
+            tokens.push("HDR".to_string());
+        }
+    } else {
+        for f in &parts.hdr_formats {
+            let t = match f {
+                HdrFormat::Hdr10 => "HDR".to_string(),
+                other => other.label().to_string(),
+            };
+            if !tokens.contains(&t) {
+                tokens.push(t);
+            }
+        }
     }
+    tokens
+}
+
+fn extras_to_vec(parts: &SceneNameParts) -> Vec<String> {
+    use std::collections::BTreeSet;
+    let mut set: BTreeSet<String> = hdr_tokens(parts).into_iter().collect();
     if let Some(bd) = &parts.bit_depth {
         set.insert(bd.clone());
     }
@@ -374,7 +919,7 @@ fn extras_to_vec(parts: &SceneNameParts) -> Vec<String> {
     // avoid duplicates: if HDR/DV/bitdepth already set, do not re-add from extra_tags
     for e in &parts.extra_tags {
         let el = e.to_ascii_uppercase();
-        if (el == "HDR" && parts.hdr)
+        if ((el.starts_with("HDR") || el == "HLG") && parts.hdr)
             || (el == "DV" && parts.dv)
             || (parts.bit_depth.as_ref().is_some() && el.contains("BIT"))
         {
@@ -401,6 +946,9 @@ fn assemble(parts: &SceneNameParts) -> String {
         segs.push(tag.clone());
     }
 
+    // Editions: after title/year, in canonical order ("Extended.Remastered")
+    segs.extend(parts.editions.iter().cloned());
+
     // Language tag: place after title/year if present
     if let Some(tag) = &parts.language_tag {
         segs.push(tag.clone());
@@ -410,6 +958,9 @@ fn assemble(parts: &SceneNameParts) -> String {
     if let Some(res) = &parts.resolution {
         segs.push(res.clone());
     }
+    if let Some(service) = &parts.streaming_service {
+        segs.push(service.clone());
+    }
     if let Some(src) = &parts.source {
         segs.push(src.clone());
     }
@@ -426,6 +977,9 @@ fn assemble(parts: &SceneNameParts) -> String {
     if let Some(ch) = &parts.audio_channels {
         segs.push(ch.clone());
     }
+    if parts.atmos {
+        segs.push("Atmos".to_string());
+    }
 
     // Video codec must be last
     if let Some(v) = &parts.video_codec {
@@ -442,34 +996,271 @@ fn assemble(parts: &SceneNameParts) -> String {
     name
 }
 
-/// Deterministically propose a scene name, optionally reusing info parsed from the original.
-/// If `original` is present and valid, we accept it to avoid unnecessary churn.
-pub fn propose_scene_name(
-    original: Option<&str>,
-    hints: &RadarrHints,
+/// Build `parts` from hints + MediaInfo, fill the gaps from the parsed original name and
+/// salvage its special tags.
+fn finish(
+    mut parts: SceneNameParts,
     tech: &TechnicalInfo,
-    validation: Option<&ValidationResult>,
+    original: Option<&str>,
+    parsed: Option<&SceneNameParts>,
+    mut prov: Provenance,
+    reason: DecisionReason,
 ) -> SceneDecision {
-    // Always rebuild deterministically from Radarr hints + MediaInfo
-    let mut parts = build_parts_from(hints, tech);
-    // Salvage special tags from original scene name (case-insensitive)
-    for t in salvage_special_tags(original) {
-        parts.extra_tags.insert(t);
+    if let Some(p) = parsed {
+        merge_parsed(&mut parts, tech, p, &mut prov);
+    }
+    // Salvage editions and special tags from original scene name (case-insensitive)
+    let (editions, salvaged) = salvage_special_tags(original);
+    if parts.editions.is_empty() && !editions.is_empty() {
+        parts.editions = editions.into_iter().map(str::to_string).collect();
+        prov.set("edition", Origin::OriginalName);
     }
-    let rebuilt = sanitize_scene_name(&assemble(&parts));
+    if !salvaged.is_empty() {
+        prov.set("extra_tags", Origin::OriginalName);
+    }
+    parts.extra_tags.extend(salvaged);
+
+    SceneDecision {
+        chosen: render(&parts),
+        reason,
+        provenance: prov,
+        parts,
+    }
+}
 
-    let reason = if let Some(v) = validation {
-        DecisionReason::Rebuilt {
-            issues: v.issues.clone(),
+fn render(parts: &SceneNameParts) -> String {
+    sanitize_scene_name(assemble(parts))
+}
+
+/// The name to release under: the decision's name with the profile's group policy applied,
+/// "-NoTag" when requested and no group is left, shortened to the profile's `max_length` (in
+/// bytes: the name is also a file name).
+pub fn finalize_scene_name(
+    decision: &SceneDecision,
+    append_no_tag: bool,
+    profile: &NamingProfile,
+) -> String {
+    finalize(decision, append_no_tag, profile).name
+}
+
+/// [`finalize_scene_name`] with `tag` ("TMDB949") inserted before the release group, the name
+/// being shortened with room left for the tag.
+pub fn finalize_scene_name_tagged(
+    decision: &SceneDecision,
+    append_no_tag: bool,
+    profile: &NamingProfile,
+    tag: &str,
+) -> String {
+    let room = NamingProfile {
+        max_length: profile.max_length.saturating_sub(tag.len() + 1),
+        ..profile.clone()
+    };
+    let name = finalize_scene_name(decision, append_no_tag, &room);
+    match rules::split_group(&name) {
+        (body, Some(group)) => format!("{body}.{tag}-{group}"),
+        (body, None) => format!("{body}.{tag}"),
+    }
+}
+
+/// [`finalize_scene_name`], keeping the final parts and what each rule changed.
+pub fn finalize(
+    decision: &SceneDecision,
+    append_no_tag: bool,
+    profile: &NamingProfile,
+) -> FinalName {
+    let mut parts = decision.parts.clone();
+    let mut provenance = decision.provenance.clone();
+    let mut notes = Vec::new();
+
+    let group = groups::apply(&profile.groups, parts.release_group.as_deref());
+    if group != parts.release_group {
+        tracing::debug!(
+            origin = ?decision.provenance.get("release_group"),
+            "Release group {:?} -> {:?} (naming profile)",
+            parts.release_group,
+            group
+        );
+        notes.push(match (&parts.release_group, &group) {
+            (Some(from), Some(to)) => {
+                format!("release group '{from}' renamed to '{to}' (groups policy)")
+            }
+            (Some(from), None) => format!("release group '{from}' dropped (groups policy)"),
+            (None, _) => "house tag used (groups policy)".to_string(),
+        });
+        provenance.set("release_group", Origin::Config);
+        parts.release_group = group;
+    }
+    if append_no_tag && parts.release_group.is_none() {
+        parts.release_group = Some("NoTag".to_string());
+        provenance.set("release_group", Origin::Config);
+        notes.push("\"-NoTag\" appended (append_no_tag_on_missing_group)".to_string());
+    }
+    if profile.transliterate == Transliteration::Ascii {
+        let before = render(&parts);
+        transliterate_parts(&mut parts);
+        if render(&parts) != before {
+            notes.push("transliterated to ASCII (transliterate = \"ascii\")".to_string());
         }
-    } else {
-        DecisionReason::Rebuilt { issues: vec![] }
+    }
+    let full = render(&parts);
+    let (name, changes) = fit_to_length(parts.clone(), profile.max_length);
+    if !changes.is_empty() {
+        tracing::warn!(
+            "Scene name '{}' is {} bytes (max {}), shortened to '{}': {}",
+            full,
+            full.len(),
+            profile.max_length,
+            name,
+            changes.join(", ")
+        );
+        notes.push(format!(
+            "shortened from {} to {} bytes (max_length = {}): {}",
+            full.len(),
+            name.len(),
+            profile.max_length,
+            changes.join(", ")
+        ));
+    }
+    FinalName {
+        name,
+        parts,
+        provenance,
+        notes,
+    }
+}
+
+/// ASCII spelling of every free-text part (title, editions, tags). A title that can't be
+/// fully romanized (e.g. kanji) is kept as is, for the charset rule to report, rather than
+/// cut down to its kana.
+fn transliterate_parts(parts: &mut SceneNameParts) {
+    let ascii = |tokens: &[String]| -> Vec<String> {
+        tokens
+            .iter()
+            .map(|t| normalize_tokens_to_scene(transliterate::to_ascii(t)))
+            .flat_map(|t| t.split('.').map(str::to_string).collect::<Vec<_>>())
+            .filter(|t| !t.is_empty())
+            .collect()
     };
+    let title = parts.title_tokens.join(".");
+    match transliterate::to_ascii_exact(&title) {
+        Some(spelled) if !normalize_tokens_to_scene(&spelled).is_empty() => {
+            parts.title_tokens = ascii(&parts.title_tokens);
+        }
+        _ => tracing::warn!("Title '{}' has no full ASCII spelling, keeping it", title),
+    }
+    parts.editions = ascii(&parts.editions);
+    let tags: Vec<String> = parts.extra_tags.iter().cloned().collect();
+    parts.extra_tags = ascii(&tags).into_iter().collect();
+}
 
-    SceneDecision {
-        chosen: rebuilt,
-        reason,
+/// Shorten `parts` until the name fits `max_bytes`: drop optional extras (least useful
+/// first), abbreviate the longest title tokens one letter at a time, then drop title tokens
+/// from the end and, as a last resort, trailing tokens of the body. The release group is
+/// always kept whole. Returns the name and what was changed.
+fn fit_to_length(mut parts: SceneNameParts, max_bytes: usize) -> (String, Vec<String>) {
+    type DropFn = fn(&mut SceneNameParts) -> bool;
+    const DROPS: [(&str, DropFn); 7] = [
+        ("streaming service", |p| {
+            p.streaming_service.take().is_some()
+        }),
+        ("extra tags", |p| {
+            !std::mem::take(&mut p.extra_tags).is_empty()
+        }),
+        ("editions", |p| !std::mem::take(&mut p.editions).is_empty()),
+        ("Atmos", |p| std::mem::replace(&mut p.atmos, false)),
+        ("audio channels", |p| p.audio_channels.take().is_some()),
+        ("bit depth", |p| p.bit_depth.take().is_some()),
+        ("audio codec", |p| p.audio_codec.take().is_some()),
+    ];
+
+    let fits = |p: &SceneNameParts| render(p).len() <= max_bytes;
+    let mut changes = Vec::new();
+    for (what, drop) in DROPS {
+        if fits(&parts) {
+            break;
+        }
+        if drop(&mut parts) {
+            changes.push(format!("dropped {what}"));
+        }
     }
+
+    if !fits(&parts) {
+        let before = parts.title_tokens.join(".");
+        while !fits(&parts) {
+            // Longest token, the last one on ties, so the start of the title stays readable
+            let Some(token) = parts
+                .title_tokens
+                .iter_mut()
+                .filter(|t| t.chars().count() > 1)
+                .max_by_key(|t| t.chars().count())
+            else {
+                break;
+            };
+            token.pop();
+        }
+        changes.push(format!(
+            "abbreviated title '{before}' to '{}'",
+            parts.title_tokens.join(".")
+        ));
+    }
+
+    if !fits(&parts) && parts.title_tokens.len() > 1 {
+        let before = parts.title_tokens.join(".");
+        while !fits(&parts) && parts.title_tokens.len() > 1 {
+            parts.title_tokens.pop();
+        }
+        changes.push(format!(
+            "cut title '{before}' to '{}'",
+            parts.title_tokens.join(".")
+        ));
+    }
+
+    if fits(&parts) {
+        return (render(&parts), changes);
+    }
+
+    // Cut the body on token boundaries and re-append the group: the naming rules and the
+    // group policy both expect "-GROUP" at the end
+    let group = parts
+        .release_group
+        .take()
+        .map(|g| format!("-{}", sanitize_scene_name(g)))
+        .unwrap_or_default();
+    let budget = max_bytes.saturating_sub(group.len());
+    let mut body = render(&parts);
+    while body.len() > budget {
+        match body.rfind('.') {
+            Some(dot) => body.truncate(dot),
+            None => {
+                let mut cut = budget;
+                while !body.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                body.truncate(cut);
+            }
+        }
+    }
+    let body = body.trim_end_matches(['.', '-']);
+    changes.push(format!("cut to '{body}'"));
+    (format!("{body}{group}"), changes)
+}
+
+/// Deterministically propose a scene name, optionally reusing info parsed from the original.
+/// MediaInfo wins for technical fields; the original name fills what hints and MediaInfo lack.
+pub fn propose_scene_name(
+    original: Option<&str>,
+    hints: &RadarrHints,
+    tech: &TechnicalInfo,
+    validation: Option<&rules::ValidationReport>,
+) -> SceneDecision {
+    let parsed = original.map(parse_scene_name);
+    let mut prov = Provenance::default();
+    let parts = build_parts_from(hints, tech, parsed.as_ref(), &mut prov);
+
+    let reason = DecisionReason::Rebuilt {
+        issues: validation.map(|v| v.violations.clone()).unwrap_or_default(),
+    };
+    finish(parts, tech, original, parsed.as_ref(), prov, reason)
 }
 
 /// Deterministically propose a scene name for a series/anime episode file.
@@ -479,15 +1270,11 @@ pub fn propose_episode_scene_name(
     hints: &EpisodeHints,
     tech: &TechnicalInfo,
 ) -> SceneDecision {
-    let mut parts = build_episode_parts_from(hints, tech);
-    for t in salvage_special_tags(original) {
-        parts.extra_tags.insert(t);
-    }
-    let rebuilt = sanitize_scene_name(&assemble(&parts));
-    SceneDecision {
-        chosen: rebuilt,
-        reason: DecisionReason::Rebuilt { issues: vec![] },
-    }
+    let parsed = original.map(parse_scene_name);
+    let mut prov = Provenance::default();
+    let parts = build_episode_parts_from(hints, tech, parsed.as_ref(), &mut prov);
+    let reason = DecisionReason::Rebuilt { issues: vec![] };
+    finish(parts, tech, original, parsed.as_ref(), prov, reason)
 }
 
 /// Deterministically propose a scene name for a pack (season/integrale).
@@ -496,15 +1283,11 @@ pub fn propose_pack_scene_name(
     hints: &PackHints,
     tech: &TechnicalInfo,
 ) -> SceneDecision {
-    let mut parts = build_pack_parts_from(hints, tech);
-    for t in salvage_special_tags(original) {
-        parts.extra_tags.insert(t);
-    }
-    let rebuilt = sanitize_scene_name(&assemble(&parts));
-    SceneDecision {
-        chosen: rebuilt,
-        reason: DecisionReason::Rebuilt { issues: vec![] },
-    }
+    let parsed = original.map(parse_scene_name);
+    let mut prov = Provenance::default();
+    let parts = build_pack_parts_from(hints, tech, parsed.as_ref(), &mut prov);
+    let reason = DecisionReason::Rebuilt { issues: vec![] };
+    finish(parts, tech, original, parsed.as_ref(), prov, reason)
 }
 
 fn sanitize_scene_name<S: AsRef<str>>(s: S) -> String {
@@ -0,0 +1,110 @@
+//! Movie editions: Radarr's free-form `edition` field ("Director's Cut", "IMAX Enhanced") and
+//! edition tokens of scene names, both mapped to canonical scene tokens.
+
+/// Canonical token and the word sequences that spell it, most specific first: "IMAX Enhanced"
+/// must win over "IMAX", "Director's Cut" over "Cut".
+const EDITIONS: &[(&str, &[&[&str]])] = &[
+    ("IMAX.Enhanced", &[&["imax", "enhanced"]]),
+    ("IMAX", &[&["imax"]]),
+    (
+        "Directors.Cut",
+        &[&["directors", "cut"], &["director", "cut"], &["dircut"]],
+    ),
+    ("Theatrical.Cut", &[&["theatrical", "cut"], &["theatrical"]]),
+    ("Extended", &[&["extended"]]),
+    ("Ultimate.Cut", &[&["ultimate", "cut"]]),
+    ("Final.Cut", &[&["final", "cut"]]),
+    ("Criterion", &[&["criterion"]]),
+    ("Open.Matte", &[&["open", "matte"], &["openmatte"]]),
+    ("Unrated", &[&["unrated"]]),
+    ("Uncut", &[&["uncut"]]),
+    ("Remastered", &[&["remastered"], &["remaster"]]),
+    ("Special.Edition", &[&["special", "edition"]]),
+    (
+        "Collectors.Edition",
+        &[&["collectors", "edition"], &["collector", "edition"]],
+    ),
+    ("Anniversary.Edition", &[&["anniversary", "edition"]]),
+];
+
+/// Lower-case words of a name, split on anything but letters and digits. Apostrophes are
+/// dropped so "Director's" reads as "directors".
+pub fn words(s: &str) -> Vec<String> {
+    s.replace(['\'', '’'], "")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Canonical edition tokens found in `words`, in canonical order, matching whole words only.
+/// Words claimed by a more specific edition are not matched again ("IMAX Enhanced" is not also
+/// "IMAX").
+pub fn from_words(words: &[String]) -> Vec<&'static str> {
+    let mut used = vec![false; words.len()];
+    let mut found = Vec::new();
+    for (token, spellings) in EDITIONS {
+        for spelling in *spellings {
+            let n = spelling.len();
+            let hit = (0..words.len().saturating_sub(n - 1))
+                .find(|&i| (0..n).all(|k| !used[i + k] && words[i + k] == spelling[k]));
+            if let Some(i) = hit {
+                used[i..i + n].iter_mut().for_each(|u| *u = true);
+                if !found.contains(token) {
+                    found.push(*token);
+                }
+            }
+        }
+    }
+    EDITIONS
+        .iter()
+        .map(|(t, _)| *t)
+        .filter(|t| found.contains(t))
+        .collect()
+}
+
+/// Canonical tokens for Radarr's `edition` field. Unknown editions are kept, dotted, so a
+/// custom edition isn't lost ("Black and Chrome" -> "Black.and.Chrome").
+pub fn from_radarr(edition: &str) -> Vec<String> {
+    let w = words(edition);
+    let known = from_words(&w);
+    if !known.is_empty() {
+        return known.into_iter().map(str::to_string).collect();
+    }
+    let custom: Vec<String> = edition
+        .replace(['\'', '’'], "")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !w.eq_ignore_ascii_case("edition"))
+        .map(str::to_string)
+        .collect();
+    if custom.is_empty() {
+        Vec::new()
+    } else {
+        vec![custom.join(".")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_tokens() {
+        assert_eq!(from_radarr("Director's Cut"), vec!["Directors.Cut"]);
+        assert_eq!(from_radarr("IMAX Enhanced"), vec!["IMAX.Enhanced"]);
+        assert_eq!(
+            from_radarr("Extended Edition Remastered"),
+            vec!["Extended", "Remastered"]
+        );
+        assert_eq!(from_radarr("The Criterion Collection"), vec!["Criterion"]);
+        assert_eq!(from_radarr("Black and Chrome"), vec!["Black.and.Chrome"]);
+        assert!(from_radarr("").is_empty());
+
+        // Whole words only
+        assert!(from_words(&words("Improperly.Extendedness.2020")).is_empty());
+        assert_eq!(
+            from_words(&words("Movie.2019.Open.Matte.1080p")),
+            vec!["Open.Matte"]
+        );
+    }
+}
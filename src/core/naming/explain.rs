@@ -0,0 +1,396 @@
+//! `seedarr explain`: every part of a rebuilt scene name with its value and where it came
+//! from, the configuration rules that changed it and the validation report.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::builder::hdr_tokens;
+use super::rules::{ValidationReport, Violation};
+use super::types::{FinalName, Origin, SceneDecision};
+use crate::core::media::model::{AudioTrack, MediaInfo, VideoTrack};
+
+/// What the name was built from, to describe each origin.
+pub struct Sources<'a> {
+    /// "Radarr" or "Sonarr".
+    pub arr: &'a str,
+    pub original: Option<&'a str>,
+    pub quality: Option<&'a str>,
+    /// How the title was picked (e.g. `title_strategy = "original_aka"`).
+    pub title_rule: String,
+    /// None when the file could not be probed.
+    pub media: Option<&'a MediaInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Field {
+    pub field: &'static str,
+    pub value: Option<String>,
+    pub origin: Option<Origin>,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+    /// "Amélie (2001)", "The Expanse S01E02".
+    pub item: String,
+    pub path: String,
+    pub original: Option<String>,
+    /// Name rebuilt from the sources, before the configuration rules.
+    pub rebuilt: String,
+    /// Name to release under.
+    pub name: String,
+    pub fields: Vec<Field>,
+    /// Changes made by configuration rules, in order.
+    pub rules: Vec<String>,
+    pub violations: Vec<Violation>,
+    /// How a run would release the item when not under `name` alone (season packs...).
+    pub notes: Vec<String>,
+}
+
+const VIDEO_FIELDS: &[&str] = &["resolution", "video_codec", "bit_depth", "hdr"];
+const AUDIO_FIELDS: &[&str] = &["audio_codec", "audio_channels", "atmos"];
+
+pub fn explain(
+    item: String,
+    path: String,
+    decision: &SceneDecision,
+    final_name: &FinalName,
+    report: &ValidationReport,
+    sources: &Sources,
+) -> Explanation {
+    let p = &final_name.parts;
+    let opt = |v: &Option<String>| v.clone().filter(|s| !s.is_empty());
+    let joined = |v: Vec<String>| Some(v.join(".")).filter(|s| !s.is_empty());
+    let values: Vec<(&'static str, Option<String>)> = vec![
+        ("title", joined(p.title_tokens.clone())),
+        ("year", p.year.map(|y| y.to_string())),
+        ("episode", opt(&p.episode_tag)),
+        ("edition", joined(p.editions.clone())),
+        ("language", opt(&p.language_tag)),
+        ("resolution", opt(&p.resolution)),
+        ("streaming_service", opt(&p.streaming_service)),
+        ("source", opt(&p.source)),
+        ("hdr", joined(hdr_tokens(p))),
+        ("bit_depth", opt(&p.bit_depth)),
+        ("audio_codec", opt(&p.audio_codec)),
+        ("audio_channels", opt(&p.audio_channels)),
+        ("atmos", p.atmos.then(|| "Atmos".to_string())),
+        ("video_codec", opt(&p.video_codec)),
+        ("extra_tags", joined(p.extra_tags.iter().cloned().collect())),
+        ("release_group", opt(&p.release_group)),
+    ];
+
+    let fields = values
+        .into_iter()
+        .map(|(field, value)| {
+            let origin = value
+                .as_ref()
+                .and_then(|_| final_name.provenance.get(field));
+            Field {
+                field,
+                detail: origin.map(|o| detail(field, o, sources)),
+                value,
+                origin,
+            }
+        })
+        .collect();
+
+    Explanation {
+        item,
+        path,
+        original: sources.original.map(str::to_string),
+        rebuilt: decision.chosen.clone(),
+        name: final_name.name.clone(),
+        fields,
+        rules: final_name.notes.clone(),
+        violations: report.violations.clone(),
+        notes: Vec::new(),
+    }
+}
+
+fn detail(field: &str, origin: Origin, sources: &Sources) -> String {
+    let arr = sources.arr;
+    match origin {
+        Origin::MediaInfo => {
+            let Some(media) = sources.media else {
+                return "MediaInfo".to_string();
+            };
+            if VIDEO_FIELDS.contains(&field) {
+                media
+                    .primary_video()
+                    .map(|v| describe_video(media, v))
+                    .unwrap_or_else(|| "MediaInfo".to_string())
+            } else if AUDIO_FIELDS.contains(&field) {
+                media
+                    .primary_audio()
+                    .map(|a| describe_audio(media, a))
+                    .unwrap_or_else(|| "MediaInfo".to_string())
+            } else {
+                describe_languages(media)
+            }
+        }
+        Origin::Arr => match field {
+            "title" => format!("{arr} title, {}", sources.title_rule),
+            "source" => format!("{arr} quality source"),
+            "release_group" => format!("{arr} release group"),
+            "episode" => format!("{arr} episode numbers"),
+            _ => arr.to_string(),
+        },
+        Origin::QualityName => match sources.quality {
+            Some(q) => format!("{arr} quality '{q}'"),
+            None => format!("{arr} quality"),
+        },
+        Origin::OriginalName => match sources.original {
+            Some(o) => format!("original name '{o}'"),
+            None => "original name".to_string(),
+        },
+        Origin::Config => "configuration rule (see Rules)".to_string(),
+    }
+}
+
+/// 1-based position of `track` in `tracks`.
+fn position<T>(tracks: &[T], track: &T) -> usize {
+    tracks
+        .iter()
+        .position(|t| std::ptr::eq(t, track))
+        .map_or(0, |i| i + 1)
+}
+
+fn describe_video(media: &MediaInfo, v: &VideoTrack) -> String {
+    let mut s = format!(
+        "MediaInfo video track #{}: {}",
+        position(&media.video, v),
+        v.format
+    );
+    if let (Some(w), Some(h)) = (v.width, v.height) {
+        s.push_str(&format!(" {w}x{h}"));
+    }
+    if let Some(bits) = v.bit_depth {
+        s.push_str(&format!(", {bits}-bit"));
+    }
+    if let Some(hdr) = &v.hdr_format {
+        s.push_str(&format!(", {hdr}"));
+    }
+    s
+}
+
+fn describe_audio(media: &MediaInfo, a: &AudioTrack) -> String {
+    let mut s = format!(
+        "MediaInfo audio track #{}: {}",
+        position(&media.audio, a),
+        a.commercial_name.as_deref().unwrap_or(&a.format)
+    );
+    if let Some(ch) = a.channels {
+        s.push_str(&format!(", {ch} ch"));
+    }
+    if let Some(lang) = &a.language {
+        s.push_str(&format!(", {lang}"));
+    }
+    if a.default {
+        s.push_str(", default");
+    }
+    s
+}
+
+fn describe_languages(media: &MediaInfo) -> String {
+    let list = |langs: Vec<&str>| {
+        if langs.is_empty() {
+            "none".to_string()
+        } else {
+            langs.join(", ")
+        }
+    };
+    format!(
+        "MediaInfo audio languages: {}; subtitles: {}",
+        list(
+            media
+                .audio
+                .iter()
+                .filter(|a| !a.commentary)
+                .filter_map(|a| a.language.as_deref())
+                .collect()
+        ),
+        list(
+            media
+                .subtitles
+                .iter()
+                .filter_map(|s| s.language.as_deref())
+                .collect()
+        )
+    )
+}
+
+fn origin_name(origin: Option<Origin>) -> &'static str {
+    match origin {
+        Some(Origin::MediaInfo) => "media_info",
+        Some(Origin::Arr) => "arr",
+        Some(Origin::QualityName) => "quality_name",
+        Some(Origin::OriginalName) => "original_name",
+        Some(Origin::Config) => "config",
+        None => "-",
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.item)?;
+        writeln!(f, "  Path:     {}", self.path)?;
+        writeln!(
+            f,
+            "  Original: {}",
+            self.original.as_deref().unwrap_or("<none>")
+        )?;
+        writeln!(f, "  Rebuilt:  {}", self.rebuilt)?;
+        writeln!(f, "  Final:    {}", self.name)?;
+        writeln!(f)?;
+
+        let width = self
+            .fields
+            .iter()
+            .filter_map(|x| x.value.as_ref())
+            .map(|v| v.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(5);
+        writeln!(
+            f,
+            "  {:<18} {:<width$} {:<14} DETAIL",
+            "FIELD", "VALUE", "ORIGIN"
+        )?;
+        for x in &self.fields {
+            let row = format!(
+                "  {:<18} {:<width$} {:<14} {}",
+                x.field,
+                x.value.as_deref().unwrap_or("-"),
+                origin_name(x.origin),
+                x.detail.as_deref().unwrap_or("")
+            );
+            writeln!(f, "{}", row.trim_end())?;
+        }
+
+        if !self.rules.is_empty() {
+            writeln!(f, "\n  Rules:")?;
+            for r in &self.rules {
+                writeln!(f, "    - {r}")?;
+            }
+        }
+        if self.violations.is_empty() {
+            writeln!(f, "\n  Validation: ok")?;
+        } else {
+            writeln!(f, "\n  Validation:")?;
+            for v in &self.violations {
+                writeln!(f, "    - {v}")?;
+            }
+        }
+        if !self.notes.is_empty() {
+            writeln!(f, "\n  Notes:")?;
+            for n in &self.notes {
+                writeln!(f, "    - {n}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{GroupPolicy, NamingProfile};
+    use crate::core::naming::{finalize, propose_scene_name, rules, RadarrHints};
+
+    #[test]
+    fn explains_each_part() {
+        let media = MediaInfo {
+            video: vec![VideoTrack {
+                format: "HEVC".to_string(),
+                width: Some(3840),
+                height: Some(2160),
+                bit_depth: Some(10),
+                ..VideoTrack::default()
+            }],
+            audio: vec![
+                AudioTrack {
+                    format: "AC-3".to_string(),
+                    channels: Some(2),
+                    language: Some("en".to_string()),
+                    commentary: true,
+                    ..AudioTrack::default()
+                },
+                AudioTrack {
+                    format: "E-AC-3".to_string(),
+                    channels: Some(6),
+                    language: Some("en".to_string()),
+                    default: true,
+                    ..AudioTrack::default()
+                },
+            ],
+            ..MediaInfo::default()
+        };
+        let tech = media.technical_info();
+        let hints = RadarrHints {
+            title: "Heat".to_string(),
+            year: Some(1995),
+            quality: Some("WEBDL-2160p".to_string()),
+            release_group: Some("YIFY".to_string()),
+            ..RadarrHints::default()
+        };
+        let original = "Heat.1995.2160p.AMZN.WEB-DL.DDP5.1.H.265-YIFY";
+        let decision = propose_scene_name(Some(original), &hints, &tech, None);
+        let profile = NamingProfile {
+            groups: GroupPolicy {
+                forbidden: vec!["YIFY".to_string()],
+                house_tag: Some("SEED".to_string()),
+                ..GroupPolicy::default()
+            },
+            ..NamingProfile::default()
+        };
+        let final_name = finalize(&decision, false, &profile);
+        let report = rules::check(&final_name.name, &profile);
+        let sources = Sources {
+            arr: "Radarr",
+            original: Some(original),
+            quality: hints.quality.as_deref(),
+            title_rule: "title_strategy = \"always_local\"".to_string(),
+            media: Some(&media),
+        };
+        let e = explain(
+            "Heat (1995)".to_string(),
+            "/movies/Heat.mkv".to_string(),
+            &decision,
+            &final_name,
+            &report,
+            &sources,
+        );
+
+        assert_eq!(
+            e.name,
+            "Heat.1995.2160p.AMZN.WEB-DL.10bit.EAC3.5.1.x265-SEED"
+        );
+        let field = |name: &str| e.fields.iter().find(|f| f.field == name).unwrap();
+        assert_eq!(
+            field("audio_codec").detail.as_deref(),
+            Some("MediaInfo audio track #2: E-AC-3, 6 ch, en, default")
+        );
+        assert_eq!(
+            field("resolution").detail.as_deref(),
+            Some("MediaInfo video track #1: HEVC 3840x2160, 10-bit")
+        );
+        assert_eq!(field("source").origin, Some(Origin::QualityName));
+        assert_eq!(
+            field("streaming_service").origin,
+            Some(Origin::OriginalName)
+        );
+        assert_eq!(field("release_group").origin, Some(Origin::Config));
+        assert_eq!(field("edition").value, None);
+        assert_eq!(
+            e.rules,
+            ["release group 'YIFY' renamed to 'SEED' (groups policy)"]
+        );
+
+        let json = serde_json::to_value(&e).unwrap();
+        assert_eq!(json["fields"][0]["field"], "title");
+        assert_eq!(json["fields"][0]["origin"], "arr");
+        assert!(e.to_string().contains("release_group"));
+    }
+}
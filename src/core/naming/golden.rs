@@ -0,0 +1,253 @@
+//! Golden-file regression suite for the naming engine.
+//!
+//! Each directory under `tests/fixtures/naming/` is one case:
+//!
+//! - `hints.json`: `kind` (`movie`, `episode` or `pack`), the `original` scene name if any and
+//!   the Radarr/Sonarr `hints` (fields of [`RadarrHints`], [`EpisodeHints`] or [`PackHints`];
+//!   `source` and `modifier` are the *arr quality's source and modifier)
+//! - `mediainfo.json` (optional): `mediainfo --Output=JSON` of the file
+//! - `expected.txt`: the scene name the engine must propose
+//!
+//! All mismatches are reported together with the tokens that changed. After an intended naming
+//! change, `SEEDARR_UPDATE_GOLDENS=1 cargo test golden` rewrites the `expected.txt` files (and
+//! creates those of new cases) so the diff can be reviewed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::{
+    propose_episode_scene_name, propose_pack_scene_name, propose_scene_name, EpisodeHints,
+    PackHints, RadarrHints, Source, TechnicalInfo,
+};
+use crate::core::media::mediainfo::parse_mediainfo_json;
+
+const UPDATE_VAR: &str = "SEEDARR_UPDATE_GOLDENS";
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Case {
+    Movie {
+        original: Option<String>,
+        hints: MovieFixture,
+    },
+    Episode {
+        original: Option<String>,
+        hints: EpisodeFixture,
+    },
+    Pack {
+        original: Option<String>,
+        hints: PackFixture,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MovieFixture {
+    title: String,
+    year: Option<u16>,
+    quality: Option<String>,
+    source: Option<String>,
+    modifier: Option<String>,
+    release_group: Option<String>,
+    edition: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EpisodeFixture {
+    series_title: String,
+    series_year: Option<u16>,
+    season_number: Option<u16>,
+    #[serde(default)]
+    episode_numbers: Vec<u16>,
+    #[serde(default)]
+    absolute_episode_numbers: Vec<u16>,
+    quality: Option<String>,
+    source: Option<String>,
+    release_group: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PackFixture {
+    title: String,
+    year: Option<u16>,
+    pack_tag: String,
+    quality: Option<String>,
+    source: Option<String>,
+    release_group: Option<String>,
+}
+
+fn source(source: Option<&str>, modifier: Option<&str>) -> Option<Source> {
+    source.and_then(|s| Source::from_arr(s, modifier))
+}
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/naming")
+}
+
+/// Technical info of the case's `mediainfo.json`, with the pipelines' resolution fallback.
+fn technical_info(dir: &Path, quality: Option<&str>) -> Result<TechnicalInfo, String> {
+    let path = dir.join("mediainfo.json");
+    let mut tech = if path.exists() {
+        let text = fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let json = serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+        parse_mediainfo_json(&json)
+            .ok_or_else(|| format!("{}: not a mediainfo report", path.display()))?
+            .technical_info()
+    } else {
+        TechnicalInfo::default()
+    };
+    crate::app::common::apply_resolution_fallback(&mut tech, quality);
+    Ok(tech)
+}
+
+/// Scene name proposed for the case in `dir`.
+fn run_case(dir: &Path) -> Result<String, String> {
+    let path = dir.join("hints.json");
+    let text = fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let case: Case = serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+
+    let decision = match case {
+        Case::Movie { original, hints: h } => {
+            let hints = RadarrHints {
+                source: source(h.source.as_deref(), h.modifier.as_deref()),
+                title: h.title,
+                year: h.year,
+                quality: h.quality,
+                release_group: h.release_group,
+                edition: h.edition,
+            };
+            let tech = technical_info(dir, hints.quality.as_deref())?;
+            propose_scene_name(original.as_deref(), &hints, &tech, None)
+        }
+        Case::Episode { original, hints: h } => {
+            let hints = EpisodeHints {
+                source: source(h.source.as_deref(), None),
+                series_title: h.series_title,
+                series_year: h.series_year,
+                season_number: h.season_number,
+                episode_numbers: h.episode_numbers,
+                absolute_episode_numbers: h.absolute_episode_numbers,
+                quality: h.quality,
+                release_group: h.release_group,
+            };
+            let tech = technical_info(dir, hints.quality.as_deref())?;
+            propose_episode_scene_name(original.as_deref(), &hints, &tech)
+        }
+        Case::Pack { original, hints: h } => {
+            let hints = PackHints {
+                source: source(h.source.as_deref(), None),
+                title: h.title,
+                year: h.year,
+                pack_tag: h.pack_tag,
+                quality: h.quality,
+                release_group: h.release_group,
+            };
+            let tech = technical_info(dir, hints.quality.as_deref())?;
+            propose_pack_scene_name(original.as_deref(), &hints, &tech)
+        }
+    };
+    Ok(decision.chosen)
+}
+
+/// `-token` for tokens only in `expected`, `+token` for those only in `actual`.
+fn token_diff(expected: &str, actual: &str) -> String {
+    let split = |s: &str| -> Vec<String> {
+        s.split(['.', '-'])
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let mut new = split(actual);
+    let removed: Vec<String> = split(expected)
+        .into_iter()
+        .filter(|t| match new.iter().position(|n| n == t) {
+            Some(i) => {
+                new.remove(i);
+                false
+            }
+            None => true,
+        })
+        .map(|t| format!("-{t}"))
+        .collect();
+    let added = new.iter().map(|t| format!("+{t}"));
+    let diff: Vec<String> = removed.into_iter().chain(added).collect();
+    if diff.is_empty() {
+        "(same tokens, different order)".to_string()
+    } else {
+        diff.join(" ")
+    }
+}
+
+#[test]
+fn naming_goldens() {
+    let update = std::env::var_os(UPDATE_VAR).is_some();
+    let mut dirs: Vec<PathBuf> = fs::read_dir(fixtures_dir())
+        .expect("tests/fixtures/naming is missing")
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    assert!(!dirs.is_empty(), "no naming fixtures");
+
+    let mut report = Vec::new();
+    for dir in &dirs {
+        let case = dir.file_name().unwrap().to_string_lossy().to_string();
+        let actual = match run_case(dir) {
+            Ok(name) => name,
+            Err(e) => {
+                report.push(format!("  {case}\n    error: {e}"));
+                continue;
+            }
+        };
+        let golden = dir.join("expected.txt");
+        let expected = fs::read_to_string(&golden)
+            .ok()
+            .map(|s| s.trim().to_string());
+        if expected.as_deref() == Some(actual.as_str()) {
+            continue;
+        }
+
+        if update {
+            fs::write(&golden, format!("{actual}\n")).unwrap();
+            eprintln!(
+                "updated {case}: {} -> {actual}",
+                expected.as_deref().unwrap_or("<new>")
+            );
+            continue;
+        }
+        report.push(match expected {
+            Some(expected) => format!(
+                "  {case}\n    expected: {expected}\n    actual:   {actual}\n    {}",
+                token_diff(&expected, &actual)
+            ),
+            None => format!("  {case}\n    no expected.txt, actual: {actual}"),
+        });
+    }
+
+    assert!(
+        report.is_empty(),
+        "{} of {} naming goldens differ ({UPDATE_VAR}=1 cargo test golden accepts them):\n{}",
+        report.len(),
+        dirs.len(),
+        report.join("\n")
+    );
+}
+
+#[test]
+fn token_diff_lists_changed_tokens() {
+    assert_eq!(
+        token_diff(
+            "Heat.1995.1080p.BluRay.x264-GRP",
+            "Heat.1995.1080p.WEB-DL.x264-GRP"
+        ),
+        "-BluRay +WEB +DL"
+    );
+    assert_eq!(
+        token_diff("A.1080p.x264", "A.x264.1080p"),
+        "(same tokens, different order)"
+    );
+}
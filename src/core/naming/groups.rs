@@ -0,0 +1,129 @@
+//! Release group policy: bracketed junk, known aliases, forbidden groups and the house tag.
+
+use std::collections::HashMap;
+
+use crate::config::GroupPolicy;
+
+/// `s` without bracketed parts ("GRP[rarbg]" -> "GRP", "GRP (Obfuscated)" -> "GRP"). A group
+/// that is only a bracketed name ("[HorribleSubs]") keeps its content.
+pub fn strip_brackets(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut inner = String::new();
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '[' | '(' | '{' => depth += 1,
+            ']' | ')' | '}' if depth > 0 => depth -= 1,
+            _ if depth > 0 => inner.push(c),
+            _ => out.push(c),
+        }
+    }
+    let out = out.trim();
+    if out.is_empty() {
+        inner.trim().to_string()
+    } else {
+        out.to_string()
+    }
+}
+
+/// Comparison key: letters and digits, lower-cased ("QxR", "qxr", "Q x R").
+fn key(group: &str) -> String {
+    group
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// The group to release under, per `policy`: the house tag when always used or when `group` is
+/// forbidden, else the canonical spelling of a known alias, else `group` itself. None when a
+/// forbidden group has no house tag to replace it.
+pub fn apply(policy: &GroupPolicy, group: Option<&str>) -> Option<String> {
+    let house = policy.house_tag.clone().filter(|t| !t.is_empty());
+    if policy.always_house_tag {
+        if let Some(tag) = house {
+            return Some(tag);
+        }
+    }
+    let group = group?;
+    let k = key(group);
+
+    if policy.forbidden.iter().any(|f| key(f) == k) {
+        tracing::info!(
+            "Release group '{}' is forbidden by the naming profile: {}",
+            group,
+            house.as_deref().unwrap_or("dropped")
+        );
+        return house;
+    }
+
+    let canonical = policy.aliases.iter().find_map(|(name, variants)| {
+        (key(name) == k || variants.iter().any(|v| key(v) == k)).then(|| name.clone())
+    });
+    Some(canonical.unwrap_or_else(|| group.to_string()))
+}
+
+/// Most common group of a pack's files, compared case-insensitively; None on a tie or when no
+/// file has a group.
+pub fn majority<'a>(groups: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let mut counts: HashMap<String, (usize, &'a str)> = HashMap::new();
+    for group in groups {
+        let k = key(group);
+        if k.is_empty() {
+            continue;
+        }
+        counts.entry(k).or_insert((0, group)).0 += 1;
+    }
+    let best = counts.values().map(|(n, _)| *n).max()?;
+    let mut top = counts.values().filter(|(n, _)| *n == best);
+    match (top.next(), top.next()) {
+        (Some((_, group)), None) => Some(group.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_and_majority() {
+        assert_eq!(strip_brackets("GRP[rarbg]"), "GRP");
+        assert_eq!(strip_brackets("GRP (Obfuscated)"), "GRP");
+        assert_eq!(strip_brackets("[HorribleSubs]"), "HorribleSubs");
+
+        let policy = GroupPolicy {
+            aliases: HashMap::from([(
+                "QxR".to_string(),
+                vec!["QxRHEVC".to_string(), "QxRTeam".to_string()],
+            )]),
+            forbidden: vec!["YIFY".to_string()],
+            house_tag: Some("SEED".to_string()),
+            always_house_tag: false,
+        };
+        assert_eq!(apply(&policy, Some("qxr")).as_deref(), Some("QxR"));
+        assert_eq!(apply(&policy, Some("QxR-HEVC")).as_deref(), Some("QxR"));
+        assert_eq!(apply(&policy, Some("yify")).as_deref(), Some("SEED"));
+        assert_eq!(apply(&policy, Some("FLUX")).as_deref(), Some("FLUX"));
+        assert_eq!(apply(&policy, None), None);
+
+        let no_house = GroupPolicy {
+            house_tag: None,
+            ..policy.clone()
+        };
+        assert_eq!(apply(&no_house, Some("YIFY")), None);
+        let always = GroupPolicy {
+            always_house_tag: true,
+            ..policy
+        };
+        assert_eq!(apply(&always, Some("FLUX")).as_deref(), Some("SEED"));
+        assert_eq!(apply(&always, None).as_deref(), Some("SEED"));
+
+        assert_eq!(
+            majority(["NTb", "NTb", "FLUX", "ntb"]).as_deref(),
+            Some("NTb")
+        );
+        assert_eq!(majority(["NTb", "FLUX"]), None);
+        assert_eq!(majority(std::iter::empty()), None);
+    }
+}
@@ -1,9 +1,15 @@
 pub mod builder;
+pub mod edition;
+pub mod explain;
+#[cfg(test)]
+mod golden;
+pub mod groups;
 pub mod parser;
+pub mod rules;
+pub mod titles;
+pub mod transliterate;
 pub mod types;
-pub mod validator;
 
 pub use builder::*;
 pub use parser::*;
 pub use types::*;
-pub use validator::*;
@@ -2,27 +2,44 @@ use once_cell::sync::Lazy;
 use regex::{Regex, RegexBuilder};
 use std::collections::BTreeSet;
 
-use super::types::SceneNameParts;
+use super::types::{SceneNameParts, Source};
+use crate::core::media::language;
 
 // Regexes for common tokens we want to salvage
 static YEAR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(19|20)\d{2}").unwrap());
 static RESOLUTION_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)\b(480p|576p|720p|1080p|2160p|4k|8k)\b").unwrap());
 static SOURCE_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)\b(AMZN(\.WEB(-?DL)?)?|WEB(-?DL|Rip)?|Blu[- ]?Ray|BRRip|BDRip|WEBRip|WEB|HDLight|HDLigh|mHD)\b").unwrap()
+    Regex::new(r"(?i)\b(AMZN(\.WEB(-?DL)?)?|WEB(-?DL|Rip)?|Blu[- ]?Ray|BRRip|BDRip|WEBRip|WEB|REMUX|HDTV|PDTV|SDTV|DVDRip|DVD|HDLight|HDLigh|mHD)\b").unwrap()
+});
+// Case-sensitive: short service codes ("NF", "iT") are common words in lowercase.
+// Only searched after the title (see TITLE_END_RE), where "NF" or "CR" can't be title words.
+static SERVICE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(AMZN|NF|DSNP|ATVP|HMAX|HULU|PCOK|PMTP|CRAV|STAN|MYCANAL|CR|iT)\b").unwrap()
+});
+// First token after the title: year, resolution or episode marker
+static TITLE_END_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b((19|20)\d{2}|\d{3,4}p|4k|S\d{1,2}(E\d{1,4})*|E\d{2,4})\b").unwrap()
 });
 static VCODEC_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)\b(x265|x264|h\.?265|h\.?264|hevc|avc)\b").unwrap());
-static ACODEC_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"(?i)\b(DDP|EAC3|AC3|DTS(-HD)?|TrueHD|AAC)\b").unwrap());
+static ACODEC_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(DDP|EAC3|AC3|DTS-HD\.(MA|HRA)|DTS-X|DTS(-HD|-ES)?|TrueHD|AAC|FLAC|Opus|LPCM|PCM)\b",
+    )
+    .unwrap()
+});
+static ATMOS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bAtmos\b").unwrap());
 static ACHANNELS_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)\b(7\.1|5\.1|6CH|2\.0|2CH)\b").unwrap());
 static BITDEPTH_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\b(10bit|8bit)\b").unwrap());
 static HDR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\b(HDR10\+?|HDR|HLG)\b").unwrap());
 static DV_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\b(DV|Dolby[ .]?Vision)\b").unwrap());
 static MULTI_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)\b(MULTI|MULTi|Multi|FRENCH|VFF|VFQ|VFI|VOA|VF2|FR2|TRUEFRENCH|FR|EN)\b")
-        .unwrap()
+    Regex::new(
+        r"(?i)\b(MULTI|MULTi|Multi|FRENCH|VFF|VFQ|VFI|VOA|VF2|FR2|TRUEFRENCH|VOSTFR|VF|FR|EN)\b",
+    )
+    .unwrap()
 });
 static GROUP_RE: Lazy<Regex> = Lazy::new(|| {
     RegexBuilder::new(r"-([A-Za-z0-9._⚡]+)$")
@@ -31,13 +48,29 @@ static GROUP_RE: Lazy<Regex> = Lazy::new(|| {
         .unwrap()
 });
 
+// Tails of hyphenated tokens ("WEB-DL", "DTS-HD", "DTS-X") that GROUP_RE would otherwise take
+// for a group
+static HYPHENATED_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^(DL|Rip|HD|MA|HRA|X|ES)$").unwrap());
+
+/// Whether a GROUP_RE capture is a release group rather than the tail of a hyphenated word:
+/// "Spider-Man.2002.1080p.BluRay.x264" would otherwise yield "Man.2002.1080p.BluRay.x264".
+fn is_release_group(candidate: &str) -> bool {
+    !candidate.contains('.')
+        && !HYPHENATED_RE.is_match(candidate)
+        && !RESOLUTION_RE.is_match(candidate)
+        && !SOURCE_RE.is_match(candidate)
+        && !VCODEC_RE.is_match(candidate)
+        && !ACODEC_RE.is_match(candidate)
+}
+
 pub fn parse_scene_name(input: &str) -> SceneNameParts {
     let mut parts = SceneNameParts::default();
     let s = input.trim();
 
     // Release group
-    if let Some(caps) = GROUP_RE.captures(s) {
-        if let Some(m) = caps.get(1) {
+    if let Some(m) = GROUP_RE.captures(s).and_then(|caps| caps.get(1)) {
+        if is_release_group(m.as_str()) {
             parts.release_group = Some(m.as_str().to_string());
         }
     }
@@ -52,8 +85,27 @@ pub fn parse_scene_name(input: &str) -> SceneNameParts {
     if let Some(m) = RESOLUTION_RE.find(s) {
         parts.resolution = Some(m.as_str().to_string());
     }
-    if let Some(m) = SOURCE_RE.find(s) {
-        parts.source = Some(m.as_str().to_string());
+    // Source: canonical token when recognized ("BluRay.REMUX" is a remux), raw otherwise
+    let sources: Vec<&str> = SOURCE_RE.find_iter(s).map(|m| m.as_str()).collect();
+    let known: Vec<Source> = sources
+        .iter()
+        .filter_map(|t| Source::from_token(t))
+        .collect();
+    parts.source = known
+        .iter()
+        .find(|s| **s == Source::Remux)
+        .or(known.first())
+        .map(|s| s.token().to_string())
+        .or_else(|| sources.first().map(|t| t.to_string()));
+    // A title may start with a year ("2012"): the title ends at a later token
+    if let Some(end) = TITLE_END_RE
+        .find_iter(s)
+        .find(|m| m.start() > 0)
+        .map(|m| m.end())
+    {
+        if let Some(m) = SERVICE_RE.find(&s[end..]) {
+            parts.streaming_service = Some(m.as_str().to_string());
+        }
     }
     if let Some(m) = VCODEC_RE.find(s) {
         parts.video_codec = Some(m.as_str().to_string());
@@ -67,6 +119,9 @@ pub fn parse_scene_name(input: &str) -> SceneNameParts {
     if let Some(m) = BITDEPTH_RE.find(s) {
         parts.bit_depth = Some(m.as_str().to_string());
     }
+    if ATMOS_RE.is_match(s) {
+        parts.atmos = true;
+    }
     if HDR_RE.is_match(s) {
         parts.hdr = true;
     }
@@ -86,6 +141,7 @@ pub fn parse_scene_name(input: &str) -> SceneNameParts {
         if YEAR_RE.is_match(t)
             || RESOLUTION_RE.is_match(t)
             || SOURCE_RE.is_match(t)
+            || SERVICE_RE.is_match(t)
             || VCODEC_RE.is_match(t)
             || ACODEC_RE.is_match(t)
             || ACHANNELS_RE.is_match(t)
@@ -99,7 +155,8 @@ pub fn parse_scene_name(input: &str) -> SceneNameParts {
         let is_known = matches!(
             lt.as_str(),
             "imax" | "4klight" | "hdlight" | "vff" | "vfq" | "vfi" | "multi" | "french" | "atmos"
-        ) || MULTI_RE.is_match(t);
+        ) || MULTI_RE.is_match(t)
+            || language::from_scene_token(t).is_some();
         let is_all_upper =
             t.chars().all(|c| !c.is_ascii_lowercase()) && t.chars().any(|c| c.is_ascii_uppercase());
         if is_known || is_all_upper {
@@ -127,3 +184,63 @@ pub fn parse_scene_name(input: &str) -> SceneNameParts {
 
     parts
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_source_and_streaming_service() {
+        let p = parse_scene_name("Dune.2021.MULTi.2160p.NF.WEB-DL.DDP5.1.Atmos.x265-GRP");
+        assert_eq!(p.source.as_deref(), Some("WEB-DL"));
+        assert_eq!(p.streaming_service.as_deref(), Some("NF"));
+        assert!(!p.extra_tags.contains("NF"));
+
+        let p = parse_scene_name("Dune.2021.2160p.BluRay.REMUX.HDR.TrueHD.7.1-GRP");
+        assert_eq!(p.source.as_deref(), Some("REMUX"));
+        assert_eq!(p.streaming_service, None);
+
+        let p = parse_scene_name("Dune.2021.1080p.AMZN.WEBRip.x264-GRP");
+        assert_eq!(p.source.as_deref(), Some("WEBRip"));
+        assert_eq!(p.streaming_service.as_deref(), Some("AMZN"));
+
+        assert_eq!(
+            parse_scene_name("Show.S01E01.720p.HDTV.x264-GRP")
+                .source
+                .as_deref(),
+            Some("HDTV")
+        );
+    }
+
+    #[test]
+    fn streaming_services_are_only_read_after_the_title() {
+        let p = parse_scene_name("NF.Confidential.2019.1080p.WEB-DL.x264-GRP");
+        assert_eq!(p.streaming_service, None);
+        let p = parse_scene_name("CR.Stories.S01E01.1080p.NF.WEB-DL.x264-GRP");
+        assert_eq!(p.streaming_service.as_deref(), Some("NF"));
+        assert_eq!(
+            parse_scene_name("Show.S01E01.SDTV.x264-GRP")
+                .source
+                .as_deref(),
+            Some("SDTV")
+        );
+    }
+
+    #[test]
+    fn hyphenated_words_are_not_groups() {
+        for name in [
+            "Spider-Man.2002.1080p.BluRay.x264",
+            "Dune.2021.1080p.WEB-DL",
+            "Dune.2021.1080p.BluRay.DTS-HD",
+            "Dune.2021.1080p.BluRay.x264-1080p",
+        ] {
+            assert_eq!(parse_scene_name(name).release_group, None, "{name}");
+        }
+        assert_eq!(
+            parse_scene_name("Spider-Man.2002.1080p.BluRay.x264-GRP")
+                .release_group
+                .as_deref(),
+            Some("GRP")
+        );
+    }
+}
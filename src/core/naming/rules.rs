@@ -0,0 +1,532 @@
+//! Validation rules for proposed scene names. A naming profile (`[naming.profiles.<name>]`)
+//! configures the rules and their severities; names with errors are not linked, turned into
+//! torrents or uploaded.
+
+use std::fmt;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::NamingProfile;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warn,
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    /// Empty name, or Radarr's "Unknown" placeholder.
+    Empty,
+    MaxLength,
+    /// Characters outside `allowed_chars` (whitespace and path-unsafe characters by default).
+    Charset,
+    /// "..", or a name starting/ending with a separator.
+    DoubleDots,
+    ForbiddenWord,
+    RequiredToken,
+    TokenOrder,
+    GroupFormat,
+}
+
+impl Rule {
+    fn default_severity(self) -> Severity {
+        match self {
+            Rule::RequiredToken | Rule::TokenOrder => Severity::Warn,
+            _ => Severity::Error,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::Empty => "empty",
+            Rule::MaxLength => "max_length",
+            Rule::Charset => "charset",
+            Rule::DoubleDots => "double_dots",
+            Rule::ForbiddenWord => "forbidden_word",
+            Rule::RequiredToken => "required_token",
+            Rule::TokenOrder => "token_order",
+            Rule::GroupFormat => "group_format",
+        }
+    }
+}
+
+/// Token classes recognized in a scene name, in scene order (group last).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenKind {
+    Year,
+    Episode,
+    Resolution,
+    Source,
+    AudioCodec,
+    VideoCodec,
+    Group,
+}
+
+impl TokenKind {
+    fn name(self) -> &'static str {
+        match self {
+            TokenKind::Year => "year",
+            TokenKind::Episode => "episode",
+            TokenKind::Resolution => "resolution",
+            TokenKind::Source => "source",
+            TokenKind::AudioCodec => "audio_codec",
+            TokenKind::VideoCodec => "video_codec",
+            TokenKind::Group => "group",
+        }
+    }
+
+    /// Position in the expected order; year and episode share the first slot.
+    fn rank(self) -> u8 {
+        match self {
+            TokenKind::Year | TokenKind::Episode => 0,
+            TokenKind::Resolution => 1,
+            TokenKind::Source => 2,
+            TokenKind::AudioCodec => 3,
+            TokenKind::VideoCodec => 4,
+            TokenKind::Group => 5,
+        }
+    }
+}
+
+static TOKEN_RES: Lazy<Vec<(TokenKind, Regex)>> = Lazy::new(|| {
+    [
+        (TokenKind::Year, r"^(19|20)\d{2}$"),
+        (TokenKind::Episode, r"^(?i)(S\d{1,2}(E\d{1,4})*|E\d{2,4})$"),
+        (TokenKind::Resolution, r"^(?i)(480p|576p|720p|1080p|1440p|2160p|4k|8k)$"),
+        (
+            TokenKind::Source,
+            r"^(?i)(REMUX|BluRay|WEB-DL|WEBRip|WEB|HDTV|PDTV|SDTV|DVDRip|DVD|BDRip|BRRip|HDLight|4KLight)$",
+        ),
+        (
+            TokenKind::AudioCodec,
+            r"^(?i)(TrueHD|EAC3|DDP|AC3|DTS-HD|DTS-X|DTS-ES|DTS|AAC|FLAC|Opus|LPCM)$",
+        ),
+        (TokenKind::VideoCodec, r"^(?i)(x265|x264|h265|h264|HEVC|AVC|AV1)$"),
+    ]
+    .into_iter()
+    .map(|(k, re)| (k, Regex::new(re).unwrap()))
+    .collect()
+});
+
+/// Whitespace, control and path-unsafe characters.
+static UNSAFE_CHAR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"[\s\p{Cc}/\\:*?"<>|]"#).unwrap());
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub message: String,
+    /// Offending tokens or characters.
+    pub items: Vec<String>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Error => "error",
+            _ => "warning",
+        };
+        write!(f, "{level}[{}]: {}", self.rule.name(), self.message)?;
+        if !self.items.is_empty() {
+            write!(f, " ({})", self.items.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Violation> {
+        self.violations
+            .iter()
+            .filter(|v| v.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Violation> {
+        self.violations
+            .iter()
+            .filter(|v| v.severity == Severity::Warn)
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for v in &self.violations {
+            writeln!(f, "  - {v}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compile the profile's regexes, so a typo is reported at startup rather than per release.
+pub fn check_profile(profile: &NamingProfile) -> Result<(), String> {
+    for (what, re) in [
+        ("allowed_chars", &profile.allowed_chars),
+        ("group_format", &profile.group_format),
+    ] {
+        if let Some(re) = re {
+            Regex::new(re).map_err(|e| format!("invalid naming {what} regex '{re}': {e}"))?;
+        }
+    }
+    let groups = &profile.groups;
+    match &groups.house_tag {
+        Some(tag) if tag.is_empty() || !tag.chars().all(|c| c.is_ascii_alphanumeric()) => {
+            return Err(format!(
+                "naming house_tag '{tag}' must be letters and digits only"
+            ));
+        }
+        None if groups.always_house_tag => {
+            return Err("naming always_house_tag is set but house_tag is missing".to_string());
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Split a scene name into its dot-separated tokens and release group. A name ending in a
+/// hyphenated token ("...1080p.WEB-DL", "...DTS-HD") has no group.
+pub fn split_group(name: &str) -> (&str, Option<&str>) {
+    match name.rsplit_once('-') {
+        Some((body, group))
+            if !group.is_empty() && !group.contains('.') && !is_compound(body, group) =>
+        {
+            (body, Some(group))
+        }
+        _ => (name, None),
+    }
+}
+
+/// Whether the body's last token and `suffix` form one known token (WEB-DL, DTS-HD, DTS-X).
+fn is_compound(body: &str, suffix: &str) -> bool {
+    let last = body.rsplit('.').next().unwrap_or(body);
+    classify(&format!("{last}-{suffix}")).is_some()
+}
+
+fn classify(token: &str) -> Option<TokenKind> {
+    TOKEN_RES
+        .iter()
+        .find(|(_, re)| re.is_match(token))
+        .map(|(k, _)| *k)
+}
+
+/// Run every rule of `profile` on `name`.
+pub fn check(name: &str, profile: &NamingProfile) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let mut add = |rule: Rule, message: String, items: Vec<String>| {
+        let severity = profile
+            .severity
+            .get(&rule)
+            .copied()
+            .unwrap_or(rule.default_severity());
+        if severity != Severity::Off {
+            report.violations.push(Violation {
+                rule,
+                severity,
+                message,
+                items,
+            });
+        }
+    };
+
+    let name = name.trim();
+    if name.is_empty() || name.eq_ignore_ascii_case("unknown") {
+        add(Rule::Empty, "no usable name".to_string(), Vec::new());
+        return report;
+    }
+
+    if name.len() > profile.max_length {
+        add(
+            Rule::MaxLength,
+            format!(
+                "{} bytes, at most {} allowed",
+                name.len(),
+                profile.max_length
+            ),
+            Vec::new(),
+        );
+    }
+
+    let allowed = profile
+        .allowed_chars
+        .as_deref()
+        .map(|re| Regex::new(&format!("^(?:{re})$")));
+    let mut bad_chars: Vec<String> = Vec::new();
+    for c in name.chars() {
+        let s = c.to_string();
+        let ok = match &allowed {
+            Some(Ok(re)) => re.is_match(&s),
+            _ => !UNSAFE_CHAR_RE.is_match(&s),
+        };
+        if !ok && !bad_chars.contains(&s) {
+            bad_chars.push(s);
+        }
+    }
+    if !bad_chars.is_empty() {
+        add(
+            Rule::Charset,
+            "characters not allowed".to_string(),
+            bad_chars.iter().map(|c| format!("{c:?}")).collect(),
+        );
+    }
+
+    if name.contains("..") || name.starts_with(['.', '-']) || name.ends_with(['.', '-']) {
+        add(
+            Rule::DoubleDots,
+            "empty token between separators".to_string(),
+            Vec::new(),
+        );
+    }
+
+    let (body, group) = split_group(name);
+    let tokens: Vec<&str> = body.split('.').filter(|t| !t.is_empty()).collect();
+
+    let forbidden: Vec<String> = profile
+        .forbidden_words
+        .iter()
+        .filter(|w| {
+            let words: Vec<&str> = w.split(['.', ' ']).filter(|p| !p.is_empty()).collect();
+            !words.is_empty()
+                && tokens.windows(words.len()).any(|win| {
+                    win.iter()
+                        .zip(&words)
+                        .all(|(t, w)| t.eq_ignore_ascii_case(w))
+                })
+        })
+        .cloned()
+        .collect();
+    if !forbidden.is_empty() {
+        add(
+            Rule::ForbiddenWord,
+            "forbidden words".to_string(),
+            forbidden,
+        );
+    }
+
+    // The title can't be a year ("2012.2009.1080p"): classification starts at the second token
+    let mut kinds: Vec<(TokenKind, &str)> = tokens
+        .iter()
+        .skip(1)
+        .filter_map(|t| classify(t).map(|k| (k, *t)))
+        .collect();
+    if let Some(g) = group {
+        kinds.push((TokenKind::Group, g));
+    }
+
+    let missing: Vec<String> = profile
+        .required_tokens
+        .iter()
+        .filter(|k| !kinds.iter().any(|(found, _)| found == *k))
+        .map(|k| k.name().to_string())
+        .collect();
+    if !missing.is_empty() {
+        add(Rule::RequiredToken, "missing tokens".to_string(), missing);
+    }
+
+    if profile.token_order {
+        let mut highest: Option<(TokenKind, &str)> = None;
+        let mut misplaced = Vec::new();
+        for (kind, token) in &kinds {
+            match highest {
+                Some((h, h_token)) if kind.rank() < h.rank() => {
+                    misplaced.push(format!("{token} after {h_token}"));
+                }
+                _ => highest = Some((*kind, token)),
+            }
+        }
+        if !misplaced.is_empty() {
+            add(
+                Rule::TokenOrder,
+                "expected year/episode, resolution, source, audio, video codec".to_string(),
+                misplaced,
+            );
+        }
+    }
+
+    if let (Some(re), Some(g)) = (profile.group_format.as_deref(), group) {
+        match Regex::new(re) {
+            Ok(re) if re.is_match(g) => {}
+            Ok(_) => add(
+                Rule::GroupFormat,
+                "release group doesn't match group_format".to_string(),
+                vec![g.to_string()],
+            ),
+            Err(e) => add(Rule::GroupFormat, format!("invalid regex: {e}"), Vec::new()),
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(name: &str, profile: &NamingProfile) -> Vec<(Rule, Severity)> {
+        check(name, profile)
+            .violations
+            .iter()
+            .map(|v| (v.rule, v.severity))
+            .collect()
+    }
+
+    #[test]
+    fn default_profile() {
+        let p = NamingProfile::default();
+        assert!(rules(
+            "Dune.Part.Two.2024.MULTi.VFF.2160p.WEB-DL.DV.HDR.EAC3.5.1.Atmos.x265-GRP",
+            &p
+        )
+        .is_empty());
+        assert!(rules("Le.Fabuleux.Destin.d'Amélie.Poulain.2001.1080p.x264", &p).is_empty());
+
+        assert_eq!(rules("Unknown", &p), vec![(Rule::Empty, Severity::Error)]);
+        assert_eq!(
+            rules("Dune..2021.1080p.x264", &p),
+            vec![(Rule::DoubleDots, Severity::Error)]
+        );
+        assert_eq!(
+            rules("Dune 2021.1080p.x264", &p),
+            vec![(Rule::Charset, Severity::Error)]
+        );
+        assert_eq!(
+            rules("Dune.2021.x264.1080p.BluRay", &p),
+            vec![(Rule::TokenOrder, Severity::Warn)]
+        );
+        assert_eq!(
+            rules("Dune.2021.BluRay", &p),
+            vec![(Rule::RequiredToken, Severity::Warn)]
+        );
+    }
+
+    #[test]
+    fn resolution_1440p_is_a_token() {
+        let p = NamingProfile::default();
+        assert!(rules("Dune.Part.Two.2024.MULTi.1440p.WEB.x264-GRP", &p).is_empty());
+    }
+
+    #[test]
+    fn sdtv_is_a_source() {
+        let p = NamingProfile {
+            required_tokens: vec![TokenKind::Source],
+            ..NamingProfile::default()
+        };
+        assert!(rules("Show.S01E01.576p.SDTV.x264-GRP", &p).is_empty());
+    }
+
+    #[test]
+    fn legacy_validator_examples() {
+        let p = NamingProfile::default();
+        assert!(rules(
+            "Rebel.Moon.Part.One.A.Child.of.Fire.2023.MULTi.1080p.WEB.x264-FW",
+            &p
+        )
+        .is_empty());
+
+        // Spaces and brackets were accepted as long as the name had two dots; they are now
+        // charset errors
+        for bad in [
+            "The.Blues.Brothers .1980-MULTI.(VFF-VO)-1080p-HDLigh.x264.ac3.mHDgz",
+            "Fight Club (1999) - VO-VF - 1080p - x265",
+            "Bodies Bodies Bodies (2022) MULTi VFI 2160p 10bit 4KLight DV HDR BluRay DDP 5.1 Atmos x265-QTZ",
+            "Everything Everywhere All at Once (2022)",
+        ] {
+            let report = check(bad, &p);
+            assert!(report.has_errors(), "{bad}");
+            assert!(report.errors().any(|v| v.rule == Rule::Charset), "{bad}");
+        }
+        assert!(check("Unknown", &p).has_errors());
+        assert!(check("", &p).has_errors());
+    }
+
+    #[test]
+    fn hyphenated_tokens_are_not_groups() {
+        assert_eq!(
+            split_group("Dune.2021.1080p.WEB-DL"),
+            ("Dune.2021.1080p.WEB-DL", None)
+        );
+        assert_eq!(
+            split_group("Dune.2021.1080p.BluRay.DTS-HD"),
+            ("Dune.2021.1080p.BluRay.DTS-HD", None)
+        );
+        assert_eq!(
+            split_group("Dune.2021.1080p.BluRay.DTS-X"),
+            ("Dune.2021.1080p.BluRay.DTS-X", None)
+        );
+        assert_eq!(
+            split_group("Dune.2021.1080p.WEB-DL-GRP"),
+            ("Dune.2021.1080p.WEB-DL", Some("GRP"))
+        );
+
+        let p = NamingProfile {
+            required_tokens: vec![TokenKind::Source, TokenKind::Group],
+            ..NamingProfile::default()
+        };
+        let report = check("Dune.2021.1080p.x264.WEB-DL", &p);
+        let missing: Vec<&str> = report
+            .violations
+            .iter()
+            .filter(|v| v.rule == Rule::RequiredToken)
+            .flat_map(|v| v.items.iter().map(String::as_str))
+            .collect();
+        assert_eq!(missing, vec!["group"]);
+        // WEB-DL is read as the source, out of order after the codec
+        assert!(report.violations.iter().any(|v| v.rule == Rule::TokenOrder));
+        assert!(rules("Dune.2021.1080p.BluRay.DTS-HD.x264-GRP", &p).is_empty());
+    }
+
+    #[test]
+    fn profile_rules_and_severities() {
+        let mut p = NamingProfile {
+            max_length: 30,
+            allowed_chars: Some("[A-Za-z0-9.+-]".to_string()),
+            forbidden_words: vec!["Directors.Cut".to_string()],
+            required_tokens: vec![TokenKind::Year, TokenKind::Source, TokenKind::Group],
+            group_format: Some("^[A-Z]+$".to_string()),
+            ..NamingProfile::default()
+        };
+        let report = check("Amélie.2001.Directors.Cut.1080p.x264-grp", &p);
+        let found: Vec<Rule> = report.violations.iter().map(|v| v.rule).collect();
+        assert_eq!(
+            found,
+            vec![
+                Rule::MaxLength,
+                Rule::Charset,
+                Rule::ForbiddenWord,
+                Rule::RequiredToken,
+                Rule::GroupFormat
+            ]
+        );
+        assert!(report.has_errors());
+        assert_eq!(report.violations[3].items, vec!["source"]);
+        assert_eq!(
+            report.violations[1].to_string(),
+            "error[charset]: characters not allowed (\"é\")"
+        );
+
+        p.severity.insert(Rule::MaxLength, Severity::Off);
+        p.severity.insert(Rule::Charset, Severity::Warn);
+        p.severity.insert(Rule::ForbiddenWord, Severity::Warn);
+        p.severity.insert(Rule::GroupFormat, Severity::Warn);
+        let report = check("Amélie.2001.Directors.Cut.1080p.x264-grp", &p);
+        assert!(!report.has_errors());
+        assert_eq!(report.warnings().count(), 4);
+
+        p.allowed_chars = Some("[".to_string());
+        assert!(check_profile(&p).is_err());
+    }
+}
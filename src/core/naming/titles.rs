@@ -0,0 +1,247 @@
+//! Title selection: which of the local, original and alternate titles of a movie or series
+//! goes into the release name and tracker listings.
+//!
+//! Radarr and Sonarr don't report the language of alternate titles, so `prefer_locale` guesses
+//! it from the title itself: the script for non-Latin languages (Cyrillic, Greek, kana...) and
+//! articles, common words and accented letters for Latin ones ("Le", "Der", "ñ"). A title with
+//! no clear winner is never picked by that guess.
+
+use crate::config::TitleStrategy;
+use crate::core::media::language;
+
+/// Every title known for a movie or series.
+#[derive(Debug, Clone, Default)]
+pub struct TitleCandidates {
+    /// Title in the *arr's UI language.
+    pub local: String,
+    pub original: Option<String>,
+    /// Normalized language of `original` ("fr", "ja").
+    pub original_language: Option<String>,
+    pub alternates: Vec<String>,
+}
+
+impl TitleCandidates {
+    /// The original or localized title that isn't `chosen`, for a second naming attempt.
+    pub fn alternative(&self, chosen: &str) -> Option<String> {
+        [self.original.as_ref(), Some(&self.local)]
+            .into_iter()
+            .flatten()
+            .find(|t| t.as_str() != chosen)
+            .cloned()
+    }
+}
+
+/// Latin-script languages: marker words and letters.
+const LATIN_CUES: &[(&str, &[&str], &[char])] = &[
+    (
+        "en",
+        &["the", "of", "and", "a", "an", "in", "to", "with"],
+        &[],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "l", "du", "des", "un", "une", "et", "d", "au", "aux", "pour",
+        ],
+        &['é', 'è', 'ê', 'à', 'ç', 'œ', 'ù', 'î', 'ô', 'ë'],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ein", "eine", "dem", "den", "im", "vom", "zum",
+        ],
+        &['ß', 'ä', 'ö', 'ü'],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "del", "y", "una", "un", "por", "con", "la",
+        ],
+        &['ñ', '¿', '¡', 'á', 'í', 'ó', 'ú'],
+    ),
+    (
+        "it",
+        &[
+            "il", "lo", "gli", "della", "di", "e", "una", "dei", "delle", "la",
+        ],
+        &['ì', 'ò'],
+    ),
+    (
+        "pt",
+        &["o", "os", "as", "do", "da", "dos", "um", "uma", "e"],
+        &['ã', 'õ'],
+    ),
+];
+
+/// Language of a title guessed from its script or marker words; None when unsure.
+pub fn guess_language(title: &str) -> Option<&'static str> {
+    let script = title.chars().find_map(|c| match c {
+        'а'..='я' | 'А'..='Я' | 'ё' | 'Ё' => Some("ru"),
+        'і' | 'ї' | 'є' | 'ґ' => Some("uk"),
+        'α'..='ω' | 'Α'..='Ω' => Some("el"),
+        '\u{3040}'..='\u{30FF}' => Some("ja"),
+        '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' => Some("ko"),
+        '\u{0600}'..='\u{06FF}' => Some("ar"),
+        '\u{0590}'..='\u{05FF}' => Some("he"),
+        '\u{0E00}'..='\u{0E7F}' => Some("th"),
+        _ => None,
+    });
+    if let Some(lang) = script {
+        // Ukrainian letters win over the shared Cyrillic ones
+        if lang == "ru" && title.chars().any(|c| "іїєґІЇЄҐ".contains(c)) {
+            return Some("uk");
+        }
+        return Some(lang);
+    }
+    // Han without kana
+    if title
+        .chars()
+        .any(|c| ('\u{4E00}'..='\u{9FFF}').contains(&c))
+    {
+        return Some("zh");
+    }
+
+    let lower = title.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let scores: Vec<(&'static str, usize)> = LATIN_CUES
+        .iter()
+        .map(|(lang, markers, letters)| {
+            let w = words.iter().filter(|w| markers.contains(w)).count();
+            let l = letters.iter().filter(|c| lower.contains(**c)).count();
+            (*lang, w + l)
+        })
+        .collect();
+    let best = scores.iter().map(|(_, s)| *s).max().unwrap_or(0);
+    let mut winners = scores.iter().filter(|(_, s)| *s == best);
+    match (winners.next(), winners.next()) {
+        (Some((lang, _)), None) if best > 0 => Some(lang),
+        _ => None,
+    }
+}
+
+/// Title picked by `strategy`. `locale` is the wanted language for `prefer_locale`.
+pub fn choose(titles: &TitleCandidates, strategy: TitleStrategy, locale: Option<&str>) -> String {
+    let original = titles.original.as_deref().filter(|t| !t.trim().is_empty());
+    let original_is = |lang: &str| {
+        titles
+            .original_language
+            .as_deref()
+            .is_some_and(|l| language::base(l) == lang)
+    };
+
+    match strategy {
+        TitleStrategy::AlwaysLocal => titles.local.clone(),
+        TitleStrategy::AlwaysOriginal => original.unwrap_or(&titles.local).to_string(),
+        TitleStrategy::OriginalIfEnElseLocal => match original {
+            Some(o) if original_is("en") => o.to_string(),
+            _ => titles.local.clone(),
+        },
+        TitleStrategy::PreferLocale => {
+            let Some(wanted) = locale.and_then(language::normalize) else {
+                return titles.local.clone();
+            };
+            let wanted = language::base(&wanted);
+            if let Some(o) = original.filter(|_| original_is(wanted)) {
+                return o.to_string();
+            }
+            titles
+                .alternates
+                .iter()
+                .find(|t| guess_language(t) == Some(wanted))
+                .cloned()
+                .unwrap_or_else(|| titles.local.clone())
+        }
+        TitleStrategy::OriginalAka => match original {
+            Some(o) if !same_title(o, &titles.local) => format!("{o} AKA {}", titles.local),
+            _ => titles.local.clone(),
+        },
+    }
+}
+
+/// Equal once case, accents and punctuation are ignored ("Amélie" vs "Amelie!").
+fn same_title(a: &str, b: &str) -> bool {
+    let key = |s: &str| -> String {
+        super::transliterate::to_ascii(s)
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    };
+    key(a) == key(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amelie() -> TitleCandidates {
+        TitleCandidates {
+            local: "Amelie".to_string(),
+            original: Some("Le Fabuleux Destin d'Amélie Poulain".to_string()),
+            original_language: Some("fr".to_string()),
+            alternates: vec![
+                "Die fabelhafte Welt der Amélie".to_string(),
+                "Амели".to_string(),
+                "El fabuloso destino de Amélie Poulain".to_string(),
+            ],
+        }
+    }
+
+    #[test]
+    fn strategies() {
+        let t = amelie();
+        assert_eq!(choose(&t, TitleStrategy::AlwaysLocal, None), "Amelie");
+        assert_eq!(
+            choose(&t, TitleStrategy::AlwaysOriginal, None),
+            "Le Fabuleux Destin d'Amélie Poulain"
+        );
+        assert_eq!(
+            choose(&t, TitleStrategy::OriginalIfEnElseLocal, None),
+            "Amelie"
+        );
+        assert_eq!(
+            choose(&t, TitleStrategy::OriginalAka, None),
+            "Le Fabuleux Destin d'Amélie Poulain AKA Amelie"
+        );
+        assert_eq!(
+            choose(&t, TitleStrategy::PreferLocale, Some("German")),
+            "Die fabelhafte Welt der Amélie"
+        );
+        assert_eq!(choose(&t, TitleStrategy::PreferLocale, Some("ru")), "Амели");
+        assert_eq!(
+            choose(&t, TitleStrategy::PreferLocale, Some("fr-CA")),
+            "Le Fabuleux Destin d'Amélie Poulain"
+        );
+        // No title in that language
+        assert_eq!(
+            choose(&t, TitleStrategy::PreferLocale, Some("ja")),
+            "Amelie"
+        );
+
+        // Same title twice is not an AKA
+        let heat = TitleCandidates {
+            local: "Heat".to_string(),
+            original: Some("Heat".to_string()),
+            original_language: Some("en".to_string()),
+            alternates: Vec::new(),
+        };
+        assert_eq!(choose(&heat, TitleStrategy::OriginalAka, None), "Heat");
+    }
+
+    #[test]
+    fn guesses_title_languages() {
+        assert_eq!(guess_language("La Cité des enfants perdus"), Some("fr"));
+        assert_eq!(guess_language("Das Boot"), Some("de"));
+        assert_eq!(guess_language("El laberinto del fauno"), Some("es"));
+        assert_eq!(guess_language("Война и мир"), Some("ru"));
+        assert_eq!(guess_language("千と千尋の神隠し"), Some("ja"));
+        assert_eq!(guess_language("기생충"), Some("ko"));
+        assert_eq!(guess_language("The Lord of the Rings"), Some("en"));
+        // "la" is French, Spanish and Italian
+        assert_eq!(guess_language("La Strada"), None);
+        assert_eq!(guess_language("Heat"), None);
+    }
+}
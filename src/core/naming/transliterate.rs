@@ -0,0 +1,360 @@
+//! ASCII transliteration for trackers and indexers that only accept ASCII names: accents are
+//! stripped, ligatures and special letters spelled out (ß -> ss, œ -> oe), Cyrillic and Greek
+//! mapped letter by letter and Japanese kana romanized (Hepburn). Letters without a
+//! romanization (kanji, hangul...) are dropped by [`to_ascii`]; [`to_ascii_exact`] gives up
+//! instead, so a title isn't mangled into a fragment ("千と千尋" -> "To").
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Latin letters that don't decompose into a base letter + accent.
+const LATIN: &[(char, &str)] = &[
+    ('ß', "ss"),
+    ('ẞ', "SS"),
+    ('æ', "ae"),
+    ('Æ', "AE"),
+    ('œ', "oe"),
+    ('Œ', "OE"),
+    ('ø', "o"),
+    ('Ø', "O"),
+    ('ł', "l"),
+    ('Ł', "L"),
+    ('đ', "d"),
+    ('Đ', "D"),
+    ('ð', "d"),
+    ('Ð', "D"),
+    ('þ', "th"),
+    ('Þ', "Th"),
+    ('ı', "i"),
+    ('ħ', "h"),
+    ('Ħ', "H"),
+];
+
+/// Lower-case Cyrillic (Russian, Ukrainian, Belarusian, Serbian, Bulgarian letters).
+const CYRILLIC: &[(char, &str)] = &[
+    ('а', "a"),
+    ('б', "b"),
+    ('в', "v"),
+    ('г', "g"),
+    ('ґ', "g"),
+    ('д', "d"),
+    ('ђ', "dj"),
+    ('е', "e"),
+    ('ё', "yo"),
+    ('є', "ye"),
+    ('ж', "zh"),
+    ('з', "z"),
+    ('и', "i"),
+    ('і', "i"),
+    ('ї', "yi"),
+    ('й', "y"),
+    ('ј', "j"),
+    ('к', "k"),
+    ('л', "l"),
+    ('љ', "lj"),
+    ('м', "m"),
+    ('н', "n"),
+    ('њ', "nj"),
+    ('о', "o"),
+    ('п', "p"),
+    ('р', "r"),
+    ('с', "s"),
+    ('т', "t"),
+    ('ћ', "c"),
+    ('у', "u"),
+    ('ў', "u"),
+    ('ф', "f"),
+    ('х', "kh"),
+    ('ц', "ts"),
+    ('ч', "ch"),
+    ('џ', "dz"),
+    ('ш', "sh"),
+    ('щ', "shch"),
+    ('ъ', ""),
+    ('ы', "y"),
+    ('ь', ""),
+    ('э', "e"),
+    ('ю', "yu"),
+    ('я', "ya"),
+];
+
+/// Lower-case Greek; accented letters decompose to these first.
+const GREEK: &[(char, &str)] = &[
+    ('α', "a"),
+    ('β', "v"),
+    ('γ', "g"),
+    ('δ', "d"),
+    ('ε', "e"),
+    ('ζ', "z"),
+    ('η', "i"),
+    ('θ', "th"),
+    ('ι', "i"),
+    ('κ', "k"),
+    ('λ', "l"),
+    ('μ', "m"),
+    ('ν', "n"),
+    ('ξ', "x"),
+    ('ο', "o"),
+    ('π', "p"),
+    ('ρ', "r"),
+    ('σ', "s"),
+    ('ς', "s"),
+    ('τ', "t"),
+    ('υ', "y"),
+    ('φ', "f"),
+    ('χ', "ch"),
+    ('ψ', "ps"),
+    ('ω', "o"),
+];
+
+/// Hiragana (katakana are folded onto it), Hepburn.
+const KANA: &[(char, &str)] = &[
+    ('あ', "a"),
+    ('い', "i"),
+    ('う', "u"),
+    ('え', "e"),
+    ('お', "o"),
+    ('か', "ka"),
+    ('き', "ki"),
+    ('く', "ku"),
+    ('け', "ke"),
+    ('こ', "ko"),
+    ('が', "ga"),
+    ('ぎ', "gi"),
+    ('ぐ', "gu"),
+    ('げ', "ge"),
+    ('ご', "go"),
+    ('さ', "sa"),
+    ('し', "shi"),
+    ('す', "su"),
+    ('せ', "se"),
+    ('そ', "so"),
+    ('ざ', "za"),
+    ('じ', "ji"),
+    ('ず', "zu"),
+    ('ぜ', "ze"),
+    ('ぞ', "zo"),
+    ('た', "ta"),
+    ('ち', "chi"),
+    ('つ', "tsu"),
+    ('て', "te"),
+    ('と', "to"),
+    ('だ', "da"),
+    ('ぢ', "ji"),
+    ('づ', "zu"),
+    ('で', "de"),
+    ('ど', "do"),
+    ('な', "na"),
+    ('に', "ni"),
+    ('ぬ', "nu"),
+    ('ね', "ne"),
+    ('の', "no"),
+    ('は', "ha"),
+    ('ひ', "hi"),
+    ('ふ', "fu"),
+    ('へ', "he"),
+    ('ほ', "ho"),
+    ('ば', "ba"),
+    ('び', "bi"),
+    ('ぶ', "bu"),
+    ('べ', "be"),
+    ('ぼ', "bo"),
+    ('ぱ', "pa"),
+    ('ぴ', "pi"),
+    ('ぷ', "pu"),
+    ('ぺ', "pe"),
+    ('ぽ', "po"),
+    ('ま', "ma"),
+    ('み', "mi"),
+    ('む', "mu"),
+    ('め', "me"),
+    ('も', "mo"),
+    ('や', "ya"),
+    ('ゆ', "yu"),
+    ('よ', "yo"),
+    ('ら', "ra"),
+    ('り', "ri"),
+    ('る', "ru"),
+    ('れ', "re"),
+    ('ろ', "ro"),
+    ('わ', "wa"),
+    ('ゐ', "i"),
+    ('ゑ', "e"),
+    ('を', "o"),
+    ('ん', "n"),
+    ('ゔ', "vu"),
+    ('ぁ', "a"),
+    ('ぃ', "i"),
+    ('ぅ', "u"),
+    ('ぇ', "e"),
+    ('ぉ', "o"),
+    ('ゃ', "ya"),
+    ('ゅ', "yu"),
+    ('ょ', "yo"),
+    ('ゎ', "wa"),
+];
+
+fn lookup(table: &[(char, &'static str)], c: char) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == c).map(|(_, v)| *v)
+}
+
+/// Letter-by-letter mapping, keeping the case of the source letter.
+fn letter(c: char) -> Option<String> {
+    if let Some(s) = lookup(LATIN, c) {
+        return Some(s.to_string());
+    }
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    let s = lookup(CYRILLIC, lower).or_else(|| lookup(GREEK, lower))?;
+    if lower == c {
+        return Some(s.to_string());
+    }
+    let mut chars = s.chars();
+    Some(match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    })
+}
+
+/// Katakana folded onto hiragana.
+fn hiragana(c: char) -> Option<char> {
+    match c {
+        'ぁ'..='ゖ' => Some(c),
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60),
+        _ => None,
+    }
+}
+
+/// Romanize the kana run starting at `chars[i]`; returns the romaji and the chars consumed.
+fn kana(chars: &[char], i: usize) -> Option<(String, usize)> {
+    let c = hiragana(chars[i])?;
+    let next = chars.get(i + 1).copied().and_then(hiragana);
+
+    // Sokuon: double the next consonant ("kitte", "matcha")
+    if c == 'っ' {
+        let (rest, n) = next.and_then(|_| kana(chars, i + 1))?;
+        let double = if rest.starts_with("ch") {
+            "t".to_string()
+        } else {
+            rest.chars().next().unwrap_or_default().to_string()
+        };
+        return Some((double + &rest, n + 1));
+    }
+
+    let base = lookup(KANA, c)?;
+    // Yoon: "ki" + "ya" -> "kya", "shi" + "yo" -> "sho"
+    if let Some(small @ ('ゃ' | 'ゅ' | 'ょ')) = next {
+        if let Some(stem) = base.strip_suffix('i').filter(|s| !s.is_empty()) {
+            let y = lookup(KANA, small).unwrap_or_default();
+            let glide = if matches!(stem, "sh" | "ch" | "j") {
+                &y[1..]
+            } else {
+                y
+            };
+            return Some((format!("{stem}{glide}"), 2));
+        }
+    }
+    Some((base.to_string(), 1))
+}
+
+/// ASCII spelling of `s`. Separators and ASCII are kept as they are.
+pub fn to_ascii(s: &str) -> String {
+    spell(s).0
+}
+
+/// [`to_ascii`], or None when a letter or digit of `s` has no ASCII spelling.
+pub fn to_ascii_exact(s: &str) -> Option<String> {
+    let (out, complete) = spell(s);
+    complete.then_some(out)
+}
+
+/// ASCII spelling of `s`, and whether every letter and digit could be spelled.
+fn spell(s: &str) -> (String, bool) {
+    let chars: Vec<char> = s.nfc().collect();
+    let mut out = String::with_capacity(s.len());
+    let mut complete = true;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_ascii() {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+        // Long vowel mark: dropped, as in common scene spellings ("Doragon.Boru")
+        if c == 'ー' {
+            i += 1;
+            continue;
+        }
+        if let Some((romaji, n)) = kana(&chars, i) {
+            // Kana have no case: capitalize at the start of a word
+            let word_start = out.chars().last().is_none_or(|p| !p.is_alphanumeric());
+            let mut r = romaji.chars();
+            if let (true, Some(first)) = (word_start, r.next()) {
+                out.push(first.to_ascii_uppercase());
+                out.push_str(r.as_str());
+            } else {
+                out.push_str(&romaji);
+            }
+            i += n;
+            continue;
+        }
+        match letter(c) {
+            Some(s) => out.push_str(&s),
+            None => {
+                // Accented letters: base letter, without the combining marks
+                for d in c.to_string().nfkd() {
+                    if d.is_ascii() {
+                        out.push(d);
+                    } else if !is_combining_mark(d) {
+                        match letter(d) {
+                            Some(l) => out.push_str(&l),
+                            None => complete &= !d.is_alphanumeric(),
+                        }
+                    }
+                }
+            }
+        }
+        i += 1;
+    }
+    (out, complete)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latin_letters() {
+        assert_eq!(to_ascii("À.bout.de.souffle"), "A.bout.de.souffle");
+        assert_eq!(to_ascii("Amélie.Poulain"), "Amelie.Poulain");
+        assert_eq!(to_ascii("Straße"), "Strasse");
+        assert_eq!(to_ascii("Cœur.Œuvre"), "Coeur.OEuvre");
+        assert_eq!(to_ascii("Łódź.Smørrebrød"), "Lodz.Smorrebrod");
+        assert_eq!(to_ascii("Ｆｕｌｌｗｉｄｔｈ"), "Fullwidth");
+    }
+
+    #[test]
+    fn non_latin_titles() {
+        assert_eq!(to_ascii("Брат"), "Brat");
+        assert_eq!(to_ascii("Война.и.мир"), "Voyna.i.mir");
+        assert_eq!(to_ascii("Щука"), "Shchuka");
+        assert_eq!(to_ascii("Ζορμπάς"), "Zormpas");
+        assert_eq!(to_ascii("となりのトトロ"), "Tonarinototoro");
+        assert_eq!(to_ascii("ドラゴンボール"), "Doragonboru");
+        assert_eq!(
+            to_ascii("きっと.しょうねん.マッチャ"),
+            "Kitto.Shounen.Matcha"
+        );
+        assert_eq!(
+            to_ascii_exact("となりのトトロ").as_deref(),
+            Some("Tonarinototoro")
+        );
+        assert_eq!(
+            to_ascii_exact("Amélie – Poulain").as_deref(),
+            Some("Amelie  Poulain")
+        );
+        // No romanization table: no exact spelling
+        assert_eq!(to_ascii_exact("千と千尋の神隠し"), None);
+        assert_eq!(to_ascii_exact("기생충"), None);
+    }
+}
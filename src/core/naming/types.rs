@@ -1,21 +1,8 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Issue {
-    Empty,
-    IsUnknown,
-    MissingDots,
-    MissingYear,
-    MissingResolution,
-    MissingSource,
-    MissingVideoCodec,
-}
+use serde::Serialize;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ValidationResult {
-    pub valid: bool,
-    pub issues: Vec<Issue>,
-}
+use super::rules::Violation;
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct SceneNameParts {
@@ -23,26 +10,141 @@ pub struct SceneNameParts {
     /// Episode marker for series/anime releases (e.g. "S01E02" or "E012").
     pub episode_tag: Option<String>,
     pub year: Option<u16>,
+    /// Canonical edition tokens ("Directors.Cut", "IMAX.Enhanced"), placed after the year.
+    pub editions: Vec<String>,
     pub resolution: Option<String>,
     pub source: Option<String>,
+    /// Streaming service the WEB release came from ("NF", "AMZN"), placed before the source.
+    pub streaming_service: Option<String>,
     pub video_codec: Option<String>,
     pub audio_codec: Option<String>,
     pub audio_channels: Option<String>,
+    /// Dolby Atmos object audio, appended after the channels ("TrueHD.7.1.Atmos").
+    pub atmos: bool,
     pub bit_depth: Option<String>, // "10bit" etc.
     pub hdr: bool,
     pub dv: bool,
+    /// Base-layer HDR formats; when empty, `hdr` alone yields the generic "HDR" token.
+    pub hdr_formats: BTreeSet<HdrFormat>,
     pub languages: BTreeSet<String>,
     pub language_tag: Option<String>,
     pub release_group: Option<String>,
     pub extra_tags: BTreeSet<String>, // salvage: IMAX, 4KLight, VFF/VFQ, etc.
 }
 
+/// Where a release was sourced from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Source {
+    /// Untouched Blu-ray (or DVD) streams.
+    Remux,
+    BluRay,
+    WebDl,
+    WebRip,
+    /// WEB release where DL vs Rip is unknown.
+    Web,
+    Hdtv,
+    /// Standard-definition TV capture.
+    Sdtv,
+    Dvd,
+}
+
+impl Source {
+    /// Scene token.
+    pub fn token(self) -> &'static str {
+        match self {
+            Self::Remux => "REMUX",
+            Self::BluRay => "BluRay",
+            Self::WebDl => "WEB-DL",
+            Self::WebRip => "WEBRip",
+            Self::Web => "WEB",
+            Self::Hdtv => "HDTV",
+            Self::Sdtv => "SDTV",
+            Self::Dvd => "DVDRip",
+        }
+    }
+
+    pub fn is_web(self) -> bool {
+        matches!(self, Self::WebDl | Self::WebRip | Self::Web)
+    }
+
+    /// From Radarr's quality `source` + `modifier` ("bluray" + "remux") or Sonarr's quality
+    /// `source` ("web", "webRip", "blurayRaw", "television").
+    pub fn from_arr(source: &str, modifier: Option<&str>) -> Option<Self> {
+        let remux = modifier.is_some_and(|m| m.eq_ignore_ascii_case("remux"));
+        let s = match source.to_ascii_lowercase().as_str() {
+            "bluray" | "dvd" if remux => Self::Remux,
+            "blurayraw" => Self::Remux,
+            "bluray" => Self::BluRay,
+            "webdl" | "web" => Self::WebDl,
+            "webrip" => Self::WebRip,
+            "tv" | "television" | "televisionraw" => Self::Hdtv,
+            "dvd" => Self::Dvd,
+            _ => return None,
+        };
+        Some(s)
+    }
+
+    /// From a quality name ("Remux-2160p", "WEBDL-1080p", "Bluray-1080p Remux").
+    pub fn from_quality_name(name: &str) -> Option<Self> {
+        let l = name.to_ascii_lowercase();
+        let s = if l.contains("remux") {
+            Self::Remux
+        } else if l.contains("webdl") || l.contains("web-dl") {
+            Self::WebDl
+        } else if l.contains("webrip") {
+            Self::WebRip
+        } else if l.contains("web") {
+            Self::Web
+        } else if l.contains("blu") {
+            Self::BluRay
+        } else if l.contains("hdtv") {
+            Self::Hdtv
+        } else if l.contains("sdtv") {
+            Self::Sdtv
+        } else if l.contains("dvd") {
+            Self::Dvd
+        } else {
+            return None;
+        };
+        Some(s)
+    }
+
+    /// From a scene-name token ("WEB-DL", "BDRip", "AMZN.WEBRip", "REMUX").
+    pub fn from_token(token: &str) -> Option<Self> {
+        let l = token.to_ascii_lowercase().replace(['-', '.', ' '], "");
+        let s = if l.contains("remux") {
+            Self::Remux
+        } else if l.contains("webdl") {
+            Self::WebDl
+        } else if l.contains("webrip") {
+            Self::WebRip
+        } else if l.ends_with("web") {
+            Self::Web
+        } else if l.contains("bluray") || l == "bdrip" || l == "brrip" {
+            Self::BluRay
+        } else if l == "hdtv" || l == "pdtv" {
+            Self::Hdtv
+        } else if l == "sdtv" {
+            Self::Sdtv
+        } else if l.starts_with("dvd") {
+            Self::Dvd
+        } else {
+            return None;
+        };
+        Some(s)
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct RadarrHints {
     pub title: String,
     pub year: Option<u16>,
     pub quality: Option<String>,
+    /// Source from the quality's source/modifier fields; preferred over `quality`.
+    pub source: Option<Source>,
     pub release_group: Option<String>,
+    /// Radarr's movie file edition ("Director's Cut"); preferred over the original name.
+    pub edition: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -55,6 +157,7 @@ pub struct EpisodeHints {
     /// Absolute episode numbers (anime). If set, can be used for tagging.
     pub absolute_episode_numbers: Vec<u16>,
     pub quality: Option<String>,
+    pub source: Option<Source>,
     pub release_group: Option<String>,
 }
 
@@ -65,18 +168,55 @@ pub struct PackHints {
     /// Pack tag inserted after title/year (e.g. "S01" or "INTEGRALE").
     pub pack_tag: String,
     pub quality: Option<String>,
+    pub source: Option<Source>,
     pub release_group: Option<String>,
 }
 
-#[derive(Debug, Clone, Default)]
+/// HDR format of the base layer (Dolby Vision is tracked separately, see [`DolbyVision`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum HdrFormat {
+    #[serde(rename = "HDR10")]
+    Hdr10,
+    #[serde(rename = "HDR10+")]
+    Hdr10Plus,
+    #[serde(rename = "HLG")]
+    Hlg,
+}
+
+impl HdrFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Hdr10 => "HDR10",
+            Self::Hdr10Plus => "HDR10+",
+            Self::Hlg => "HLG",
+        }
+    }
+}
+
+/// Dolby Vision metadata of the video track.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DolbyVision {
+    /// Profile (5, 7, 8...).
+    pub profile: Option<u8>,
+    pub level: Option<u8>,
+    /// Format the base layer is compatible with ("HDR10", "SDR", "HLG"); None for profile 5,
+    /// which can't be played without Dolby Vision support.
+    pub compatibility: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct TechnicalInfo {
     pub resolution: Option<String>,
     pub video_codec: Option<String>,
     pub bit_depth: Option<String>,
+    /// Any HDR base layer (HDR10, HDR10+ or HLG). False for Dolby Vision profile 5.
     pub hdr: bool,
     pub dv: bool,
+    pub hdr_formats: BTreeSet<HdrFormat>,
+    pub dolby_vision: Option<DolbyVision>,
     pub audio_codec: Option<String>,
     pub audio_channels: Option<String>,
+    pub atmos: bool,
     pub audio_languages: BTreeSet<String>,
     pub subtitle_languages: BTreeSet<String>,
     pub has_vfi: bool,
@@ -86,11 +226,60 @@ pub struct TechnicalInfo {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DecisionReason {
     AcceptedExisting,
-    Rebuilt { issues: Vec<Issue> },
+    /// Rebuilt; `issues` are the naming rules the original name broke, when it was checked.
+    Rebuilt {
+        issues: Vec<Violation>,
+    },
+}
+
+/// Where a field of a rebuilt scene name came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Origin {
+    /// Probed from the media file.
+    MediaInfo,
+    /// Radarr/Sonarr metadata (title, year, episode numbers, quality fields, release group).
+    Arr,
+    /// Inferred from the Radarr/Sonarr quality name ("WEBDL-1080p").
+    QualityName,
+    /// Parsed from the original scene name.
+    OriginalName,
+    /// Set by a configuration rule (naming profile group policy, "-NoTag").
+    Config,
+}
+
+/// Origin of each field of a rebuilt name, keyed by field ("source", "audio_codec"...).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Provenance(BTreeMap<String, Origin>);
+
+impl Provenance {
+    pub fn set(&mut self, field: &str, origin: Origin) {
+        self.0.insert(field.to_string(), origin);
+    }
+
+    pub fn get(&self, field: &str) -> Option<Origin> {
+        self.0.get(field).copied()
+    }
+}
+
+/// A decision once the configuration rules are applied: group policy, "-NoTag",
+/// transliteration and the length limit.
+#[derive(Debug, Clone)]
+pub struct FinalName {
+    pub name: String,
+    /// The parts `name` was rendered from, before any shortening.
+    pub parts: SceneNameParts,
+    pub provenance: Provenance,
+    /// What the rules changed, in order.
+    pub notes: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct SceneDecision {
     pub chosen: String,
     pub reason: DecisionReason,
+    pub provenance: Provenance,
+    /// The parts `chosen` was assembled from.
+    pub parts: SceneNameParts,
 }
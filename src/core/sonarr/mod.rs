@@ -1,20 +1,21 @@
 use serde::Deserialize;
 
+use crate::utils::http::HttpClient;
 use crate::utils::Error;
 
 #[derive(Debug, Clone)]
 pub struct SonarrClient {
     base_url: String,
     api_key: String,
-    client: reqwest::Client,
+    http: HttpClient,
 }
 
 impl SonarrClient {
-    pub fn new(base_url: String, api_key: String) -> Self {
+    pub fn new(base_url: String, api_key: String, http: HttpClient) -> Self {
         Self {
             base_url: base_url.trim_end_matches('/').to_string(),
             api_key,
-            client: reqwest::Client::new(),
+            http,
         }
     }
 
@@ -28,17 +29,20 @@ impl SonarrClient {
         query: &[(&str, String)],
     ) -> Result<T, Error> {
         let url = self.api_url(path);
-        let mut req = self
-            .client
-            .get(url)
-            .header("Accept", "application/json")
-            .header("X-Api-Key", &self.api_key);
-
-        if !query.is_empty() {
-            req = req.query(query);
-        }
-
-        let resp = req.send().await?;
+        let resp = self
+            .http
+            .send(&url, |client| {
+                let req = client
+                    .get(&url)
+                    .header("Accept", "application/json")
+                    .header("X-Api-Key", &self.api_key);
+                if query.is_empty() {
+                    req
+                } else {
+                    req.query(query)
+                }
+            })
+            .await?;
         let status = resp.status();
         if !status.is_success() {
             let body = resp.text().await.unwrap_or_default();
@@ -77,7 +81,47 @@ pub struct SeriesResource {
     pub series_type: Option<String>,
     pub overview: Option<String>,
     #[serde(default)]
+    pub tvdb_id: Option<i64>,
+    #[serde(default)]
+    pub tmdb_id: Option<i64>,
+    #[serde(default)]
+    pub imdb_id: Option<String>,
+    #[serde(default)]
     pub images: Vec<ImageResource>,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    /// Episode runtime in minutes.
+    #[serde(default)]
+    pub runtime: Option<i32>,
+    #[serde(default)]
+    pub ratings: Option<SeriesRatings>,
+    /// Sonarr v4 only.
+    #[serde(default)]
+    pub original_language: Option<LanguageResource>,
+    #[serde(default)]
+    pub alternate_titles: Vec<AlternateTitleResource>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageResource {
+    pub name: Option<String>,
+}
+
+/// Scene and TVDB alias of a series (no language is reported).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlternateTitleResource {
+    pub title: String,
+    #[serde(default)]
+    pub season_number: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesRatings {
+    pub votes: Option<i64>,
+    pub value: Option<f64>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -127,4 +171,7 @@ pub struct QualityModel {
 #[serde(rename_all = "camelCase")]
 pub struct QualityName {
     pub name: Option<String>,
+    /// "television", "web", "webRip", "bluray", "blurayRaw"...
+    #[serde(default)]
+    pub source: Option<String>,
 }
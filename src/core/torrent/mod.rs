@@ -2,49 +2,45 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::{error, info, warn};
 
-/// Create a .torrent for the given seed directory using the intermodal CLI.
-/// Output file: <seed_dir>/<scene_name>.torrent
-pub fn create_torrent_for_seed_dir(
-    seed_dir: &Path,
-    scene_name: &str,
-    config: &crate::config::Config,
-) -> std::io::Result<PathBuf> {
-    let output_root = config
-        .torrent
-        .output_dir
-        .as_ref()
-        .map(|s| PathBuf::from(s))
-        .unwrap_or_else(|| seed_dir.to_path_buf());
-    let output = output_root.join(format!("{}.torrent", scene_name));
+/// Builds a .torrent file from a directory.
+pub trait TorrentCreator {
+    /// Short identifier used in logs.
+    fn name(&self) -> &str;
 
-    // Idempotency: skip if torrent already exists
-    if output.exists() {
-        info!("Torrent already exists: '{}' — skipping", output.display());
-        return Ok(output);
-    }
+    /// Write a torrent of `source` (following symlinks) to `output`.
+    fn create(&self, source: &Path, output: &Path) -> std::io::Result<()>;
+}
 
-    let mut cmd = Command::new("imdl");
-    cmd.arg("torrent").arg("create").arg("--follow-symlinks");
+/// `imdl torrent create` (intermodal CLI).
+pub struct Intermodal {
+    pub private: bool,
+    pub announce_url: Option<String>,
+}
 
-    if config.torrent.private {
-        cmd.arg("--private");
-    }
-    if let Some(url) = &config.torrent.announce_url {
-        cmd.arg("-a").arg(url);
+impl TorrentCreator for Intermodal {
+    fn name(&self) -> &str {
+        "intermodal"
     }
 
-    // Set explicit output to avoid surprises
-    cmd.arg("--output").arg(&output);
-    // Source directory (the seed dir with symlinks)
-    cmd.arg(seed_dir);
-
-    info!("Creating torrent via intermodal: '{}'", output.display());
-    match cmd.output() {
-        Ok(out) => {
-            if out.status.success() {
-                info!("Torrent created: '{}'", output.display());
-                Ok(output)
-            } else {
+    fn create(&self, source: &Path, output: &Path) -> std::io::Result<()> {
+        let mut cmd = Command::new("imdl");
+        cmd.arg("torrent").arg("create").arg("--follow-symlinks");
+
+        if self.private {
+            cmd.arg("--private");
+        }
+        if let Some(url) = &self.announce_url {
+            cmd.arg("-a").arg(url);
+        }
+
+        // Set explicit output to avoid surprises
+        cmd.arg("--output").arg(output);
+        // Source directory (the seed dir with symlinks)
+        cmd.arg(source);
+
+        match cmd.output() {
+            Ok(out) if out.status.success() => Ok(()),
+            Ok(out) => {
                 error!(
                     "intermodal exited with status {:?}. stderr: {}",
                     out.status,
@@ -55,10 +51,97 @@ pub fn create_torrent_for_seed_dir(
                     "intermodal failed",
                 ))
             }
+            Err(e) => {
+                warn!("Failed to spawn 'imdl': {}. Is intermodal installed?", e);
+                Err(e)
+            }
         }
-        Err(e) => {
-            warn!("Failed to spawn 'imdl': {}. Is intermodal installed?", e);
-            Err(e)
+    }
+}
+
+pub fn from_config(config: &crate::config::TorrentConfig) -> Box<dyn TorrentCreator> {
+    Box::new(Intermodal {
+        private: config.private,
+        announce_url: config.announce_url.clone(),
+    })
+}
+
+/// Create a .torrent for the given seed directory.
+/// Output file: <seed_dir>/<scene_name>.torrent (or under `torrent.output_dir`)
+pub fn create_torrent_for_seed_dir(
+    creator: &dyn TorrentCreator,
+    seed_dir: &Path,
+    scene_name: &str,
+    config: &crate::config::Config,
+) -> std::io::Result<PathBuf> {
+    let output = torrent_path(seed_dir, scene_name, config);
+
+    // Idempotency: skip if torrent already exists
+    if output.exists() {
+        info!("Torrent already exists: '{}' — skipping", output.display());
+        return Ok(output);
+    }
+    let replaced = replaced_torrent_path(&output);
+    if replaced.exists() {
+        info!(
+            "Torrent already replaced by a tracker torrent: '{}' — skipping",
+            replaced.display()
+        );
+        return Ok(replaced);
+    }
+
+    info!(
+        "Creating torrent via {}: '{}'",
+        creator.name(),
+        output.display()
+    );
+    creator.create(seed_dir, &output)?;
+    info!("Torrent created: '{}'", output.display());
+    Ok(output)
+}
+
+fn torrent_path(seed_dir: &Path, scene_name: &str, config: &crate::config::Config) -> PathBuf {
+    config
+        .torrent
+        .output_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| seed_dir.to_path_buf())
+        .join(format!("{}.torrent", scene_name))
+}
+
+/// Move the locally-built torrent of a seed dir (built or [replaced](replaced_torrent_path)) to
+/// `<file>.stale` once its files changed, so the next [`create_torrent_for_seed_dir`] rebuilds
+/// it. Returns its path, which a rebuild writes again.
+pub fn set_stale_torrent_aside(
+    seed_dir: &Path,
+    scene_name: &str,
+    config: &crate::config::Config,
+) -> std::io::Result<PathBuf> {
+    let output = torrent_path(seed_dir, scene_name, config);
+    for torrent in [output.clone(), replaced_torrent_path(&output)] {
+        if torrent.exists() {
+            let stale = stale_path(&torrent);
+            std::fs::rename(&torrent, &stale)?;
+            warn!(
+                "Seed files changed: torrent '{}' moved to '{}'",
+                torrent.display(),
+                stale.display()
+            );
         }
     }
+    Ok(output)
+}
+
+/// `<file>.stale`: where a torrent that no longer matches its seed dir is kept.
+pub fn stale_path(file: &Path) -> PathBuf {
+    let mut stale = file.as_os_str().to_owned();
+    stale.push(".stale");
+    PathBuf::from(stale)
+}
+
+/// Where a locally-built torrent is moved once a tracker-issued torrent replaces it in the
+/// same directory: watch-folder clients only load `*.torrent`, so they don't seed both.
+pub fn replaced_torrent_path(torrent: &Path) -> PathBuf {
+    torrent.with_extension("torrent.local")
 }
@@ -1,48 +1,231 @@
-use crate::core::naming::TechnicalInfo;
+use serde::{Deserialize, Serialize};
 
-/// Builds a Markdown description for tracker upload.
+use crate::core::naming::{DolbyVision, TechnicalInfo};
+
+use super::{ContentKind, ExternalIds};
+
+/// Markup accepted by a tracker for torrent descriptions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DescriptionFormat {
+    /// Markdown with inline HTML `<img>` (Torrust).
+    #[default]
+    Markdown,
+    /// `[b]`, `[img]`, `[list]`... (UNIT3D, Gazelle).
+    #[serde(alias = "bb_code")]
+    Bbcode,
+    Plain,
+}
+
+/// Tracker-agnostic description, rendered per tracker with [`render`].
 ///
-/// Keep this stable & deterministic: it should always rebuild from Radarr + MediaInfo.
-pub fn build_movie_markdown(
+/// Keep this stable & deterministic: it should always rebuild from Radarr/Sonarr + MediaInfo.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Description {
+    /// Display title, e.g. "Heat (1995)" or "Dark — S01E01".
+    pub title: String,
+    /// Scene name of the release.
+    pub release: String,
+    pub poster: Option<Image>,
+    pub synopsis: Option<String>,
+    pub sections: Vec<Section>,
+    pub screenshots: Vec<Image>,
+    pub footer: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub url: String,
+    pub alt: String,
+}
+
+/// Titled block of `label: value` lines (e.g. "Media").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub heading: String,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub label: String,
+    pub value: String,
+}
+
+/// Release data beyond naming inputs: Radarr/Sonarr metadata for templates, screenshots.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReleaseMetadata {
+    pub genres: Vec<String>,
+    /// Minutes.
+    pub runtime: Option<u32>,
+    pub ratings: Vec<Rating>,
+    pub cast: Vec<CastMember>,
+    /// Full MediaInfo text report.
+    pub mediainfo: Option<String>,
+    /// Published screenshot URLs.
+    pub screenshots: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Rating {
+    /// "imdb", "tmdb", "metacritic", "rotten_tomatoes", or "sonarr".
+    pub source: String,
+    pub value: f64,
+    pub votes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CastMember {
+    pub name: String,
+    pub character: Option<String>,
+}
+
+/// Variables available to description templates.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TemplateContext {
+    /// "movie", "series" or "anime".
+    pub kind: String,
+    pub title: String,
+    pub year: Option<u16>,
+    /// Episode/pack heading (Sonarr only), e.g. "S01E01 - Pilot".
+    pub episode: Option<String>,
+    /// Scene name of the release.
+    pub release: String,
+    pub cover_url: Option<String>,
+    pub overview: Option<String>,
+    pub ids: ExternalIds,
+    #[serde(flatten)]
+    pub meta: ReleaseMetadata,
+    pub tech: TechnicalInfo,
+    /// Built-in description, rendered in the tracker's `description_format`.
+    pub default_description: String,
+}
+
+impl TemplateContext {
+    /// Sample context used to check templates: every field present, one element per list.
+    pub fn schema() -> serde_json::Value {
+        let mut tech = TechnicalInfo::default();
+        tech.audio_languages.insert(String::new());
+        tech.subtitle_languages.insert(String::new());
+        tech.dolby_vision = Some(DolbyVision::default());
+        let ctx = TemplateContext {
+            meta: ReleaseMetadata {
+                genres: vec![String::new()],
+                ratings: vec![Rating::default()],
+                cast: vec![CastMember::default()],
+                screenshots: vec![String::new()],
+                ..ReleaseMetadata::default()
+            },
+            tech,
+            ..TemplateContext::default()
+        };
+        serde_json::to_value(ctx).unwrap_or_default()
+    }
+}
+
+pub fn kind_name(kind: ContentKind) -> &'static str {
+    match kind {
+        ContentKind::Movie => "movie",
+        ContentKind::Series => "series",
+        ContentKind::Anime => "anime",
+    }
+}
+
+/// Description of a Radarr movie.
+pub fn movie_description(
     title: &str,
     year: Option<u16>,
     cover_url: Option<&str>,
     overview: Option<&str>,
     scene_name: &str,
     tech: &TechnicalInfo,
-) -> String {
-    let mut md = String::new();
-
-    md.push_str("# ");
-    md.push_str(title);
-    if let Some(y) = year {
-        md.push_str(&format!(" ({y})"));
-    }
-    md.push_str("\n\n");
+    screenshots: &[String],
+) -> Description {
+    let display = match year {
+        Some(y) => format!("{title} ({y})"),
+        None => title.to_string(),
+    };
+    base_description(
+        display,
+        title,
+        cover_url,
+        overview,
+        scene_name,
+        tech,
+        screenshots,
+        "Radarr",
+    )
+}
 
-    md.push_str("**Release:** ");
-    md.push_str(scene_name);
-    md.push_str("\n\n");
+/// Description of a Sonarr episode or pack; `episode_heading` is e.g. "S01E01 - Pilot".
+pub fn episode_description(
+    series_title: &str,
+    episode_heading: &str,
+    cover_url: Option<&str>,
+    overview: Option<&str>,
+    scene_name: &str,
+    tech: &TechnicalInfo,
+    screenshots: &[String],
+) -> Description {
+    let display = if episode_heading.trim().is_empty() {
+        series_title.to_string()
+    } else {
+        format!("{series_title} — {episode_heading}")
+    };
+    base_description(
+        display,
+        series_title,
+        cover_url,
+        overview,
+        scene_name,
+        tech,
+        screenshots,
+        "Sonarr",
+    )
+}
 
-    if let Some(url) = cover_url {
-        let url = url.trim();
-        if (url.starts_with("https://") || url.starts_with("http://")) && !url.is_empty() {
-            let alt = escape_html_attr(title);
-            let src = escape_html_attr(url);
-            md.push_str(&format!("<img src=\"{src}\" alt=\"{alt}\" />\n\n"));
-        }
-    }
+fn base_description(
+    display_title: String,
+    poster_alt: &str,
+    cover_url: Option<&str>,
+    overview: Option<&str>,
+    scene_name: &str,
+    tech: &TechnicalInfo,
+    screenshots: &[String],
+    source: &str,
+) -> Description {
+    let poster = cover_url
+        .map(str::trim)
+        .filter(|u| u.starts_with("https://") || u.starts_with("http://"))
+        .map(|url| Image {
+            url: url.to_string(),
+            alt: poster_alt.to_string(),
+        });
+    let synopsis = overview
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
 
-    if let Some(ov) = overview {
-        let ov = ov.trim();
-        if !ov.is_empty() {
-            md.push_str("## Synopsis\n\n");
-            md.push_str(ov);
-            md.push_str("\n\n");
-        }
+    Description {
+        title: display_title,
+        release: scene_name.to_string(),
+        poster,
+        synopsis,
+        sections: vec![media_section(tech)],
+        screenshots: screenshots
+            .iter()
+            .enumerate()
+            .map(|(i, url)| Image {
+                url: url.clone(),
+                alt: format!("Screenshot {}", i + 1),
+            })
+            .collect(),
+        footer: Some(format!("Generated by seedarr from {source} + MediaInfo.")),
     }
+}
 
-    md.push_str("## Media\n\n");
+fn media_section(tech: &TechnicalInfo) -> Section {
+    let mut fields = Vec::new();
 
     // Video
     let mut video_bits = Vec::new();
@@ -55,17 +238,14 @@ pub fn build_movie_markdown(
     if let Some(bd) = &tech.bit_depth {
         video_bits.push(bd.clone());
     }
-    if tech.dv {
-        video_bits.push("DV".to_string());
+    if let Some(dv) = &tech.dolby_vision {
+        video_bits.push(dolby_vision_label(dv));
     }
-    if tech.hdr && !tech.dv {
-        video_bits.push("HDR".to_string());
+    for f in &tech.hdr_formats {
+        video_bits.push(f.label().to_string());
     }
-
     if !video_bits.is_empty() {
-        md.push_str("- **Video:** ");
-        md.push_str(&video_bits.join(" "));
-        md.push('\n');
+        fields.push(field("Video", video_bits.join(" ")));
     }
 
     // Audio
@@ -76,6 +256,9 @@ pub fn build_movie_markdown(
     if let Some(ch) = &tech.audio_channels {
         audio_bits.push(ch.clone());
     }
+    if tech.atmos {
+        audio_bits.push("Atmos".to_string());
+    }
     if !tech.audio_languages.is_empty() {
         let langs = tech
             .audio_languages
@@ -89,9 +272,7 @@ pub fn build_movie_markdown(
         audio_bits.push("VFI".to_string());
     }
     if !audio_bits.is_empty() {
-        md.push_str("- **Audio:** ");
-        md.push_str(&audio_bits.join(" "));
-        md.push('\n');
+        fields.push(field("Audio", audio_bits.join(" ")));
     }
 
     // Subtitles
@@ -102,129 +283,185 @@ pub fn build_movie_markdown(
             .cloned()
             .collect::<Vec<_>>()
             .join(", ");
-        md.push_str("- **Subtitles:** ");
-        md.push_str(&langs);
-        md.push('\n');
+        fields.push(field("Subtitles", langs));
     }
 
-    md.push_str("\n---\n\n");
-    md.push_str("_Generated by seedarr from Radarr + MediaInfo._\n");
+    Section {
+        heading: "Media".to_string(),
+        fields,
+    }
+}
 
-    md
+/// "Dolby Vision profile 8, level 6 (HDR10 compatible)"; profile 5 reads "(no HDR10 fallback)".
+fn dolby_vision_label(dv: &DolbyVision) -> String {
+    let mut label = "Dolby Vision".to_string();
+    if let Some(p) = dv.profile {
+        label.push_str(&format!(" profile {p}"));
+        if let Some(l) = dv.level {
+            label.push_str(&format!(", level {l}"));
+        }
+    }
+    match &dv.compatibility {
+        Some(c) => label.push_str(&format!(" ({c} compatible)")),
+        None if dv.profile == Some(5) => label.push_str(" (no HDR10 fallback)"),
+        None => {}
+    }
+    label
 }
 
-/// Builds a Markdown description for a Sonarr episode upload.
-///
-/// Keep this stable & deterministic: it should always rebuild from Sonarr + MediaInfo.
-pub fn build_episode_markdown(
-    series_title: &str,
-    episode_heading: &str,
-    cover_url: Option<&str>,
-    overview: Option<&str>,
-    scene_name: &str,
-    tech: &TechnicalInfo,
-) -> String {
+fn field(label: &str, value: String) -> Field {
+    Field {
+        label: label.to_string(),
+        value,
+    }
+}
+
+pub fn render(doc: &Description, format: DescriptionFormat) -> String {
+    match format {
+        DescriptionFormat::Markdown => render_markdown(doc),
+        DescriptionFormat::Bbcode => render_bbcode(doc),
+        DescriptionFormat::Plain => render_plain(doc),
+    }
+}
+
+fn render_markdown(doc: &Description) -> String {
     let mut md = String::new();
 
-    md.push_str("# ");
-    md.push_str(series_title);
-    if !episode_heading.trim().is_empty() {
-        md.push_str(" — ");
-        md.push_str(episode_heading);
+    md.push_str(&format!("# {}\n\n", doc.title));
+    md.push_str(&format!("**Release:** {}\n\n", doc.release));
+
+    if let Some(img) = &doc.poster {
+        let alt = escape_html_attr(&img.alt);
+        let src = escape_html_attr(&img.url);
+        md.push_str(&format!("<img src=\"{src}\" alt=\"{alt}\" />\n\n"));
     }
-    md.push_str("\n\n");
 
-    md.push_str("**Release:** ");
-    md.push_str(scene_name);
-    md.push_str("\n\n");
+    if let Some(ov) = &doc.synopsis {
+        md.push_str(&format!("## Synopsis\n\n{ov}\n\n"));
+    }
 
-    if let Some(url) = cover_url {
-        let url = url.trim();
-        if (url.starts_with("https://") || url.starts_with("http://")) && !url.is_empty() {
-            let alt = escape_html_attr(series_title);
-            let src = escape_html_attr(url);
-            md.push_str(&format!("<img src=\"{src}\" alt=\"{alt}\" />\n\n"));
+    for section in &doc.sections {
+        md.push_str(&format!("## {}\n\n", section.heading));
+        for f in &section.fields {
+            md.push_str(&format!("- **{}:** {}\n", f.label, f.value));
         }
+        md.push('\n');
     }
 
-    if let Some(ov) = overview {
-        let ov = ov.trim();
-        if !ov.is_empty() {
-            md.push_str("## Synopsis\n\n");
-            md.push_str(ov);
-            md.push_str("\n\n");
+    if !doc.screenshots.is_empty() {
+        md.push_str("## Screenshots\n\n");
+        for img in &doc.screenshots {
+            let alt = escape_html_attr(&img.alt);
+            let src = escape_html_attr(&img.url);
+            md.push_str(&format!("<img src=\"{src}\" alt=\"{alt}\" />\n"));
         }
+        md.push('\n');
     }
 
-    md.push_str("## Media\n\n");
-
-    // Video
-    let mut video_bits = Vec::new();
-    if let Some(res) = &tech.resolution {
-        video_bits.push(res.clone());
-    }
-    if let Some(v) = &tech.video_codec {
-        video_bits.push(v.clone());
+    if let Some(footer) = &doc.footer {
+        md.push_str(&format!("---\n\n_{footer}_\n"));
     }
-    if let Some(bd) = &tech.bit_depth {
-        video_bits.push(bd.clone());
+
+    md
+}
+
+fn render_bbcode(doc: &Description) -> String {
+    let mut bb = String::new();
+
+    bb.push_str(&format!(
+        "[size=5][b]{}[/b][/size]\n\n",
+        bb_text(&doc.title)
+    ));
+    bb.push_str(&format!("[b]Release:[/b] {}\n\n", bb_text(&doc.release)));
+
+    if let Some(img) = &doc.poster {
+        // BBCode has no escaping: a bracket in the URL would break the tag.
+        if !img.url.contains(['[', ']']) {
+            bb.push_str(&format!("[img]{}[/img]\n\n", img.url));
+        }
     }
-    if tech.dv {
-        video_bits.push("DV".to_string());
+
+    if let Some(ov) = &doc.synopsis {
+        bb.push_str(&format!("[b]Synopsis[/b]\n{}\n\n", bb_text(ov)));
     }
-    if tech.hdr && !tech.dv {
-        video_bits.push("HDR".to_string());
+
+    for section in &doc.sections {
+        bb.push_str(&format!("[b]{}[/b]\n", bb_text(&section.heading)));
+        if !section.fields.is_empty() {
+            bb.push_str("[list]\n");
+            for f in &section.fields {
+                bb.push_str(&format!(
+                    "[*][b]{}:[/b] {}\n",
+                    bb_text(&f.label),
+                    bb_text(&f.value)
+                ));
+            }
+            bb.push_str("[/list]\n");
+        }
+        bb.push('\n');
     }
 
-    if !video_bits.is_empty() {
-        md.push_str("- **Video:** ");
-        md.push_str(&video_bits.join(" "));
-        md.push('\n');
+    let screenshots: Vec<&Image> = doc
+        .screenshots
+        .iter()
+        .filter(|img| !img.url.contains(['[', ']']))
+        .collect();
+    if !screenshots.is_empty() {
+        bb.push_str("[b]Screenshots[/b]\n");
+        for img in screenshots {
+            bb.push_str(&format!("[img]{}[/img]\n", img.url));
+        }
+        bb.push('\n');
     }
 
-    // Audio
-    let mut audio_bits = Vec::new();
-    if let Some(a) = &tech.audio_codec {
-        audio_bits.push(a.clone());
+    if let Some(footer) = &doc.footer {
+        bb.push_str(&format!("[i]{}[/i]\n", bb_text(footer)));
     }
-    if let Some(ch) = &tech.audio_channels {
-        audio_bits.push(ch.clone());
+
+    bb
+}
+
+fn render_plain(doc: &Description) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!("{}\n\n", doc.title));
+    out.push_str(&format!("Release: {}\n\n", doc.release));
+
+    if let Some(img) = &doc.poster {
+        out.push_str(&format!("Poster: {}\n\n", img.url));
     }
-    if !tech.audio_languages.is_empty() {
-        let langs = tech
-            .audio_languages
-            .iter()
-            .cloned()
-            .collect::<Vec<_>>()
-            .join(", ");
-        audio_bits.push(format!("langs: {langs}"));
+
+    if let Some(ov) = &doc.synopsis {
+        out.push_str(&format!("Synopsis\n{ov}\n\n"));
     }
-    if tech.has_vfi {
-        audio_bits.push("VFI".to_string());
+
+    for section in &doc.sections {
+        out.push_str(&format!("{}\n", section.heading));
+        for f in &section.fields {
+            out.push_str(&format!("- {}: {}\n", f.label, f.value));
+        }
+        out.push('\n');
     }
-    if !audio_bits.is_empty() {
-        md.push_str("- **Audio:** ");
-        md.push_str(&audio_bits.join(" "));
-        md.push('\n');
+
+    if !doc.screenshots.is_empty() {
+        out.push_str("Screenshots\n");
+        for img in &doc.screenshots {
+            out.push_str(&format!("- {}\n", img.url));
+        }
+        out.push('\n');
     }
 
-    // Subtitles
-    if !tech.subtitle_languages.is_empty() {
-        let langs = tech
-            .subtitle_languages
-            .iter()
-            .cloned()
-            .collect::<Vec<_>>()
-            .join(", ");
-        md.push_str("- **Subtitles:** ");
-        md.push_str(&langs);
-        md.push('\n');
+    if let Some(footer) = &doc.footer {
+        out.push_str(footer);
+        out.push('\n');
     }
 
-    md.push_str("\n---\n\n");
-    md.push_str("_Generated by seedarr from Sonarr + MediaInfo._\n");
+    out
+}
 
-    md
+/// Turns brackets in user text into parentheses so it can't open or close BBCode tags.
+fn bb_text(s: &str) -> String {
+    s.replace('[', "(").replace(']', ")")
 }
 
 fn escape_html_attr(s: &str) -> String {
@@ -234,3 +471,107 @@ fn escape_html_attr(s: &str) -> String {
         .replace('>', "&gt;")
         .replace('"', "&quot;")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::upload::template::Template;
+
+    fn sample() -> Description {
+        let tech = TechnicalInfo {
+            resolution: Some("1080p".to_string()),
+            video_codec: Some("x264".to_string()),
+            audio_codec: Some("DTS".to_string()),
+            audio_channels: Some("5.1".to_string()),
+            ..TechnicalInfo::default()
+        };
+        movie_description(
+            "Heat",
+            Some(1995),
+            Some("https://img.example/heat.jpg"),
+            Some("  A group of robbers...  "),
+            "Heat.1995.1080p.BluRay.x264-GRP",
+            &tech,
+            &[],
+        )
+    }
+
+    #[test]
+    fn renders_markdown() {
+        assert_eq!(
+            render(&sample(), DescriptionFormat::Markdown),
+            "# Heat (1995)\n\n\
+             **Release:** Heat.1995.1080p.BluRay.x264-GRP\n\n\
+             <img src=\"https://img.example/heat.jpg\" alt=\"Heat\" />\n\n\
+             ## Synopsis\n\nA group of robbers...\n\n\
+             ## Media\n\n- **Video:** 1080p x264\n- **Audio:** DTS 5.1\n\n\
+             ---\n\n_Generated by seedarr from Radarr + MediaInfo._\n"
+        );
+    }
+
+    #[test]
+    fn renders_bbcode() {
+        assert_eq!(
+            render(&sample(), DescriptionFormat::Bbcode),
+            "[size=5][b]Heat (1995)[/b][/size]\n\n\
+             [b]Release:[/b] Heat.1995.1080p.BluRay.x264-GRP\n\n\
+             [img]https://img.example/heat.jpg[/img]\n\n\
+             [b]Synopsis[/b]\nA group of robbers...\n\n\
+             [b]Media[/b]\n[list]\n[*][b]Video:[/b] 1080p x264\n[*][b]Audio:[/b] DTS 5.1\n[/list]\n\n\
+             [i]Generated by seedarr from Radarr + MediaInfo.[/i]\n"
+        );
+    }
+
+    #[test]
+    fn drops_non_http_poster_and_blank_synopsis() {
+        let doc = episode_description(
+            "Dark",
+            "",
+            Some("/local/poster.jpg"),
+            Some("   "),
+            "Dark.S01.1080p.WEB.x264-GRP",
+            &TechnicalInfo::default(),
+            &[],
+        );
+        assert_eq!(doc.title, "Dark");
+        assert!(doc.poster.is_none());
+        assert!(doc.synopsis.is_none());
+    }
+
+    #[test]
+    fn renders_screenshots_after_media() {
+        let mut doc = sample();
+        doc.screenshots = vec![Image {
+            url: "https://img.example/01.png".to_string(),
+            alt: "Screenshot 1".to_string(),
+        }];
+        let bb = render(&doc, DescriptionFormat::Bbcode);
+        assert!(bb.contains(
+            "[/list]\n\n[b]Screenshots[/b]\n[img]https://img.example/01.png[/img]\n\n[i]"
+        ));
+        let md = render(&doc, DescriptionFormat::Markdown);
+        assert!(md.contains(
+            "## Screenshots\n\n<img src=\"https://img.example/01.png\" alt=\"Screenshot 1\" />\n\n---"
+        ));
+    }
+
+    #[test]
+    fn bbcode_neutralizes_brackets_in_text() {
+        let mut doc = sample();
+        doc.synopsis = Some("Robbers [/b][url=https://evil.example]win[/url]]".to_string());
+        let bb = render(&doc, DescriptionFormat::Bbcode);
+        assert!(
+            bb.contains("[b]Synopsis[/b]\nRobbers (/b)(url=https://evil.example)win(/url))\n\n")
+        );
+    }
+
+    #[test]
+    fn schema_exposes_dolby_vision_fields() {
+        let template = Template::parse(
+            "{% if tech.dv %}DV {{ tech.dolby_vision.profile }} \
+             {{ tech.dolby_vision.compatibility }}{% endif %}",
+        )
+        .unwrap();
+        assert_eq!(template.check(&TemplateContext::schema()), Ok(()));
+    }
+}
@@ -0,0 +1,359 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::naming::{parse_scene_name, transliterate, Source};
+
+use super::ContentKind;
+
+// Episode/pack marker used to tell "S01" from "S01E02" releases of the same series.
+static EPISODE_TAG_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(S\d{2}(E\d{2,3})*|E\d{3}|INTEGRALE)\b").unwrap());
+
+/// What to do when the tracker already carries a release of the same title.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DupePolicy {
+    /// Never search, always upload.
+    #[default]
+    Always,
+    /// Skip when a release with the same resolution and source already exists.
+    SkipSameResolutionSource,
+    /// Skip when an existing release is at least as good (resolution first, then source).
+    /// Releases whose quality can't be compared (unknown resolution or source) don't block.
+    SkipIfBetter,
+}
+
+/// Search criteria for the release we're about to upload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DupeQuery {
+    pub kind: ContentKind,
+    pub title: String,
+    pub year: Option<u16>,
+    pub tmdb_id: Option<i64>,
+    pub imdb_id: Option<String>,
+    pub tvdb_id: Option<i64>,
+    pub scene_name: String,
+    pub resolution: Option<String>,
+    pub source: Option<String>,
+}
+
+/// A release found on the tracker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExistingRelease {
+    pub name: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DupeVerdict {
+    Upload,
+    Skip { existing: ExistingRelease },
+}
+
+/// Apply `policy` to the releases returned by the tracker search.
+///
+/// Search results are fuzzy, so releases whose parsed title/year/episode tag don't match
+/// the query are ignored.
+pub fn evaluate(policy: DupePolicy, query: &DupeQuery, found: &[ExistingRelease]) -> DupeVerdict {
+    if policy == DupePolicy::Always {
+        return DupeVerdict::Upload;
+    }
+
+    let ours = Quality::new(query.resolution.as_deref(), query.source.as_deref());
+
+    for existing in found
+        .iter()
+        .filter(|r| is_same_release_target(query, &r.name))
+    {
+        let parts = parse_scene_name(&existing.name);
+        let theirs = Quality::new(parts.resolution.as_deref(), parts.source.as_deref());
+
+        let conflict = match policy {
+            DupePolicy::Always => false,
+            DupePolicy::SkipSameResolutionSource => theirs.is_same(&ours),
+            DupePolicy::SkipIfBetter => theirs.is_at_least(&ours),
+        };
+        if conflict {
+            return DupeVerdict::Skip {
+                existing: existing.clone(),
+            };
+        }
+    }
+
+    DupeVerdict::Upload
+}
+
+fn is_same_release_target(query: &DupeQuery, existing_name: &str) -> bool {
+    let existing_title = release_title_key(existing_name);
+    // The display title may differ from the one in names (tracker title strategy, accents):
+    // also compare with the title of the release name we built.
+    if existing_title.is_empty()
+        || (existing_title != release_title_key(&query.scene_name)
+            && existing_title != title_key(&query.title))
+    {
+        return false;
+    }
+
+    let parts = parse_scene_name(existing_name);
+    if let (Some(a), Some(b)) = (query.year, parts.year) {
+        if a != b {
+            return false;
+        }
+    }
+
+    let tag = |s: &str| {
+        EPISODE_TAG_RE
+            .find(s)
+            .map(|m| m.as_str().to_ascii_uppercase())
+    };
+    tag(&query.scene_name) == tag(existing_name)
+}
+
+/// Lower-case ASCII letters and digits of `s`, accents folded ("Amélie" -> "amelie").
+fn title_key(s: &str) -> String {
+    transliterate::to_ascii(s)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// [`title_key`] of the title part of a release name.
+fn release_title_key(name: &str) -> String {
+    // The parser stops title tokens at the year; without a year they run until the episode tag.
+    EPISODE_TAG_RE
+        .split(&parse_scene_name(name).title_tokens.join("."))
+        .next()
+        .map(title_key)
+        .unwrap_or_default()
+}
+
+/// Release quality; either part may be unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Quality {
+    resolution: Option<u8>,
+    source: Option<Source>,
+}
+
+impl Quality {
+    fn new(resolution: Option<&str>, source: Option<&str>) -> Self {
+        Self {
+            resolution: resolution.and_then(resolution_rank),
+            source: source.and_then(Source::from_token),
+        }
+    }
+
+    /// Same resolution and exactly the same source (a WEBRip is not a WEB-DL).
+    fn is_same(&self, other: &Quality) -> bool {
+        self.resolution.is_some()
+            && self.source.is_some()
+            && self.resolution == other.resolution
+            && self.source == other.source
+    }
+
+    /// At least as good as `other`: resolution first, then source. False when they can't be
+    /// compared.
+    fn is_at_least(&self, other: &Quality) -> bool {
+        match (self.resolution, other.resolution) {
+            (Some(a), Some(b)) if a != b => a > b,
+            (Some(_), Some(_)) => match (self.source, other.source) {
+                (Some(a), Some(b)) => source_rank(a) >= source_rank(b),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+fn resolution_rank(res: &str) -> Option<u8> {
+    let rank = match res.to_ascii_lowercase().as_str() {
+        "2160p" | "4k" => 5,
+        "1440p" => 4,
+        "1080p" => 3,
+        "720p" => 2,
+        "576p" | "480p" => 1,
+        _ => return None,
+    };
+    Some(rank)
+}
+
+/// WEB releases of unknown kind are ranked with WEB-DL, the usual meaning of a scene "WEB".
+fn source_rank(source: Source) -> u8 {
+    match source {
+        Source::Remux => 6,
+        Source::BluRay => 5,
+        Source::WebDl | Source::Web => 4,
+        Source::WebRip => 3,
+        Source::Hdtv => 2,
+        Source::Sdtv | Source::Dvd => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(scene: &str) -> DupeQuery {
+        DupeQuery {
+            kind: ContentKind::Movie,
+            title: "Interstellar".to_string(),
+            year: Some(2014),
+            tmdb_id: Some(157336),
+            imdb_id: None,
+            tvdb_id: None,
+            scene_name: scene.to_string(),
+            resolution: Some("1080p".to_string()),
+            source: Some("BluRay".to_string()),
+        }
+    }
+
+    fn release(name: &str) -> ExistingRelease {
+        ExistingRelease {
+            name: name.to_string(),
+            url: None,
+        }
+    }
+
+    #[test]
+    fn always_never_skips() {
+        let q = query("Interstellar.2014.MULTi.VF.1080p.BluRay.x264-GRP");
+        let found = [release(
+            "Interstellar.2014.MULTi.VF.1080p.BluRay.x264-OTHER",
+        )];
+        assert_eq!(
+            evaluate(DupePolicy::Always, &q, &found),
+            DupeVerdict::Upload
+        );
+    }
+
+    #[test]
+    fn same_resolution_and_source_is_a_dupe() {
+        let q = query("Interstellar.2014.MULTi.VF.1080p.BluRay.x264-GRP");
+        let found = [
+            release("Interstellar.2014.MULTi.VF.2160p.WEB.x265-OTHER"),
+            release("Interstellar.2014.VOSTFR.1080p.BluRay.x265-OTHER"),
+        ];
+        assert!(matches!(
+            evaluate(DupePolicy::SkipSameResolutionSource, &q, &found),
+            DupeVerdict::Skip { existing } if existing.name.contains("1080p")
+        ));
+    }
+
+    #[test]
+    fn better_release_blocks_only_with_skip_if_better() {
+        let q = query("Interstellar.2014.MULTi.VF.1080p.BluRay.x264-GRP");
+        let found = [release("Interstellar.2014.MULTi.VF.2160p.WEB.x265-OTHER")];
+        assert_eq!(
+            evaluate(DupePolicy::SkipSameResolutionSource, &q, &found),
+            DupeVerdict::Upload
+        );
+        assert!(matches!(
+            evaluate(DupePolicy::SkipIfBetter, &q, &found),
+            DupeVerdict::Skip { .. }
+        ));
+    }
+
+    #[test]
+    fn other_titles_and_years_are_ignored() {
+        let q = query("Interstellar.2014.MULTi.VF.1080p.BluRay.x264-GRP");
+        let found = [
+            release("Interstellar.Wars.2016.1080p.BluRay.x264-OTHER"),
+            release("Interstellar.1999.1080p.BluRay.x264-OTHER"),
+        ];
+        assert_eq!(
+            evaluate(DupePolicy::SkipIfBetter, &q, &found),
+            DupeVerdict::Upload
+        );
+    }
+
+    #[test]
+    fn season_pack_does_not_conflict_with_episode() {
+        let mut q = query("Dark.S01.MULTi.1080p.WEB.x264-GRP");
+        q.title = "Dark".to_string();
+        q.year = None;
+        q.source = Some("WEB".to_string());
+        let found = [release("Dark.S01E01.MULTi.1080p.WEB.x264-OTHER")];
+        assert_eq!(
+            evaluate(DupePolicy::SkipSameResolutionSource, &q, &found),
+            DupeVerdict::Upload
+        );
+        let found = [release("Dark.S01.MULTi.1080p.WEB.x265-OTHER")];
+        assert!(matches!(
+            evaluate(DupePolicy::SkipSameResolutionSource, &q, &found),
+            DupeVerdict::Skip { .. }
+        ));
+    }
+
+    #[test]
+    fn titles_match_across_title_strategies_and_accents() {
+        let mut q = query("Amelie.2001.MULTi.1080p.BluRay.x264-GRP");
+        q.title = "Le Fabuleux Destin d'Amélie Poulain".to_string();
+        q.year = Some(2001);
+        for name in [
+            "Amelie.2001.1080p.BluRay.x265-OTHER",
+            "Le.Fabuleux.Destin.d.Amelie.Poulain.2001.1080p.BluRay.x265-OTHER",
+        ] {
+            assert!(
+                matches!(
+                    evaluate(DupePolicy::SkipSameResolutionSource, &q, &[release(name)]),
+                    DupeVerdict::Skip { .. }
+                ),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn web_dl_and_webrip_are_told_apart() {
+        let mut q = query("Interstellar.2014.MULTi.VF.1080p.WEB-DL.x264-GRP");
+        q.source = Some("WEB-DL".to_string());
+        let webrip = [release(
+            "Interstellar.2014.MULTi.VF.1080p.WEBRip.x264-OTHER",
+        )];
+        assert_eq!(
+            evaluate(DupePolicy::SkipSameResolutionSource, &q, &webrip),
+            DupeVerdict::Upload
+        );
+        assert_eq!(
+            evaluate(DupePolicy::SkipIfBetter, &q, &webrip),
+            DupeVerdict::Upload
+        );
+
+        q.source = Some("WEBRip".to_string());
+        let web_dl = [release(
+            "Interstellar.2014.MULTi.VF.1080p.WEB-DL.x264-OTHER",
+        )];
+        assert!(matches!(
+            evaluate(DupePolicy::SkipIfBetter, &q, &web_dl),
+            DupeVerdict::Skip { .. }
+        ));
+        q.source = Some("HDTV".to_string());
+        assert!(matches!(
+            evaluate(DupePolicy::SkipIfBetter, &q, &webrip),
+            DupeVerdict::Skip { .. }
+        ));
+    }
+
+    #[test]
+    fn unknown_resolution_cannot_block() {
+        let q = query("Interstellar.2014.MULTi.VF.1080p.BluRay.x264-GRP");
+        let found = [release("Interstellar.2014.MULTi.VF.BluRay.x264-OTHER")];
+        assert_eq!(
+            evaluate(DupePolicy::SkipIfBetter, &q, &found),
+            DupeVerdict::Upload
+        );
+
+        let mut q = q;
+        q.resolution = None;
+        let found = [release("Interstellar.2014.MULTi.VF.720p.HDTV.x264-OTHER")];
+        assert_eq!(
+            evaluate(DupePolicy::SkipIfBetter, &q, &found),
+            DupeVerdict::Upload
+        );
+        assert_eq!(
+            evaluate(DupePolicy::SkipSameResolutionSource, &q, &found),
+            DupeVerdict::Upload
+        );
+    }
+}
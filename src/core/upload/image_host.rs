@@ -0,0 +1,155 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+
+use crate::config::ImageHostConfig;
+use crate::utils::http::HttpClient;
+use crate::utils::Error;
+
+/// Publishes screenshots and returns a URL usable in descriptions.
+#[async_trait]
+pub trait ImageHost: Send + Sync {
+    /// Short identifier used in logs and the URL cache (e.g. "chevereto").
+    fn name(&self) -> &str;
+
+    async fn upload_image(&self, path: &Path) -> Result<String, Error>;
+}
+
+pub fn from_config(
+    cfg: &ImageHostConfig,
+    seed_root: &Path,
+    http: &HttpClient,
+) -> Box<dyn ImageHost> {
+    match cfg {
+        ImageHostConfig::Local { base_url } => Box::new(LocalImageHost {
+            base_url: base_url.clone(),
+            seed_root: seed_root.to_path_buf(),
+        }),
+        ImageHostConfig::Chevereto { api_base, api_key } => Box::new(CheveretoImageHost {
+            api_base: api_base.clone(),
+            api_key: api_key.clone(),
+            http: http.clone(),
+        }),
+    }
+}
+
+/// Stand-in host: files stay where they were captured.
+pub struct LocalImageHost {
+    /// Public URL of `seed_root`, when it is served over HTTP.
+    base_url: Option<String>,
+    seed_root: PathBuf,
+}
+
+#[async_trait]
+impl ImageHost for LocalImageHost {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    async fn upload_image(&self, path: &Path) -> Result<String, Error> {
+        let Some(base) = &self.base_url else {
+            return Ok(format!("file://{}", path.display()));
+        };
+        let rel = path.strip_prefix(&self.seed_root).map_err(|_| {
+            Error::Other(format!(
+                "Screenshot '{}' is outside seed_path '{}'",
+                path.display(),
+                self.seed_root.display()
+            ))
+        })?;
+        let rel = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        Ok(format!("{}/{rel}", base.trim_end_matches('/')))
+    }
+}
+
+pub struct CheveretoImageHost {
+    api_base: String,
+    api_key: String,
+    http: HttpClient,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheveretoResponse {
+    image: CheveretoImage,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheveretoImage {
+    url: String,
+}
+
+#[async_trait]
+impl ImageHost for CheveretoImageHost {
+    fn name(&self) -> &str {
+        "chevereto"
+    }
+
+    async fn upload_image(&self, path: &Path) -> Result<String, Error> {
+        let bytes = tokio::fs::read(path).await.map_err(|e| {
+            Error::Other(format!(
+                "Failed to read screenshot '{}': {e}",
+                path.display()
+            ))
+        })?;
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "screenshot.png".to_string());
+
+        let url = format!("{}/api/1/upload", self.api_base.trim_end_matches('/'));
+        let resp = self
+            .http
+            .send(&url, |client| {
+                // Multipart bodies can't be cloned: rebuild the form on each attempt.
+                let form = Form::new().text("format", "json").part(
+                    "source",
+                    Part::bytes(bytes.clone()).file_name(file_name.clone()),
+                );
+                client
+                    .post(&url)
+                    .header("X-API-Key", &self.api_key)
+                    .multipart(form)
+            })
+            .await?;
+
+        let status = resp.status();
+        let body = resp.text().await?;
+        if !status.is_success() {
+            return Err(Error::Other(format!(
+                "Image upload failed ({status}): {body}"
+            )));
+        }
+        let parsed: CheveretoResponse = serde_json::from_str(&body)
+            .map_err(|e| Error::Other(format!("Unexpected image host response: {e}")))?;
+        Ok(parsed.image.url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn local_host_links_under_base_url() {
+        let host = LocalImageHost {
+            base_url: Some("https://static.example/seed/".to_string()),
+            seed_root: PathBuf::from("/data/seed"),
+        };
+        assert_eq!(
+            host.upload_image(Path::new("/data/seed/Heat.1995.screens/01.png"))
+                .await
+                .unwrap(),
+            "https://static.example/seed/Heat.1995.screens/01.png"
+        );
+        assert!(host
+            .upload_image(Path::new("/elsewhere/01.png"))
+            .await
+            .is_err());
+    }
+}
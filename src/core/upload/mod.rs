@@ -1,36 +1,156 @@
 pub mod description;
+pub mod dupe;
+pub mod image_host;
+pub mod queue;
+pub mod screenshots;
+pub mod status;
+pub mod template;
 pub mod torrust;
 
 use async_trait::async_trait;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::utils::http::HttpClient;
 use crate::utils::Error;
 
+use crate::config::TitleStrategy;
+use crate::core::naming::titles::{self, TitleCandidates};
 use crate::core::naming::TechnicalInfo;
+use crate::core::torrent::{replaced_torrent_path, stale_path};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use description::{Description, DescriptionFormat, ReleaseMetadata, TemplateContext};
+use dupe::{DupePolicy, DupeQuery, DupeVerdict, ExistingRelease};
+use queue::UploadQueue;
+use screenshots::ScreenshotPublisher;
+use status::UploadStatusStore;
+use template::Template;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ContentKind {
     Movie,
     Series,
     Anime,
 }
 
-#[derive(Debug, Clone)]
+/// External database ids of the uploaded title, used to look up existing releases.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExternalIds {
+    pub tmdb_id: Option<i64>,
+    pub imdb_id: Option<String>,
+    pub tvdb_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadRequest {
     pub title: String,
-    pub description_markdown: String,
+    /// Rendered in the uploader's `description_format()`.
+    #[serde(alias = "description_markdown")]
+    pub description: String,
     pub torrent_path: PathBuf,
     pub kind: ContentKind,
 }
 
+/// What a tracker reports back for an accepted upload.
+///
+/// All fields are optional: some trackers (or some responses, e.g. "already exists")
+/// don't expose them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadedTorrent {
+    /// Tracker-side identifier of the torrent.
+    pub torrent_id: Option<String>,
+    /// Human-facing page of the torrent on the tracker.
+    pub url: Option<String>,
+    /// Link to the tracker-issued `.torrent` (passkey announce, source flag, ...).
+    pub download_url: Option<String>,
+}
+
+/// Result of uploading one release to one tracker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadOutcome {
+    Uploaded(UploadedTorrent),
+    /// The tracker already carries an equivalent release (per `dupe_policy`).
+    Duplicate {
+        existing: String,
+    },
+    /// Not attempted this run (dry-run, already uploaded, queued for retry).
+    Skipped {
+        reason: String,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+impl UploadOutcome {
+    /// Final outcomes are never retried: the release is on the tracker one way or another.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Uploaded(_) | Self::Duplicate { .. })
+    }
+}
+
+/// Outcome of an upload for a given tracker.
+#[derive(Debug, Clone)]
+pub struct TrackerOutcome {
+    /// `TrackerUploader::name()`.
+    pub tracker: String,
+    pub outcome: UploadOutcome,
+}
+
 #[async_trait]
 pub trait TrackerUploader: Send + Sync {
-    async fn upload_torrent(&self, req: UploadRequest) -> Result<(), Error>;
+    /// Short identifier used in logs and file names (e.g. "torrust").
+    fn name(&self) -> &str;
+
+    /// Markup the tracker expects in descriptions.
+    fn description_format(&self) -> DescriptionFormat {
+        DescriptionFormat::Markdown
+    }
+
+    /// Upload the torrent: `Uploaded`, or `Duplicate` when the tracker refuses it as
+    /// already present.
+    async fn upload_torrent(&self, req: UploadRequest) -> Result<UploadOutcome, Error>;
+
+    /// Fetch the `.torrent` as rewritten by the tracker after upload.
+    ///
+    /// Private trackers usually change the infohash (announce, source flag), so this is the
+    /// file the seeding client must load.
+    async fn download_torrent(&self, uploaded: &UploadedTorrent) -> Result<Vec<u8>, Error>;
+
+    /// Look up releases of the same title already on the tracker.
+    ///
+    /// Returns `Ok(None)` when the tracker has no search API; the duplicate check is then skipped.
+    async fn search_existing(
+        &self,
+        _query: &DupeQuery,
+    ) -> Result<Option<Vec<ExistingRelease>>, Error> {
+        Ok(None)
+    }
 }
 
 pub struct UploadService {
     enabled: bool,
     dry_run: bool,
+    /// Where tracker-issued torrents are written (seeding client watch dir).
+    /// None: next to the locally-built torrent.
+    seed_torrent_dir: Option<PathBuf>,
+    dupe_policy: DupePolicy,
+    /// Failed uploads, retried on later runs (or `retry-uploads`).
+    queue: Mutex<UploadQueue>,
+    /// Last outcome per (release, tracker): reruns only target trackers without a final one.
+    status: Mutex<UploadStatusStore>,
+    /// Description templates by tracker name; others use the built-in description.
+    templates: HashMap<String, Template>,
+    /// Title strategy and locale by tracker name: `[media]`'s unless the tracker overrides them.
+    titles: HashMap<String, (TitleStrategy, Option<String>)>,
+    /// `[media]` title strategy and locale, for trackers missing from `titles`.
+    media_title: (TitleStrategy, Option<String>),
+    /// None: screenshots disabled.
+    screenshots: Option<ScreenshotPublisher>,
     uploaders: Vec<Box<dyn TrackerUploader>>,
 }
 
@@ -39,11 +159,19 @@ impl UploadService {
         Self {
             enabled: false,
             dry_run: false,
+            seed_torrent_dir: None,
+            dupe_policy: DupePolicy::Always,
+            queue: Mutex::new(UploadQueue::in_memory()),
+            status: Mutex::new(UploadStatusStore::in_memory()),
+            templates: HashMap::new(),
+            titles: HashMap::new(),
+            media_title: (TitleStrategy::AlwaysLocal, None),
+            screenshots: None,
             uploaders: Vec::new(),
         }
     }
 
-    pub fn from_config(config: &crate::config::Config) -> Result<Self, Error> {
+    pub fn from_config(config: &crate::config::Config, http: &HttpClient) -> Result<Self, Error> {
         let Some(upload_cfg) = &config.upload else {
             return Ok(Self::disabled());
         };
@@ -53,7 +181,10 @@ impl UploadService {
         // Preferred: enable each tracker explicitly.
         if let Some(tcfg) = upload_cfg.torrust.as_ref() {
             if tcfg.enable {
-                uploaders.push(Box::new(torrust::TorrustUploader::new(tcfg.clone())));
+                uploaders.push(Box::new(torrust::TorrustUploader::new(
+                    tcfg.clone(),
+                    http.clone(),
+                )));
             }
         }
 
@@ -67,7 +198,10 @@ impl UploadService {
                                 .to_string(),
                         ));
                     };
-                    uploaders.push(Box::new(torrust::TorrustUploader::new(tcfg.clone())));
+                    uploaders.push(Box::new(torrust::TorrustUploader::new(
+                        tcfg.clone(),
+                        http.clone(),
+                    )));
                 } else {
                     return Err(Error::Other(format!(
                         "Unsupported upload.tracker: {tracker}"
@@ -76,10 +210,58 @@ impl UploadService {
             }
         }
 
+        // Templates are checked here so typos fail at startup, not mid-run.
+        let mut templates = HashMap::new();
+        if let Some(path) = upload_cfg
+            .torrust
+            .as_ref()
+            .and_then(|t| t.description_template.as_ref())
+        {
+            if uploaders.iter().any(|u| u.name() == "torrust") {
+                let template = Template::load(Path::new(path), &TemplateContext::schema())?;
+                templates.insert("torrust".to_string(), template);
+            }
+        }
+
+        let mut titles = HashMap::new();
+        if let Some(tcfg) = upload_cfg.torrust.as_ref() {
+            titles.insert(
+                "torrust".to_string(),
+                (
+                    tcfg.title_strategy
+                        .unwrap_or_else(|| config.media.effective_title_strategy()),
+                    tcfg.title_locale
+                        .clone()
+                        .or_else(|| config.media.title_locale.clone()),
+                ),
+            );
+        }
+
         let enabled = !uploaders.is_empty();
+        let (queue, status) = if enabled {
+            let dir = upload_state_dir(config);
+            (
+                UploadQueue::load(&dir.join("upload-queue.json"), upload_cfg.max_attempts),
+                UploadStatusStore::load(&dir.join("upload-status.json")),
+            )
+        } else {
+            (UploadQueue::in_memory(), UploadStatusStore::in_memory())
+        };
+
         Ok(Self {
             enabled,
             dry_run: upload_cfg.dry_run,
+            seed_torrent_dir: upload_cfg.seed_torrent_dir.as_ref().map(PathBuf::from),
+            dupe_policy: upload_cfg.dupe_policy,
+            queue: Mutex::new(queue),
+            status: Mutex::new(status),
+            templates,
+            titles,
+            media_title: (
+                config.media.effective_title_strategy(),
+                config.media.title_locale.clone(),
+            ),
+            screenshots: ScreenshotPublisher::from_config(config, http)?,
             uploaders,
         })
     }
@@ -88,101 +270,739 @@ impl UploadService {
         self.enabled && !self.uploaders.is_empty() && !self.dry_run
     }
 
+    /// Capture and publish screenshots of `video` for the release. Empty when uploads or
+    /// screenshots are disabled, or ffmpeg is missing.
+    pub async fn screenshots(&self, video: &Path, scene_name: &str) -> Vec<String> {
+        match &self.screenshots {
+            Some(publisher) if self.is_enabled() => publisher.publish(video, scene_name).await,
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether a description template uses `var`, for metadata that is costly to fetch
+    /// (e.g. "cast", "mediainfo").
+    pub fn needs_template_var(&self, var: &str) -> bool {
+        self.is_enabled() && self.templates.values().any(|t| t.references(var))
+    }
+
+    /// Forget the uploads of a release whose seed files changed, so its rebuilt torrent is
+    /// uploaded again. Tracker torrents fetched for it are moved to `<file>.stale`.
+    pub fn forget_release(&self, scene_name: &str, local_torrent: &Path) {
+        let out_dir = self.tracker_torrent_dir(local_torrent);
+        for uploader in &self.uploaders {
+            let issued = tracker_torrent_path(&out_dir, scene_name, uploader.name());
+            if !issued.exists() {
+                continue;
+            }
+            let stale = stale_path(&issued);
+            match std::fs::rename(&issued, &stale) {
+                Ok(()) => tracing::warn!(
+                    "Seed files changed: {} torrent moved to '{}'",
+                    uploader.name(),
+                    stale.display()
+                ),
+                Err(e) => tracing::error!(
+                    "Failed to move stale tracker torrent '{}' aside: {e}",
+                    issued.display()
+                ),
+            }
+        }
+        if let Err(e) = self.status.lock().unwrap().forget(scene_name) {
+            tracing::error!("Failed to update upload status: {e}");
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn upload_movie_torrent(
         &self,
-        title: &str,
+        titles: &TitleCandidates,
         year: Option<u16>,
+        ids: &ExternalIds,
         cover_url: Option<&str>,
         overview: Option<&str>,
         scene_name: &str,
         tech: &TechnicalInfo,
+        meta: &ReleaseMetadata,
         torrent_path: PathBuf,
-    ) -> Result<(), Error> {
+    ) -> Vec<TrackerOutcome> {
         if !self.enabled {
-            return Ok(());
+            return Vec::new();
         }
         if self.dry_run {
             tracing::info!(
                 "Upload dry-run enabled: skipping upload for '{}'",
                 scene_name
             );
-            return Ok(());
+            return self.skipped_all("dry-run");
         }
 
-        let md =
-            description::build_movie_markdown(title, year, cover_url, overview, scene_name, tech);
-        let req = UploadRequest {
-            title: scene_name.to_string(),
-            description_markdown: md,
-            torrent_path,
-            kind: ContentKind::Movie,
+        let listing = |title: &str| {
+            let doc = description::movie_description(
+                title,
+                year,
+                cover_url,
+                overview,
+                scene_name,
+                tech,
+                &meta.screenshots,
+            );
+            let ctx = TemplateContext {
+                kind: description::kind_name(ContentKind::Movie).to_string(),
+                title: title.to_string(),
+                year,
+                episode: None,
+                release: scene_name.to_string(),
+                cover_url: cover_url.map(str::to_string),
+                overview: overview.map(str::to_string),
+                ids: ids.clone(),
+                meta: meta.clone(),
+                tech: tech.clone(),
+                default_description: String::new(),
+            };
+            let query = dupe_query(ContentKind::Movie, title, year, ids, scene_name, tech);
+            (doc, ctx, query)
         };
 
-        let mut last_err: Option<Error> = None;
-        for uploader in &self.uploaders {
-            if let Err(e) = uploader.upload_torrent(req.clone()).await {
-                tracing::error!("Uploader failed for '{}': {e}", scene_name);
-                last_err = Some(e);
-            }
-        }
-
-        if let Some(e) = last_err {
-            Err(e)
-        } else {
-            Ok(())
-        }
+        self.upload_to_all(titles, listing, torrent_path, ContentKind::Movie)
+            .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn upload_episode_torrent(
         &self,
-        series_title: &str,
+        series_titles: &TitleCandidates,
+        series_year: Option<u16>,
+        ids: &ExternalIds,
         episode_heading: &str,
         cover_url: Option<&str>,
         overview: Option<&str>,
         scene_name: &str,
         tech: &TechnicalInfo,
+        meta: &ReleaseMetadata,
         torrent_path: PathBuf,
         kind: ContentKind,
-    ) -> Result<(), Error> {
+    ) -> Vec<TrackerOutcome> {
         if !self.enabled {
-            return Ok(());
+            return Vec::new();
         }
         if self.dry_run {
             tracing::info!(
                 "Upload dry-run enabled: skipping upload for '{}'",
                 scene_name
             );
-            return Ok(());
+            return self.skipped_all("dry-run");
         }
 
-        let md = description::build_episode_markdown(
-            series_title,
-            episode_heading,
-            cover_url,
-            overview,
-            scene_name,
-            tech,
-        );
+        let listing = |series_title: &str| {
+            let doc = description::episode_description(
+                series_title,
+                episode_heading,
+                cover_url,
+                overview,
+                scene_name,
+                tech,
+                &meta.screenshots,
+            );
+            let ctx = TemplateContext {
+                kind: description::kind_name(kind).to_string(),
+                title: series_title.to_string(),
+                year: series_year,
+                episode: Some(episode_heading.to_string()),
+                release: scene_name.to_string(),
+                cover_url: cover_url.map(str::to_string),
+                overview: overview.map(str::to_string),
+                ids: ids.clone(),
+                meta: meta.clone(),
+                tech: tech.clone(),
+                default_description: String::new(),
+            };
+            let query = dupe_query(kind, series_title, series_year, ids, scene_name, tech);
+            (doc, ctx, query)
+        };
 
-        let req = UploadRequest {
-            title: scene_name.to_string(),
-            description_markdown: md,
-            torrent_path,
-            kind,
+        self.upload_to_all(series_titles, listing, torrent_path, kind)
+            .await
+    }
+
+    /// The tracker's template if it has one, else the built-in description.
+    fn render_description(
+        &self,
+        uploader: &dyn TrackerUploader,
+        doc: &Description,
+        ctx: &TemplateContext,
+    ) -> String {
+        let default = description::render(doc, uploader.description_format());
+        let Some(template) = self.templates.get(uploader.name()) else {
+            return default;
         };
+        let ctx = TemplateContext {
+            default_description: default,
+            ..ctx.clone()
+        };
+        template.render(&serde_json::to_value(ctx).unwrap_or_default())
+    }
+
+    fn skipped_all(&self, reason: &str) -> Vec<TrackerOutcome> {
+        self.uploaders
+            .iter()
+            .map(|u| TrackerOutcome {
+                tracker: u.name().to_string(),
+                outcome: UploadOutcome::Skipped {
+                    reason: reason.to_string(),
+                },
+            })
+            .collect()
+    }
+
+    /// Title shown on `tracker`, per its title strategy.
+    fn listing_title(&self, tracker: &str, candidates: &TitleCandidates) -> String {
+        let (strategy, locale) = self.titles.get(tracker).unwrap_or(&self.media_title);
+        titles::choose(candidates, *strategy, locale.as_deref())
+    }
 
-        let mut last_err: Option<Error> = None;
+    /// Upload to every tracker that doesn't already have the release, one outcome per tracker.
+    /// `listing` builds the description, template context and dupe query for a tracker's title.
+    async fn upload_to_all<F>(
+        &self,
+        candidates: &TitleCandidates,
+        listing: F,
+        torrent_path: PathBuf,
+        kind: ContentKind,
+    ) -> Vec<TrackerOutcome>
+    where
+        F: Fn(&str) -> (Description, TemplateContext, DupeQuery),
+    {
+        let mut outcomes = Vec::with_capacity(self.uploaders.len());
         for uploader in &self.uploaders {
-            if let Err(e) = uploader.upload_torrent(req.clone()).await {
-                tracing::error!("Uploader failed for '{}': {e}", scene_name);
-                last_err = Some(e);
+            let (doc, ctx, query) = listing(&self.listing_title(uploader.name(), candidates));
+            let req = UploadRequest {
+                title: query.scene_name.clone(),
+                description: self.render_description(uploader.as_ref(), &doc, &ctx),
+                torrent_path: torrent_path.clone(),
+                kind,
+            };
+            let outcome = self.upload_if_needed(uploader.as_ref(), &req, &query).await;
+            outcomes.push(TrackerOutcome {
+                tracker: uploader.name().to_string(),
+                outcome,
+            });
+        }
+        outcomes
+    }
+
+    async fn upload_if_needed(
+        &self,
+        uploader: &dyn TrackerUploader,
+        req: &UploadRequest,
+        query: &DupeQuery,
+    ) -> UploadOutcome {
+        let scene_name = query.scene_name.as_str();
+        // Uploads missing their tracker torrent are fetched again by `retry_queued`.
+        if self
+            .status
+            .lock()
+            .unwrap()
+            .get(scene_name, uploader.name())
+            .is_some_and(|r| r.torrent_pending.is_some())
+        {
+            tracing::info!(
+                "'{}' is on {} but its torrent wasn't fetched yet: skipping",
+                scene_name,
+                uploader.name()
+            );
+            return UploadOutcome::Skipped {
+                reason: "tracker torrent fetch pending".to_string(),
+            };
+        }
+        if self
+            .status
+            .lock()
+            .unwrap()
+            .is_done(scene_name, uploader.name())
+        {
+            tracing::debug!(
+                "'{}' already handled by {}: skipping",
+                scene_name,
+                uploader.name()
+            );
+            return UploadOutcome::Skipped {
+                reason: "already uploaded".to_string(),
+            };
+        }
+        // The queue owns failed items: retried by `retry_queued`, not by the pipeline.
+        if self
+            .queue
+            .lock()
+            .unwrap()
+            .contains(uploader.name(), scene_name)
+        {
+            tracing::info!(
+                "Upload of '{}' to {} is queued for retry: skipping",
+                scene_name,
+                uploader.name()
+            );
+            return UploadOutcome::Skipped {
+                reason: "queued for retry".to_string(),
+            };
+        }
+
+        let outcome = match self.upload_one(uploader, req, query).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                tracing::error!(
+                    "Upload of '{}' to {} failed: {e}",
+                    scene_name,
+                    uploader.name()
+                );
+                self.enqueue_failure(uploader.name(), req, query, &e);
+                UploadOutcome::Failed {
+                    error: e.to_string(),
+                }
             }
+        };
+        self.finish_upload(uploader, scene_name, &outcome, &req.torrent_path)
+            .await;
+        outcome
+    }
+
+    /// Retry queued uploads. Items flagged for manual review are only retried when
+    /// `include_flagged` is set.
+    pub async fn retry_queued(&self, include_flagged: bool) {
+        if !self.is_enabled() {
+            return;
         }
 
-        if let Some(e) = last_err {
-            Err(e)
-        } else {
-            Ok(())
+        let items = self.queue.lock().unwrap().pending(include_flagged);
+        if !items.is_empty() {
+            tracing::info!("Retrying {} queued upload(s)", items.len());
+        }
+
+        for item in items {
+            let Some(uploader) = self.uploaders.iter().find(|u| u.name() == item.tracker) else {
+                tracing::warn!(
+                    "Queued upload of '{}' targets disabled tracker '{}': leaving it queued",
+                    item.scene_name,
+                    item.tracker
+                );
+                continue;
+            };
+
+            match self
+                .upload_one(uploader.as_ref(), &item.request, &item.query)
+                .await
+            {
+                Ok(outcome) => {
+                    if let Err(e) = self
+                        .queue
+                        .lock()
+                        .unwrap()
+                        .remove(&item.tracker, &item.scene_name)
+                    {
+                        tracing::error!("Failed to update upload queue: {e}");
+                    }
+                    self.finish_upload(
+                        uploader.as_ref(),
+                        &item.scene_name,
+                        &outcome,
+                        &item.request.torrent_path,
+                    )
+                    .await;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Retry of '{}' to {} failed: {e}",
+                        item.scene_name,
+                        item.tracker
+                    );
+                    self.enqueue_failure(uploader.name(), &item.request, &item.query, &e);
+                    self.record_status(
+                        &item.scene_name,
+                        &item.tracker,
+                        &UploadOutcome::Failed {
+                            error: e.to_string(),
+                        },
+                        None,
+                    );
+                }
+            }
+        }
+
+        self.retry_torrent_fetches().await;
+
+        for item in self.queue.lock().unwrap().flagged() {
+            tracing::warn!(
+                "Upload of '{}' to {} needs manual review after {} attempts (last error: {})",
+                item.scene_name,
+                item.tracker,
+                item.attempts,
+                item.last_error
+            );
+        }
+    }
+
+    /// Duplicate check + upload to a single tracker: `Uploaded` or `Duplicate` on success.
+    async fn upload_one(
+        &self,
+        uploader: &dyn TrackerUploader,
+        req: &UploadRequest,
+        query: &DupeQuery,
+    ) -> Result<UploadOutcome, Error> {
+        match self.check_dupes(uploader, query).await {
+            DupeVerdict::Upload => {}
+            DupeVerdict::Skip { existing } => {
+                tracing::info!(
+                    "Skipping upload of '{}' to {}: '{}' already exists ({:?}, url={:?})",
+                    query.scene_name,
+                    uploader.name(),
+                    existing.name,
+                    self.dupe_policy,
+                    existing.url
+                );
+                return Ok(UploadOutcome::Duplicate {
+                    existing: existing.name,
+                });
+            }
+        }
+
+        let outcome = uploader.upload_torrent(with_local_torrent(req)).await?;
+        match &outcome {
+            UploadOutcome::Uploaded(uploaded) => tracing::info!(
+                "Uploaded '{}' to {} (id={:?}, url={:?})",
+                query.scene_name,
+                uploader.name(),
+                uploaded.torrent_id,
+                uploaded.url
+            ),
+            UploadOutcome::Duplicate { existing } => tracing::info!(
+                "{} already has '{}': not uploaded",
+                uploader.name(),
+                existing
+            ),
+            _ => {}
+        }
+        Ok(outcome)
+    }
+
+    /// Fetch the tracker torrents that failed to download right after their upload.
+    async fn retry_torrent_fetches(&self) {
+        let fetches = self.status.lock().unwrap().pending_fetches();
+        for (scene_name, tracker, record) in fetches {
+            let (UploadOutcome::Uploaded(uploaded), Some(local_torrent)) =
+                (&record.outcome, &record.torrent_pending)
+            else {
+                continue;
+            };
+            let Some(uploader) = self.uploaders.iter().find(|u| u.name() == tracker) else {
+                tracing::warn!(
+                    "Torrent of '{}' is still to fetch from disabled tracker '{}'",
+                    scene_name,
+                    tracker
+                );
+                continue;
+            };
+
+            tracing::info!("Fetching {} torrent for '{}' again", tracker, scene_name);
+            if self
+                .fetch_after_upload(uploader.as_ref(), uploaded, &scene_name, local_torrent)
+                .await
+            {
+                if let Err(e) = self
+                    .status
+                    .lock()
+                    .unwrap()
+                    .torrent_fetched(&scene_name, &tracker)
+                {
+                    tracing::error!("Failed to update upload status: {e}");
+                }
+            }
+        }
+    }
+
+    /// Record `outcome`, fetching the tracker torrent first for uploads: until it is on disk
+    /// the upload isn't done and `retry_queued` fetches it again.
+    async fn finish_upload(
+        &self,
+        uploader: &dyn TrackerUploader,
+        scene_name: &str,
+        outcome: &UploadOutcome,
+        local_torrent: &Path,
+    ) {
+        let fetched = match outcome {
+            UploadOutcome::Uploaded(uploaded) => {
+                self.fetch_after_upload(uploader, uploaded, scene_name, local_torrent)
+                    .await
+            }
+            _ => true,
+        };
+        self.record_status(
+            scene_name,
+            uploader.name(),
+            outcome,
+            (!fetched).then_some(local_torrent),
+        );
+    }
+
+    fn record_status(
+        &self,
+        scene_name: &str,
+        tracker: &str,
+        outcome: &UploadOutcome,
+        torrent_pending: Option<&Path>,
+    ) {
+        if let Err(e) =
+            self.status
+                .lock()
+                .unwrap()
+                .record(scene_name, tracker, outcome, torrent_pending)
+        {
+            tracing::error!("Failed to update upload status: {e}");
         }
     }
+
+    /// Fetch failures don't change the outcome (the release is on the tracker); false when the
+    /// fetch is to retry.
+    async fn fetch_after_upload(
+        &self,
+        uploader: &dyn TrackerUploader,
+        uploaded: &UploadedTorrent,
+        scene_name: &str,
+        local_torrent: &Path,
+    ) -> bool {
+        match self
+            .fetch_tracker_torrent(uploader, uploaded, scene_name, local_torrent)
+            .await
+        {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to fetch {} torrent for '{}': {e}",
+                    uploader.name(),
+                    scene_name
+                );
+                false
+            }
+        }
+    }
+
+    fn enqueue_failure(&self, tracker: &str, req: &UploadRequest, query: &DupeQuery, err: &Error) {
+        let mut queue = self.queue.lock().unwrap();
+        match queue.record_failure(tracker, req, query, &err.to_string()) {
+            Ok(item) if item.needs_review => tracing::warn!(
+                "Upload of '{}' to {} flagged for manual review after {} attempts",
+                item.scene_name,
+                item.tracker,
+                item.attempts
+            ),
+            Ok(item) => tracing::info!(
+                "Upload of '{}' to {} queued for retry (attempt {})",
+                item.scene_name,
+                item.tracker,
+                item.attempts
+            ),
+            Err(e) => tracing::error!("Failed to update upload queue: {e}"),
+        }
+    }
+
+    async fn check_dupes(&self, uploader: &dyn TrackerUploader, query: &DupeQuery) -> DupeVerdict {
+        if self.dupe_policy == DupePolicy::Always {
+            return DupeVerdict::Upload;
+        }
+        tracing::debug!(
+            "Searching {} for existing '{}' (tmdb={:?} imdb={:?} tvdb={:?})",
+            uploader.name(),
+            query.title,
+            query.tmdb_id,
+            query.imdb_id,
+            query.tvdb_id
+        );
+        // A failing search only skips the check: it must not hold the upload back
+        let found = match uploader.search_existing(query).await {
+            Ok(found) => found,
+            Err(e) => {
+                tracing::warn!(
+                    "Duplicate search on {} failed for '{}': uploading without the check ({e})",
+                    uploader.name(),
+                    query.scene_name
+                );
+                return DupeVerdict::Upload;
+            }
+        };
+        let Some(found) = found else {
+            tracing::debug!(
+                "{} has no search support: skipping duplicate check",
+                uploader.name()
+            );
+            return DupeVerdict::Upload;
+        };
+        for r in &found {
+            tracing::debug!("{} existing release: '{}'", uploader.name(), r.name);
+        }
+        dupe::evaluate(self.dupe_policy, query, &found)
+    }
+
+    /// Where tracker torrents go: the watch dir, else next to the locally-built torrent.
+    fn tracker_torrent_dir(&self, local_torrent: &Path) -> PathBuf {
+        self.seed_torrent_dir
+            .clone()
+            .or_else(|| local_torrent.parent().map(Path::to_path_buf))
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Download the tracker-issued torrent and write it where the seeding client picks it up:
+    /// `<seed_torrent_dir>/<scene>.<tracker>.torrent`. Without `seed_torrent_dir` it goes next
+    /// to the locally-built torrent, which is then moved aside so only the tracker's is seeded.
+    async fn fetch_tracker_torrent(
+        &self,
+        uploader: &dyn TrackerUploader,
+        uploaded: &UploadedTorrent,
+        scene_name: &str,
+        local_torrent: &Path,
+    ) -> Result<(), Error> {
+        if uploaded.download_url.is_none() {
+            tracing::warn!(
+                "{} did not return a download link for '{}': seed the locally-built torrent",
+                uploader.name(),
+                scene_name
+            );
+            return Ok(());
+        }
+
+        let out_dir = self.tracker_torrent_dir(local_torrent);
+        let out = tracker_torrent_path(&out_dir, scene_name, uploader.name());
+        if out.exists() {
+            tracing::info!(
+                "Tracker torrent already exists: '{}' — skipping",
+                out.display()
+            );
+            return set_local_torrent_aside(local_torrent, &out_dir);
+        }
+
+        let bytes = uploader.download_torrent(uploaded).await?;
+        tokio::fs::create_dir_all(&out_dir).await.map_err(|e| {
+            Error::Other(format!(
+                "Failed to create directory '{}': {e}",
+                out_dir.display()
+            ))
+        })?;
+        tokio::fs::write(&out, bytes).await.map_err(|e| {
+            Error::Other(format!(
+                "Failed to write tracker torrent '{}': {e}",
+                out.display()
+            ))
+        })?;
+        tracing::info!("Tracker torrent saved: '{}'", out.display());
+        set_local_torrent_aside(local_torrent, &out_dir)
+    }
+}
+
+/// Move the locally-built torrent to its [`replaced_torrent_path`] when a tracker torrent was
+/// written to the same directory.
+fn set_local_torrent_aside(local_torrent: &Path, out_dir: &Path) -> Result<(), Error> {
+    let is_local = local_torrent.extension().is_some_and(|e| e == "torrent");
+    if !is_local || local_torrent.parent() != Some(out_dir) || !local_torrent.exists() {
+        return Ok(());
+    }
+    let aside = replaced_torrent_path(local_torrent);
+    std::fs::rename(local_torrent, &aside).map_err(|e| {
+        Error::Other(format!(
+            "Failed to move local torrent '{}' aside: {e}",
+            local_torrent.display()
+        ))
+    })?;
+    tracing::info!(
+        "Local torrent replaced by the tracker's: moved to '{}'",
+        aside.display()
+    );
+    Ok(())
+}
+
+/// `req` reading the locally-built torrent from where it was moved aside, if it was (another
+/// tracker's torrent replaced it since the request was made).
+fn with_local_torrent(req: &UploadRequest) -> UploadRequest {
+    let mut req = req.clone();
+    let aside = replaced_torrent_path(&req.torrent_path);
+    if !req.torrent_path.exists() && aside.exists() {
+        req.torrent_path = aside;
+    }
+    req
+}
+
+/// Directory holding upload state files (queue, status).
+/// Defaults to `<seed_path>/.seedarr`, or `./.seedarr` without a seed path.
+fn upload_state_dir(config: &crate::config::Config) -> PathBuf {
+    if let Some(dir) = config.upload.as_ref().and_then(|u| u.state_dir.as_ref()) {
+        return PathBuf::from(dir);
+    }
+    config
+        .media
+        .seed_path
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".seedarr")
+}
+
+fn dupe_query(
+    kind: ContentKind,
+    title: &str,
+    year: Option<u16>,
+    ids: &ExternalIds,
+    scene_name: &str,
+    tech: &TechnicalInfo,
+) -> DupeQuery {
+    // Source isn't part of TechnicalInfo: read it back from the name we built.
+    let parsed = crate::core::naming::parse_scene_name(scene_name);
+    DupeQuery {
+        kind,
+        title: title.to_string(),
+        year,
+        tmdb_id: ids.tmdb_id,
+        imdb_id: ids.imdb_id.clone(),
+        tvdb_id: ids.tvdb_id,
+        scene_name: scene_name.to_string(),
+        resolution: tech.resolution.clone().or(parsed.resolution),
+        source: parsed.source,
+    }
+}
+
+fn tracker_torrent_path(dir: &Path, scene_name: &str, tracker: &str) -> PathBuf {
+    dir.join(format!("{scene_name}.{tracker}.torrent"))
+}
+
+/// Read a JSON state file (`what` names it in logs); a missing file is the empty state.
+/// An unreadable or corrupt file is moved aside to `<file>.corrupt-<timestamp>` with a warning
+/// so one bad file doesn't disable every upload.
+fn load_state_file<T: serde::de::DeserializeOwned + Default>(path: &Path, what: &str) -> T {
+    if !path.exists() {
+        return T::default();
+    }
+    let parsed = std::fs::read(path)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|e| e.to_string()));
+    match parsed {
+        Ok(state) => state,
+        Err(e) => {
+            let mut aside = path.as_os_str().to_owned();
+            aside.push(format!(".corrupt-{}", unix_now()));
+            match std::fs::rename(path, &aside) {
+                Ok(()) => tracing::warn!(
+                    "Unreadable {what} '{}' ({e}): moved to '{}', starting empty",
+                    path.display(),
+                    Path::new(&aside).display()
+                ),
+                Err(re) => tracing::warn!(
+                    "Unreadable {what} '{}' ({e}), could not move it aside ({re}): starting empty",
+                    path.display()
+                ),
+            }
+            T::default()
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
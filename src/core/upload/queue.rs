@@ -0,0 +1,242 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::Error;
+
+use super::dupe::DupeQuery;
+use super::{load_state_file, unix_now, UploadRequest};
+
+/// A failed upload waiting to be retried.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedUpload {
+    /// `TrackerUploader::name()` of the target tracker.
+    pub tracker: String,
+    pub scene_name: String,
+    pub request: UploadRequest,
+    pub query: DupeQuery,
+    pub attempts: u32,
+    pub last_error: String,
+    /// Unix timestamp (seconds) of the last attempt.
+    pub last_attempt_at: u64,
+    /// Set once `attempts` reaches the configured limit: no more automatic retries.
+    #[serde(default)]
+    pub needs_review: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct QueueFile {
+    #[serde(default)]
+    items: Vec<QueuedUpload>,
+}
+
+/// Failed uploads persisted as JSON, keyed by (tracker, scene name).
+#[derive(Debug)]
+pub struct UploadQueue {
+    /// None: in-memory only (upload disabled).
+    path: Option<PathBuf>,
+    max_attempts: u32,
+    items: Vec<QueuedUpload>,
+}
+
+impl UploadQueue {
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            max_attempts: u32::MAX,
+            items: Vec::new(),
+        }
+    }
+
+    /// Load the queue at `path`; a corrupt file is moved aside (see `load_state_file`).
+    pub fn load(path: &Path, max_attempts: u32) -> Self {
+        let file: QueueFile = load_state_file(path, "upload queue");
+        Self {
+            path: Some(path.to_path_buf()),
+            max_attempts,
+            items: file.items,
+        }
+    }
+
+    pub fn contains(&self, tracker: &str, scene_name: &str) -> bool {
+        self.find(tracker, scene_name).is_some()
+    }
+
+    /// Items to retry; flagged items are only included on request.
+    pub fn pending(&self, include_flagged: bool) -> Vec<QueuedUpload> {
+        self.items
+            .iter()
+            .filter(|i| include_flagged || !i.needs_review)
+            .cloned()
+            .collect()
+    }
+
+    pub fn flagged(&self) -> impl Iterator<Item = &QueuedUpload> {
+        self.items.iter().filter(|i| i.needs_review)
+    }
+
+    /// Record a failed attempt (creating the entry if needed) and persist the queue.
+    /// Returns the updated entry.
+    pub fn record_failure(
+        &mut self,
+        tracker: &str,
+        request: &UploadRequest,
+        query: &DupeQuery,
+        error: &str,
+    ) -> Result<QueuedUpload, Error> {
+        let now = unix_now();
+        let scene_name = query.scene_name.as_str();
+        let idx = match self.find(tracker, scene_name) {
+            Some(idx) => idx,
+            None => {
+                self.items.push(QueuedUpload {
+                    tracker: tracker.to_string(),
+                    scene_name: scene_name.to_string(),
+                    request: request.clone(),
+                    query: query.clone(),
+                    attempts: 0,
+                    last_error: String::new(),
+                    last_attempt_at: now,
+                    needs_review: false,
+                });
+                self.items.len() - 1
+            }
+        };
+
+        let item = &mut self.items[idx];
+        item.attempts += 1;
+        item.last_error = error.to_string();
+        item.last_attempt_at = now;
+        item.needs_review = item.attempts >= self.max_attempts;
+        let updated = item.clone();
+
+        self.save()?;
+        Ok(updated)
+    }
+
+    /// Drop an entry after a successful upload. No-op if absent.
+    pub fn remove(&mut self, tracker: &str, scene_name: &str) -> Result<(), Error> {
+        let Some(idx) = self.find(tracker, scene_name) else {
+            return Ok(());
+        };
+        self.items.remove(idx);
+        self.save()
+    }
+
+    fn find(&self, tracker: &str, scene_name: &str) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|i| i.tracker == tracker && i.scene_name == scene_name)
+    }
+
+    fn save(&self) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                Error::Other(format!(
+                    "Failed to create directory '{}': {e}",
+                    parent.display()
+                ))
+            })?;
+        }
+        let file = QueueFile {
+            items: self.items.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&file)
+            .map_err(|e| Error::Other(format!("Failed to serialize upload queue: {e}")))?;
+        // Write then rename so a crash never leaves a truncated queue behind.
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Failed to write upload queue '{}': {e}",
+                    path.display()
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::upload::ContentKind;
+
+    fn sample() -> (UploadRequest, DupeQuery) {
+        let req = UploadRequest {
+            title: "Heat.1995.1080p.BluRay.x264-GRP".to_string(),
+            description: "desc".to_string(),
+            torrent_path: PathBuf::from("/torrents/Heat.1995.1080p.BluRay.x264-GRP.torrent"),
+            kind: ContentKind::Movie,
+        };
+        let query = DupeQuery {
+            kind: ContentKind::Movie,
+            title: "Heat".to_string(),
+            year: Some(1995),
+            tmdb_id: Some(949),
+            imdb_id: None,
+            tvdb_id: None,
+            scene_name: req.title.clone(),
+            resolution: Some("1080p".to_string()),
+            source: Some("BluRay".to_string()),
+        };
+        (req, query)
+    }
+
+    #[test]
+    fn failures_accumulate_until_flagged_and_persist() {
+        let path =
+            std::env::temp_dir().join(format!("seedarr-queue-test-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let (req, query) = sample();
+
+        let mut queue = UploadQueue::load(&path, 2);
+        let first = queue
+            .record_failure("torrust", &req, &query, "HTTP 502")
+            .unwrap();
+        assert_eq!(first.attempts, 1);
+        assert!(!first.needs_review);
+        assert_eq!(queue.pending(false).len(), 1);
+
+        let second = queue
+            .record_failure("torrust", &req, &query, "HTTP 503")
+            .unwrap();
+        assert!(second.needs_review);
+        assert!(queue.pending(false).is_empty());
+        assert_eq!(queue.pending(true).len(), 1);
+
+        let reloaded = UploadQueue::load(&path, 2);
+        assert!(reloaded.contains("torrust", &req.title));
+        assert_eq!(reloaded.flagged().next().unwrap().last_error, "HTTP 503");
+
+        let mut reloaded = reloaded;
+        reloaded.remove("torrust", &req.title).unwrap();
+        assert!(!UploadQueue::load(&path, 2).contains("torrust", &req.title));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn corrupt_queue_is_moved_aside() {
+        let dir =
+            std::env::temp_dir().join(format!("seedarr-queue-corrupt-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("upload-queue.json");
+        std::fs::write(&path, "{\"items\": [").unwrap();
+
+        let queue = UploadQueue::load(&path, 2);
+        assert!(queue.pending(true).is_empty());
+        assert!(!path.exists());
+        let moved: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert!(
+            moved.len() == 1 && moved[0].starts_with("upload-queue.json.corrupt-"),
+            "{moved:?}"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
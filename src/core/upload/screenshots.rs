@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::core::media::screenshots::{capture_screenshots, Timestamp};
+use crate::utils::http::HttpClient;
+use crate::utils::Error;
+
+use super::image_host::{self, ImageHost};
+
+/// Captures screenshots next to the seed dir (`<seed_path>/<scene>.screens/`, outside the
+/// torrent) and publishes them to the configured image host.
+pub struct ScreenshotPublisher {
+    seed_root: PathBuf,
+    timestamps: Vec<Timestamp>,
+    host: Box<dyn ImageHost>,
+}
+
+impl ScreenshotPublisher {
+    /// None when screenshots are disabled or there is no `seed_path` to store them in.
+    pub fn from_config(
+        config: &crate::config::Config,
+        http: &HttpClient,
+    ) -> Result<Option<Self>, Error> {
+        let Some(cfg) = config.upload.as_ref().map(|u| &u.screenshots) else {
+            return Ok(None);
+        };
+        if !cfg.enable {
+            return Ok(None);
+        }
+        let Some(seed_root) = config.media.seed_path.as_ref().map(PathBuf::from) else {
+            tracing::warn!("upload.screenshots requires media.seed_path: screenshots disabled");
+            return Ok(None);
+        };
+
+        let timestamps = cfg
+            .timestamps
+            .iter()
+            .map(|s| {
+                Timestamp::parse(s).ok_or_else(|| {
+                    Error::Other(format!("Invalid upload.screenshots timestamp: '{s}'"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(Self {
+            host: image_host::from_config(&cfg.host, &seed_root, http),
+            seed_root,
+            timestamps,
+        }))
+    }
+
+    /// Capture (or reuse) screenshots of `video` and return their public URLs, in order.
+    /// URLs are cached in `urls.json` so reruns don't upload the same images again.
+    pub async fn publish(&self, video: &Path, scene_name: &str) -> Vec<String> {
+        let dir = self.seed_root.join(format!("{scene_name}.screens"));
+        let files = capture_screenshots(video, &dir, &self.timestamps);
+        if files.is_empty() {
+            return Vec::new();
+        }
+
+        let cache_path = dir.join("urls.json");
+        let mut cache: BTreeMap<String, String> = std::fs::read(&cache_path)
+            .ok()
+            .and_then(|b| serde_json::from_slice(&b).ok())
+            .unwrap_or_default();
+
+        let mut urls = Vec::new();
+        let mut changed = false;
+        for file in files {
+            let name = file
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let key = format!("{}:{name}", self.host.name());
+            if let Some(url) = cache.get(&key) {
+                urls.push(url.clone());
+                continue;
+            }
+            match self.host.upload_image(&file).await {
+                Ok(url) => {
+                    cache.insert(key, url.clone());
+                    changed = true;
+                    urls.push(url);
+                }
+                Err(e) => tracing::warn!(
+                    "Failed to upload screenshot '{}' to {}: {e}",
+                    file.display(),
+                    self.host.name()
+                ),
+            }
+        }
+
+        if changed {
+            let written = serde_json::to_vec_pretty(&cache)
+                .map_err(|e| e.to_string())
+                .and_then(|json| std::fs::write(&cache_path, json).map_err(|e| e.to_string()));
+            if let Err(e) = written {
+                tracing::warn!(
+                    "Failed to write screenshot cache '{}': {e}",
+                    cache_path.display()
+                );
+            }
+        }
+        urls
+    }
+}
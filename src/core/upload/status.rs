@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::Error;
+
+use super::{load_state_file, unix_now, UploadOutcome};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusRecord {
+    pub outcome: UploadOutcome,
+    /// Unix timestamp (seconds) of the last change.
+    pub updated_at: u64,
+    /// Locally-built torrent of an `Uploaded` release whose tracker-issued `.torrent` couldn't
+    /// be fetched yet: the fetch is retried until it lands on disk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub torrent_pending: Option<PathBuf>,
+}
+
+/// Last upload outcome per release and tracker, persisted as JSON:
+/// `{ "<scene>": { "<tracker>": { "outcome": {...}, "updated_at": ... } } }`.
+#[derive(Debug, Default)]
+pub struct UploadStatusStore {
+    /// None: in-memory only (upload disabled).
+    path: Option<PathBuf>,
+    releases: BTreeMap<String, BTreeMap<String, StatusRecord>>,
+}
+
+impl UploadStatusStore {
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Load the store at `path`; a corrupt file is moved aside (see `load_state_file`).
+    pub fn load(path: &Path) -> Self {
+        Self {
+            path: Some(path.to_path_buf()),
+            releases: load_state_file(path, "upload status"),
+        }
+    }
+
+    pub fn get(&self, scene_name: &str, tracker: &str) -> Option<&StatusRecord> {
+        self.releases.get(scene_name).and_then(|r| r.get(tracker))
+    }
+
+    /// True when this tracker already has a final outcome for the release (uploaded or dupe)
+    /// and, for uploads, its tracker torrent was fetched.
+    pub fn is_done(&self, scene_name: &str, tracker: &str) -> bool {
+        self.get(scene_name, tracker)
+            .is_some_and(|r| r.outcome.is_final() && r.torrent_pending.is_none())
+    }
+
+    /// `(scene name, tracker, record)` of the uploads still waiting for their tracker torrent.
+    pub fn pending_fetches(&self) -> Vec<(String, String, StatusRecord)> {
+        self.releases
+            .iter()
+            .flat_map(|(scene_name, trackers)| {
+                trackers
+                    .iter()
+                    .filter(|(_, r)| r.torrent_pending.is_some())
+                    .map(move |(tracker, r)| (scene_name.clone(), tracker.clone(), r.clone()))
+            })
+            .collect()
+    }
+
+    /// Record `outcome`; `torrent_pending` is the local torrent of an upload whose tracker
+    /// torrent is still to fetch.
+    pub fn record(
+        &mut self,
+        scene_name: &str,
+        tracker: &str,
+        outcome: &UploadOutcome,
+        torrent_pending: Option<&Path>,
+    ) -> Result<(), Error> {
+        self.releases
+            .entry(scene_name.to_string())
+            .or_default()
+            .insert(
+                tracker.to_string(),
+                StatusRecord {
+                    outcome: outcome.clone(),
+                    updated_at: unix_now(),
+                    torrent_pending: torrent_pending.map(Path::to_path_buf),
+                },
+            );
+        self.save()
+    }
+
+    /// Drop every tracker's record of a release. No-op if absent.
+    pub fn forget(&mut self, scene_name: &str) -> Result<(), Error> {
+        if self.releases.remove(scene_name).is_none() {
+            return Ok(());
+        }
+        self.save()
+    }
+
+    /// Mark the tracker torrent of an upload as fetched. No-op if nothing was pending.
+    pub fn torrent_fetched(&mut self, scene_name: &str, tracker: &str) -> Result<(), Error> {
+        let Some(record) = self
+            .releases
+            .get_mut(scene_name)
+            .and_then(|r| r.get_mut(tracker))
+            .filter(|r| r.torrent_pending.is_some())
+        else {
+            return Ok(());
+        };
+        record.torrent_pending = None;
+        record.updated_at = unix_now();
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                Error::Other(format!(
+                    "Failed to create directory '{}': {e}",
+                    parent.display()
+                ))
+            })?;
+        }
+        let json = serde_json::to_vec_pretty(&self.releases)
+            .map_err(|e| Error::Other(format!("Failed to serialize upload status: {e}")))?;
+        // Write then rename so a crash never leaves a truncated file behind.
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Failed to write upload status '{}': {e}",
+                    path.display()
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::upload::UploadedTorrent;
+
+    #[test]
+    fn only_uploaded_and_duplicate_are_final() {
+        let path =
+            std::env::temp_dir().join(format!("seedarr-status-test-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let scene = "Heat.1995.1080p.BluRay.x264-GRP";
+
+        let mut store = UploadStatusStore::load(&path);
+        store
+            .record(
+                scene,
+                "a",
+                &UploadOutcome::Failed {
+                    error: "HTTP 502".to_string(),
+                },
+                None,
+            )
+            .unwrap();
+        store
+            .record(
+                scene,
+                "b",
+                &UploadOutcome::Uploaded(UploadedTorrent::default()),
+                None,
+            )
+            .unwrap();
+        store
+            .record(
+                scene,
+                "c",
+                &UploadOutcome::Duplicate {
+                    existing: "Heat.1995.1080p.BluRay.x265-OTHER".to_string(),
+                },
+                None,
+            )
+            .unwrap();
+
+        let reloaded = UploadStatusStore::load(&path);
+        assert!(!reloaded.is_done(scene, "a"));
+        assert!(reloaded.is_done(scene, "b"));
+        assert!(reloaded.is_done(scene, "c"));
+        assert!(!reloaded.is_done(scene, "d"));
+
+        // An upload is only done once its tracker torrent is fetched
+        store
+            .record(
+                scene,
+                "d",
+                &UploadOutcome::Uploaded(UploadedTorrent::default()),
+                Some(Path::new("/seed/Heat.torrent")),
+            )
+            .unwrap();
+        let mut reloaded = UploadStatusStore::load(&path);
+        assert!(!reloaded.is_done(scene, "d"));
+        let pending = reloaded.pending_fetches();
+        assert_eq!(pending.len(), 1);
+        assert_eq!((pending[0].0.as_str(), pending[0].1.as_str()), (scene, "d"));
+        reloaded.torrent_fetched(scene, "d").unwrap();
+        assert!(UploadStatusStore::load(&path).is_done(scene, "d"));
+
+        reloaded.forget(scene).unwrap();
+        assert!(!UploadStatusStore::load(&path).is_done(scene, "b"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
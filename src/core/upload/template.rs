@@ -0,0 +1,535 @@
+//! Minimal text templates for upload descriptions.
+//!
+//! Syntax:
+//! - `{{ tech.resolution }}`: variable (dotted path; lists of scalars are joined with ", ")
+//! - `{% if overview %}...{% else %}...{% endif %}`, `{% if not cover_url %}`
+//! - `{% for g in genres %}...{% endfor %}`
+//! - `{# comment #}`
+//!
+//! A block or comment tag alone on its line doesn't leave an empty line behind.
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::utils::Error;
+
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Var(VarPath),
+    If {
+        cond: VarPath,
+        negate: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    For {
+        var: String,
+        list: VarPath,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct VarPath {
+    segments: Vec<String>,
+    line: usize,
+}
+
+impl VarPath {
+    fn display(&self) -> String {
+        self.segments.join(".")
+    }
+}
+
+enum Token {
+    Text(String),
+    Expr(String, usize),
+    Stmt(String, usize),
+}
+
+impl Template {
+    /// Read, parse and check a template file against `schema` (a sample context with every
+    /// field present and one element per list).
+    pub fn load(path: &Path, schema: &Value) -> Result<Self, Error> {
+        let src = std::fs::read_to_string(path).map_err(|e| {
+            Error::Other(format!(
+                "Failed to read description template '{}': {e}",
+                path.display()
+            ))
+        })?;
+        Self::parse(&src)
+            .and_then(|t| t.check(schema).map(|_| t))
+            .map_err(|e| {
+                Error::Other(format!(
+                    "Invalid description template '{}': {e}",
+                    path.display()
+                ))
+            })
+    }
+
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let tokens = tokenize(src)?;
+        let mut pos = 0;
+        let (nodes, end) = parse_nodes(&tokens, &mut pos)?;
+        if let Some((kw, line)) = end {
+            return Err(format!("line {line}: unexpected `{{% {kw} %}}`"));
+        }
+        Ok(Self { nodes })
+    }
+
+    /// Report unknown variables and misuse (printing an object, looping over a scalar).
+    pub fn check(&self, schema: &Value) -> Result<(), String> {
+        check_nodes(&self.nodes, schema, &mut Vec::new())
+    }
+
+    /// Whether any tag references the top-level variable `name`.
+    pub fn references(&self, name: &str) -> bool {
+        fn walk(nodes: &[Node], name: &str) -> bool {
+            nodes.iter().any(|n| match n {
+                Node::Text(_) => false,
+                Node::Var(p) => p.segments[0] == name,
+                Node::If {
+                    cond,
+                    then,
+                    otherwise,
+                    ..
+                } => cond.segments[0] == name || walk(then, name) || walk(otherwise, name),
+                Node::For { list, body, .. } => list.segments[0] == name || walk(body, name),
+            })
+        }
+        walk(&self.nodes, name)
+    }
+
+    pub fn render(&self, ctx: &Value) -> String {
+        let mut out = String::new();
+        render_nodes(&self.nodes, ctx, &mut Vec::new(), &mut out);
+        out
+    }
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = src;
+    let mut line = 1;
+    // Whether `rest` starts at the beginning of a line.
+    let mut line_start = true;
+
+    while !rest.is_empty() {
+        let Some(start) = find_open(rest) else {
+            tokens.push(Token::Text(rest.to_string()));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_string()));
+            line += rest[..start].matches('\n').count();
+            line_start = rest[..start].ends_with('\n');
+        }
+        let open = &rest[start..start + 2];
+        let close = match open {
+            "{{" => "}}",
+            "{%" => "%}",
+            _ => "#}",
+        };
+        let inner_start = start + 2;
+        let Some(len) = rest[inner_start..].find(close) else {
+            return Err(format!("line {line}: unclosed `{open}`"));
+        };
+        let inner = rest[inner_start..inner_start + len].trim().to_string();
+        match open {
+            "{{" => tokens.push(Token::Expr(inner.clone(), line)),
+            "{%" => tokens.push(Token::Stmt(inner.clone(), line)),
+            _ => {}
+        }
+        line += rest[inner_start..inner_start + len].matches('\n').count();
+        rest = &rest[inner_start + len + 2..];
+        let alone_on_line = line_start && (rest.is_empty() || rest.starts_with('\n'));
+        line_start = false;
+        if open != "{{" && alone_on_line {
+            if let Some(stripped) = rest.strip_prefix('\n') {
+                rest = stripped;
+                line += 1;
+                line_start = true;
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn find_open(s: &str) -> Option<usize> {
+    ["{{", "{%", "{#"].iter().filter_map(|p| s.find(p)).min()
+}
+
+type BlockEnd = Option<(String, usize)>;
+
+/// Parse until `else`/`endif`/`endfor` (returned to the caller) or end of input.
+fn parse_nodes(tokens: &[Token], pos: &mut usize) -> Result<(Vec<Node>, BlockEnd), String> {
+    let mut nodes = Vec::new();
+
+    while *pos < tokens.len() {
+        let token = &tokens[*pos];
+        *pos += 1;
+        match token {
+            Token::Text(t) => nodes.push(Node::Text(t.clone())),
+            Token::Expr(e, line) => nodes.push(Node::Var(parse_path(e, *line)?)),
+            Token::Stmt(s, line) => {
+                let words: Vec<&str> = s.split_whitespace().collect();
+                match words.as_slice() {
+                    ["if", rest @ ..] => {
+                        let (negate, cond) = match rest {
+                            ["not", p] => (true, *p),
+                            [p] => (false, *p),
+                            _ => {
+                                return Err(format!("line {line}: expected `if [not] <variable>`"))
+                            }
+                        };
+                        let cond = parse_path(cond, *line)?;
+                        let (then, end) = parse_nodes(tokens, pos)?;
+                        let otherwise = match end {
+                            Some((kw, _)) if kw == "endif" => Vec::new(),
+                            Some((kw, else_line)) if kw == "else" => {
+                                let (otherwise, end) = parse_nodes(tokens, pos)?;
+                                expect_end(end, "endif", else_line)?;
+                                otherwise
+                            }
+                            end => {
+                                expect_end(end, "endif", *line)?;
+                                Vec::new()
+                            }
+                        };
+                        nodes.push(Node::If {
+                            cond,
+                            negate,
+                            then,
+                            otherwise,
+                        });
+                    }
+                    ["for", var, "in", list] => {
+                        if !is_ident(var) {
+                            return Err(format!("line {line}: invalid loop variable `{var}`"));
+                        }
+                        let list = parse_path(list, *line)?;
+                        let (body, end) = parse_nodes(tokens, pos)?;
+                        expect_end(end, "endfor", *line)?;
+                        nodes.push(Node::For {
+                            var: var.to_string(),
+                            list,
+                            body,
+                        });
+                    }
+                    ["for", ..] => {
+                        return Err(format!("line {line}: expected `for <name> in <variable>`"))
+                    }
+                    [kw @ ("else" | "endif" | "endfor")] => {
+                        return Ok((nodes, Some((kw.to_string(), *line))))
+                    }
+                    _ => return Err(format!("line {line}: unknown tag `{{% {s} %}}`")),
+                }
+            }
+        }
+    }
+
+    Ok((nodes, None))
+}
+
+fn expect_end(end: BlockEnd, expected: &str, open_line: usize) -> Result<(), String> {
+    match end {
+        Some((kw, _)) if kw == expected => Ok(()),
+        Some((kw, line)) => Err(format!(
+            "line {line}: expected `{{% {expected} %}}`, found `{{% {kw} %}}`"
+        )),
+        None => Err(format!(
+            "line {open_line}: block is never closed (missing `{{% {expected} %}}`)"
+        )),
+    }
+}
+
+fn parse_path(s: &str, line: usize) -> Result<VarPath, String> {
+    let segments: Vec<String> = s.split('.').map(str::to_string).collect();
+    if segments.iter().any(|seg| !is_ident(seg)) {
+        return Err(format!("line {line}: invalid variable `{s}`"));
+    }
+    Ok(VarPath { segments, line })
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+type Scope<'a> = Vec<(String, &'a Value)>;
+
+fn check_nodes<'a>(nodes: &[Node], schema: &'a Value, scope: &mut Scope<'a>) -> Result<(), String> {
+    for node in nodes {
+        match node {
+            Node::Text(_) => {}
+            Node::Var(p) => {
+                let v = resolve_checked(p, schema, scope)?;
+                match v {
+                    Value::Object(map) => {
+                        return Err(format!(
+                            "line {}: `{}` is an object, use one of its fields: {}",
+                            p.line,
+                            p.display(),
+                            keys_of(map)
+                        ))
+                    }
+                    Value::Array(items) if items.iter().any(Value::is_object) => {
+                        return Err(format!(
+                            "line {}: `{}` is a list of objects, iterate it with `{{% for x in {} %}}`",
+                            p.line,
+                            p.display(),
+                            p.display()
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+            Node::If {
+                cond,
+                then,
+                otherwise,
+                ..
+            } => {
+                resolve_checked(cond, schema, scope)?;
+                check_nodes(then, schema, scope)?;
+                check_nodes(otherwise, schema, scope)?;
+            }
+            Node::For { var, list, body } => {
+                let Value::Array(items) = resolve_checked(list, schema, scope)? else {
+                    return Err(format!(
+                        "line {}: `{}` is not a list",
+                        list.line,
+                        list.display()
+                    ));
+                };
+                scope.push((var.clone(), items.first().unwrap_or(&Value::Null)));
+                let res = check_nodes(body, schema, scope);
+                scope.pop();
+                res?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn resolve_checked<'a>(
+    p: &VarPath,
+    root: &'a Value,
+    scope: &Scope<'a>,
+) -> Result<&'a Value, String> {
+    let (mut cur, rest) = match scope.iter().rev().find(|(n, _)| *n == p.segments[0]) {
+        Some((_, v)) => (*v, &p.segments[1..]),
+        None => (root, &p.segments[..]),
+    };
+
+    let mut walked: Vec<&str> = p.segments[..p.segments.len() - rest.len()]
+        .iter()
+        .map(String::as_str)
+        .collect();
+    for seg in rest {
+        let Value::Object(map) = cur else {
+            return Err(format!(
+                "line {}: `{}` has no field `{seg}`",
+                p.line,
+                walked.join(".")
+            ));
+        };
+        let Some(next) = map.get(seg) else {
+            let known: Vec<&str> = map.keys().map(String::as_str).collect();
+            let hint = match closest(seg, &known) {
+                Some(k) => format!("did you mean `{}`?", join_path(&walked, k)),
+                None => format!("available: {}", keys_of(map)),
+            };
+            return Err(format!(
+                "line {}: unknown variable `{}` ({hint})",
+                p.line,
+                p.display()
+            ));
+        };
+        walked.push(seg);
+        cur = next;
+    }
+    Ok(cur)
+}
+
+fn join_path(prefix: &[&str], last: &str) -> String {
+    let mut parts = prefix.to_vec();
+    parts.push(last);
+    parts.join(".")
+}
+
+fn keys_of(map: &serde_json::Map<String, Value>) -> String {
+    map.keys().cloned().collect::<Vec<_>>().join(", ")
+}
+
+/// Closest known name within an edit distance of 2.
+fn closest<'a>(name: &str, known: &[&'a str]) -> Option<&'a str> {
+    known
+        .iter()
+        .map(|k| (levenshtein(name, k), *k))
+        .filter(|(d, _)| *d <= 2)
+        .min()
+        .map(|(_, k)| k)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur.push((prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+fn lookup<'a>(p: &VarPath, root: &'a Value, scope: &Scope<'a>) -> &'a Value {
+    let (mut cur, rest) = match scope.iter().rev().find(|(n, _)| *n == p.segments[0]) {
+        Some((_, v)) => (*v, &p.segments[1..]),
+        None => (root, &p.segments[..]),
+    };
+    for seg in rest {
+        cur = cur.get(seg).unwrap_or(&Value::Null);
+    }
+    cur
+}
+
+fn render_nodes<'a>(nodes: &[Node], ctx: &'a Value, scope: &mut Scope<'a>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(t) => out.push_str(t),
+            Node::Var(p) => out.push_str(&to_text(lookup(p, ctx, scope))),
+            Node::If {
+                cond,
+                negate,
+                then,
+                otherwise,
+            } => {
+                if truthy(lookup(cond, ctx, scope)) != *negate {
+                    render_nodes(then, ctx, scope, out);
+                } else {
+                    render_nodes(otherwise, ctx, scope, out);
+                }
+            }
+            Node::For { var, list, body } => {
+                if let Value::Array(items) = lookup(list, ctx, scope) {
+                    for item in items {
+                        scope.push((var.clone(), item));
+                        render_nodes(body, ctx, scope, out);
+                        scope.pop();
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn to_text(v: &Value) -> String {
+    match v {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(to_text).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+fn truthy(v: &Value) -> bool {
+    match v {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|f| f != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(a) => !a.is_empty(),
+        Value::Object(o) => !o.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "title": "",
+            "overview": null,
+            "genres": [""],
+            "cast": [{ "name": "", "character": null }],
+            "tech": { "resolution": null, "hdr": false },
+        })
+    }
+
+    #[test]
+    fn renders_vars_conditions_and_loops() {
+        let t = Template::parse(
+            "# {{ title }} [{{ tech.resolution }}]\n\
+             {% if overview %}\n{{ overview }}\n{% else %}\nNo synopsis.\n{% endif %}\n\
+             Genres: {{ genres }}\n\
+             {% for c in cast %}\n- {{ c.name }}{% if c.character %} as {{ c.character }}{% endif %}\n{% endfor %}\n",
+        )
+        .unwrap();
+        t.check(&schema()).unwrap();
+
+        let ctx = json!({
+            "title": "Heat",
+            "overview": null,
+            "genres": ["Action", "Crime"],
+            "cast": [
+                { "name": "Al Pacino", "character": "Vincent Hanna" },
+                { "name": "Robert De Niro", "character": null },
+            ],
+            "tech": { "resolution": "1080p", "hdr": false },
+        });
+        assert_eq!(
+            t.render(&ctx),
+            "# Heat [1080p]\nNo synopsis.\nGenres: Action, Crime\n\
+             - Al Pacino as Vincent Hanna\n- Robert De Niro\n"
+        );
+    }
+
+    #[test]
+    fn reports_unknown_variables_with_suggestions() {
+        let err = Template::parse("line one\n{{ tech.resoluton }}")
+            .unwrap()
+            .check(&schema())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            "line 2: unknown variable `tech.resoluton` (did you mean `tech.resolution`?)"
+        );
+
+        let err = Template::parse("{% for c in cast %}{{ c.role }}{% endfor %}")
+            .unwrap()
+            .check(&schema())
+            .unwrap_err();
+        assert!(err.contains("available: character, name"), "{err}");
+
+        let err = Template::parse("{{ tech }}")
+            .unwrap()
+            .check(&schema())
+            .unwrap_err();
+        assert!(err.contains("is an object"), "{err}");
+    }
+
+    #[test]
+    fn rejects_unbalanced_blocks() {
+        assert!(Template::parse("{% if title %}x").is_err());
+        assert!(Template::parse("{% endfor %}").is_err());
+        assert!(Template::parse("{{ title").is_err());
+        assert!(Template::parse("{% if title %}x{% endfor %}").is_err());
+    }
+}
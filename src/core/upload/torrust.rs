@@ -1,12 +1,16 @@
 use async_trait::async_trait;
 use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
 use tracing::{debug, info};
 
+use crate::utils::http::HttpClient;
 use crate::utils::Error;
 
 use crate::config::TorrustUploadConfig;
 
-use super::{ContentKind, TrackerUploader, UploadRequest};
+use super::description::DescriptionFormat;
+use super::dupe::{DupeQuery, ExistingRelease};
+use super::{ContentKind, TrackerUploader, UploadOutcome, UploadRequest, UploadedTorrent};
 
 fn category_for(cfg: &TorrustUploadConfig, kind: ContentKind) -> String {
     match kind {
@@ -32,28 +36,110 @@ fn tags_json_or_default(cfg: &TorrustUploadConfig) -> String {
     }
 }
 
+/// `POST /torrent/upload` response body.
+#[derive(Debug, Deserialize)]
+struct UploadResponse {
+    data: UploadResponseData,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadResponseData {
+    torrent_id: Option<i64>,
+    info_hash: Option<String>,
+    canonical_info_hash: Option<String>,
+}
+
+/// `GET /torrents` response body.
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    data: SearchResponseData,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponseData {
+    #[serde(default)]
+    results: Vec<TorrentListing>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TorrentListing {
+    title: String,
+    info_hash: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct TorrustUploader {
     cfg: TorrustUploadConfig,
-    client: reqwest::Client,
+    http: HttpClient,
 }
 
 impl TorrustUploader {
-    pub fn new(cfg: TorrustUploadConfig) -> Self {
-        Self {
-            cfg,
-            client: reqwest::Client::new(),
-        }
+    pub fn new(cfg: TorrustUploadConfig, http: HttpClient) -> Self {
+        Self { cfg, http }
     }
 
     fn upload_url(&self) -> String {
         format!("{}/torrent/upload", self.cfg.api_base.trim_end_matches('/'))
     }
+
+    fn search_url(&self) -> String {
+        format!("{}/torrents", self.cfg.api_base.trim_end_matches('/'))
+    }
+
+    /// Base URL of the web UI: `web_base`, else `api_base` up to its `/api` segment.
+    fn web_base(&self) -> Option<&str> {
+        if let Some(base) = &self.cfg.web_base {
+            return Some(base.trim_end_matches('/'));
+        }
+        let api = self.cfg.api_base.trim_end_matches('/');
+        api.find("/api/")
+            .or_else(|| api.strip_suffix("/api").map(str::len))
+            .map(|i| &api[..i])
+    }
+
+    /// Page of the torrent in the web UI.
+    fn torrent_page_url(&self, info_hash: &str) -> Option<String> {
+        self.web_base()
+            .map(|base| format!("{base}/torrent/{info_hash}"))
+    }
+
+    fn download_url(&self, info_hash: &str) -> String {
+        format!(
+            "{}/torrent/download/{}",
+            self.cfg.api_base.trim_end_matches('/'),
+            info_hash
+        )
+    }
+
+    fn uploaded_from_body(&self, body: &str) -> UploadedTorrent {
+        let Ok(parsed) = serde_json::from_str::<UploadResponse>(body) else {
+            return UploadedTorrent::default();
+        };
+        // Torrust may rewrite the torrent; the canonical hash is the one it serves.
+        let hash = parsed
+            .data
+            .canonical_info_hash
+            .or(parsed.data.info_hash)
+            .filter(|h| !h.is_empty());
+        UploadedTorrent {
+            torrent_id: parsed.data.torrent_id.map(|id| id.to_string()),
+            url: hash.as_deref().and_then(|h| self.torrent_page_url(h)),
+            download_url: hash.as_deref().map(|h| self.download_url(h)),
+        }
+    }
 }
 
 #[async_trait]
 impl TrackerUploader for TorrustUploader {
-    async fn upload_torrent(&self, req: UploadRequest) -> Result<(), Error> {
+    fn name(&self) -> &str {
+        "torrust"
+    }
+
+    fn description_format(&self) -> DescriptionFormat {
+        self.cfg.description_format
+    }
+
+    async fn upload_torrent(&self, req: UploadRequest) -> Result<UploadOutcome, Error> {
         let title_for_logs = req.title.clone();
 
         let url = self.upload_url();
@@ -76,38 +162,41 @@ impl TrackerUploader for TorrustUploader {
             ))
         })?;
 
-        let torrent_part = Part::bytes(torrent_bytes)
-            .file_name(torrent_file_name)
-            .mime_str("application/x-bittorrent")
-            .map_err(|e| Error::Other(format!("Failed to set torrent mime: {e}")))?;
-
-        let form = Form::new()
-            .text("title", req.title)
-            .text("description", req.description_markdown)
-            .text("category", category)
-            .text("tags", tags_json)
-            .part("torrent", torrent_part);
+        // Multipart bodies can't be cloned: rebuild the form for every attempt.
+        let build_form = || {
+            let torrent_part = Part::bytes(torrent_bytes.clone())
+                .file_name(torrent_file_name.clone())
+                .mime_str("application/x-bittorrent")
+                .expect("static mime type is valid");
+            Form::new()
+                .text("title", req.title.clone())
+                .text("description", req.description.clone())
+                .text("category", category.clone())
+                .text("tags", tags_json.clone())
+                .part("torrent", torrent_part)
+        };
 
         let resp = self
-            .client
-            .post(url)
-            .header("Accept", "application/json")
-            .header("Authorization", format!("ApiKey {}", self.cfg.api_key))
-            .multipart(form)
-            .send()
+            .http
+            .send(&url, |client| {
+                client
+                    .post(&url)
+                    .header("Accept", "application/json")
+                    .header("Authorization", format!("ApiKey {}", self.cfg.api_key))
+                    .multipart(build_form())
+            })
             .await?;
 
         let status = resp.status();
         let body = resp.text().await.unwrap_or_default();
         debug!(target: "seedarr::upload", status = %status, body = %body, "Torrust upload response");
 
-        // Idempotency: if the tracker reports the infohash already exists, treat as success.
+        // The index refuses torrents it already has (same infohash or title)
         if status.as_u16() == 409 {
-            let body_lc = body.to_ascii_lowercase();
-            if body_lc.contains("infohash") && body_lc.contains("already exists") {
-                info!(target: "seedarr::upload", title = %title_for_logs, "Torrent already exists on tracker (409), skipping");
-                return Ok(());
-            }
+            info!(target: "seedarr::upload", title = %title_for_logs, body = %body, "Torrent already exists on tracker (409)");
+            return Ok(UploadOutcome::Duplicate {
+                existing: title_for_logs,
+            });
         }
 
         if !status.is_success() {
@@ -116,6 +205,148 @@ impl TrackerUploader for TorrustUploader {
             )));
         }
 
-        Ok(())
+        Ok(UploadOutcome::Uploaded(self.uploaded_from_body(&body)))
+    }
+
+    async fn download_torrent(&self, uploaded: &UploadedTorrent) -> Result<Vec<u8>, Error> {
+        let Some(url) = uploaded.download_url.as_deref() else {
+            return Err(Error::Other(
+                "Torrust upload has no download link".to_string(),
+            ));
+        };
+
+        debug!(target: "seedarr::upload", url = %url, "Downloading torrent from Torrust");
+        let resp = self
+            .http
+            .send(url, |client| {
+                client
+                    .get(url)
+                    .header("Accept", "application/x-bittorrent")
+                    .header("Authorization", format!("ApiKey {}", self.cfg.api_key))
+            })
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::Other(format!(
+                "Torrust download failed: HTTP {status} body={body}"
+            )));
+        }
+
+        Ok(resp.bytes().await?.to_vec())
+    }
+
+    async fn search_existing(
+        &self,
+        query: &DupeQuery,
+    ) -> Result<Option<Vec<ExistingRelease>>, Error> {
+        // Torrust only searches titles (no TMDB/IMDB index).
+        let category = category_for(&self.cfg, query.kind);
+        let url = self.search_url();
+        debug!(target: "seedarr::upload", url = %url, search = %query.title, "Searching Torrust for existing releases");
+
+        let resp = self
+            .http
+            .send(&url, |client| {
+                client
+                    .get(&url)
+                    .header("Accept", "application/json")
+                    .header("Authorization", format!("ApiKey {}", self.cfg.api_key))
+                    .query(&[
+                        ("search", query.title.as_str()),
+                        ("categories", category.as_str()),
+                        ("page_size", "100"),
+                    ])
+            })
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::Other(format!(
+                "Torrust search failed: HTTP {status} body={body}"
+            )));
+        }
+
+        let parsed = resp.json::<SearchResponse>().await?;
+        let found = parsed
+            .data
+            .results
+            .into_iter()
+            .map(|t| ExistingRelease {
+                url: t
+                    .info_hash
+                    .as_deref()
+                    .and_then(|h| self.torrent_page_url(h)),
+                name: t.title,
+            })
+            .collect();
+        Ok(Some(found))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uploader() -> TorrustUploader {
+        let http = HttpClient::from_config(&Default::default()).unwrap();
+        TorrustUploader::new(
+            TorrustUploadConfig {
+                enable: true,
+                api_base: "https://index.example/api/v1/".to_string(),
+                api_key: "key".to_string(),
+                web_base: None,
+                movies_category: None,
+                series_category: None,
+                animes_category: None,
+                tags: None,
+                description_format: DescriptionFormat::Markdown,
+                description_template: None,
+                title_strategy: None,
+                title_locale: None,
+            },
+            http,
+        )
+    }
+
+    #[test]
+    fn parses_upload_response_with_canonical_hash() {
+        let body = r#"{"data":{"torrent_id":42,"info_hash":"aaaa","canonical_info_hash":"bbbb"}}"#;
+        let uploaded = uploader().uploaded_from_body(body);
+        assert_eq!(uploaded.torrent_id.as_deref(), Some("42"));
+        assert_eq!(
+            uploaded.url.as_deref(),
+            Some("https://index.example/torrent/bbbb")
+        );
+        assert_eq!(
+            uploaded.download_url.as_deref(),
+            Some("https://index.example/api/v1/torrent/download/bbbb")
+        );
+    }
+
+    #[test]
+    fn unparsable_body_yields_empty_result() {
+        let uploaded = uploader().uploaded_from_body("not json");
+        assert_eq!(uploaded, UploadedTorrent::default());
+    }
+
+    #[test]
+    fn page_links_point_to_the_web_ui() {
+        let mut torrust = uploader();
+        assert_eq!(
+            torrust.torrent_page_url("cc").as_deref(),
+            Some("https://index.example/torrent/cc")
+        );
+
+        torrust.cfg.api_base = "https://api.index.example/v1".to_string();
+        assert_eq!(torrust.torrent_page_url("cc"), None);
+
+        torrust.cfg.web_base = Some("https://index.example/".to_string());
+        assert_eq!(
+            torrust.torrent_page_url("cc").as_deref(),
+            Some("https://index.example/torrent/cc")
+        );
     }
 }
@@ -6,24 +6,47 @@ mod utils;
 #[dotenvy::load(path = "./.env", required = true)]
 #[tokio::main]
 async fn main() {
+    let command = match app::cli::parse_args(std::env::args().skip(1)) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{e}\n{}", app::cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
     let config = config::Config::init().expect("Failed to initialize configuration");
     app::common::init_logging(&config);
     if let Err(e) = app::common::ensure_seed_path(&config) {
         tracing::error!("{e}");
         return;
     }
+    if let Err(e) = config
+        .naming
+        .active_profile()
+        .and_then(|p| core::naming::rules::check_profile(&p))
+    {
+        tracing::error!("Naming configuration error: {e}");
+        return;
+    }
 
-    let radarr_config = app::radarr::build_radarr_config(&config);
-    let movies = match app::radarr::fetch_radarr_movies(&radarr_config, config.test_mode).await {
-        Ok(m) => m,
+    let http = match utils::http::HttpClient::from_config(&config.http) {
+        Ok(h) => h,
         Err(e) => {
-            tracing::error!("Failed to list movies from Radarr: {e}");
+            tracing::error!("HTTP configuration error: {e}");
             return;
         }
     };
 
+    if let app::cli::Command::Explain { target, json } = &command {
+        if let Err(e) = app::explain::run(target, *json, &config, &http).await {
+            tracing::error!("explain failed: {e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // Optional: upload service (private tracker uploads). Keep main tracker-agnostic.
-    let upload_service = match core::upload::UploadService::from_config(&config) {
+    let upload_service = match core::upload::UploadService::from_config(&config, &http) {
         Ok(svc) => svc,
         Err(e) => {
             tracing::error!("Upload configuration error: {e}");
@@ -31,11 +54,16 @@ async fn main() {
         }
     };
 
-    for movie in movies {
-        app::radarr::process_movie(movie, &config, &upload_service).await;
-    }
-
-    if let Err(e) = app::sonarr::run_sonarr_pipeline(&config, &upload_service).await {
-        tracing::error!("Sonarr pipeline failed: {e}");
+    match command {
+        app::cli::Command::RetryUploads { include_flagged } => {
+            upload_service.retry_queued(include_flagged).await;
+        }
+        app::cli::Command::Run => {
+            let tools = app::common::Tools::from_config(&config);
+            if let Err(e) = app::run(&config, &http, &tools, &upload_service).await {
+                tracing::error!("{e}");
+            }
+        }
+        app::cli::Command::Explain { .. } => unreachable!("handled above"),
     }
 }
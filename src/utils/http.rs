@@ -0,0 +1,383 @@
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+use tracing::{debug, warn};
+
+use crate::config::HttpConfig;
+
+use super::Error;
+
+/// Shared HTTP layer: one `reqwest::Client` with timeouts, retries with exponential backoff
+/// (honoring `Retry-After`) and per-host rate limits. Cheap to clone.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    retry: RetryPolicy,
+    limiter: Arc<RateLimiter>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    max_retries: u32,
+    backoff_base: Duration,
+    backoff_max: Duration,
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `attempt` (1-based): base * 2^(attempt-1), capped.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.backoff_base
+            .saturating_mul(factor)
+            .min(self.backoff_max)
+    }
+}
+
+/// Whether a failed attempt may be retried, and after how long the server asked us to wait.
+pub enum Retry {
+    No,
+    After(Option<Duration>),
+}
+
+impl HttpClient {
+    pub fn from_config(cfg: &HttpConfig) -> Result<Self, Error> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(cfg.timeout_secs))
+            .connect_timeout(Duration::from_secs(cfg.connect_timeout_secs))
+            .build()?;
+
+        let mut limits = HashMap::new();
+        for rl in &cfg.rate_limits {
+            if rl.max_requests == 0 || rl.per_secs == 0 {
+                return Err(Error::Other(format!(
+                    "http.rate_limits for '{}': max_requests and per_secs must be > 0",
+                    rl.host
+                )));
+            }
+            limits.insert(
+                rl.host.to_ascii_lowercase(),
+                (rl.max_requests, Duration::from_secs(rl.per_secs)),
+            );
+        }
+
+        Ok(Self {
+            client,
+            retry: RetryPolicy {
+                max_retries: cfg.max_retries,
+                backoff_base: Duration::from_millis(cfg.backoff_base_ms),
+                backoff_max: Duration::from_millis(cfg.backoff_max_ms),
+            },
+            limiter: Arc::new(RateLimiter {
+                limits,
+                history: Mutex::new(HashMap::new()),
+            }),
+        })
+    }
+
+    /// Underlying client (timeouts only), for generated API clients that take a `reqwest::Client`.
+    pub fn inner(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Send a request built by `build`, retrying on connection errors, timeouts, 429 and 5xx.
+    /// Non-idempotent requests (uploads) are only retried when the server can't have acted on
+    /// them: connection errors and 429.
+    ///
+    /// The builder is called once per attempt since request bodies (multipart) can't be cloned.
+    /// Non-retryable HTTP errors are returned as a `Response` for the caller to inspect.
+    pub async fn send<F>(&self, url: &str, build: F) -> Result<Response, Error>
+    where
+        F: Fn(&reqwest::Client) -> reqwest::RequestBuilder,
+    {
+        let idempotent = AtomicBool::new(true);
+        let resp = self
+            .retry(
+                url,
+                || {
+                    let request = build(&self.client).build();
+                    if let Ok(r) = &request {
+                        idempotent.store(r.method().is_idempotent(), Ordering::Relaxed);
+                    }
+                    async { self.client.execute(request?).await }
+                },
+                |res: &Result<Response, reqwest::Error>| {
+                    let retry = match res {
+                        Ok(resp) => classify_status(resp.status(), retry_after(resp)),
+                        Err(e) => classify_reqwest_error(e),
+                    };
+                    if idempotent.load(Ordering::Relaxed) || was_not_processed(res) {
+                        retry
+                    } else {
+                        Retry::No
+                    }
+                },
+            )
+            .await?;
+        Ok(resp)
+    }
+
+    /// Run `op` under the rate limit of `url`'s host, retrying while `classify` allows it.
+    ///
+    /// Used directly for API clients we don't build requests for (e.g. Radarr's generated client).
+    pub async fn retry<T, Fut, Op, C>(&self, url: &str, op: Op, classify: C) -> T
+    where
+        Op: Fn() -> Fut,
+        Fut: Future<Output = T>,
+        C: Fn(&T) -> Retry,
+    {
+        let host = host_of(url);
+        let mut attempt = 0u32;
+        loop {
+            self.limiter.acquire(&host).await;
+            let out = op().await;
+
+            let Retry::After(server_delay) = classify(&out) else {
+                return out;
+            };
+            if attempt >= self.retry.max_retries {
+                warn!(target: "seedarr::http", host = %host, attempts = attempt + 1, "Giving up after retries");
+                return out;
+            }
+            attempt += 1;
+            // Retry-After is capped too: a server must not stall the whole run
+            let delay = server_delay
+                .map(|d| d.min(self.retry.backoff_max))
+                .unwrap_or_else(|| self.retry.backoff(attempt));
+            debug!(target: "seedarr::http", host = %host, attempt, delay_ms = delay.as_millis() as u64, "Retrying request");
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Retry policy for an HTTP status.
+pub fn classify_status(status: StatusCode, retry_after: Option<Duration>) -> Retry {
+    if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+        Retry::After(retry_after)
+    } else {
+        Retry::No
+    }
+}
+
+/// Retry policy for a transport-level error.
+pub fn classify_reqwest_error(e: &reqwest::Error) -> Retry {
+    if let Some(status) = e.status() {
+        return classify_status(status, None);
+    }
+    if e.is_connect() || e.is_timeout() || e.is_request() {
+        Retry::After(None)
+    } else {
+        Retry::No
+    }
+}
+
+/// Whether a failed attempt certainly left no effect on the server (safe to repeat a POST).
+fn was_not_processed(res: &Result<Response, reqwest::Error>) -> bool {
+    match res {
+        Ok(resp) => resp.status() == StatusCode::TOO_MANY_REQUESTS,
+        Err(e) => e.is_connect() || e.status() == Some(StatusCode::TOO_MANY_REQUESTS),
+    }
+}
+
+/// Parse a delta-seconds `Retry-After` header (HTTP-date values fall back to backoff).
+fn retry_after(resp: &Response) -> Option<Duration> {
+    resp.headers()
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+fn host_of(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_ascii_lowercase()))
+        .unwrap_or_default()
+}
+
+/// Sliding-window limiter: at most N requests per window, per host.
+#[derive(Debug)]
+struct RateLimiter {
+    limits: HashMap<String, (u32, Duration)>,
+    history: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl RateLimiter {
+    async fn acquire(&self, host: &str) {
+        let Some(&(max, window)) = self.limits.get(host) else {
+            return;
+        };
+        loop {
+            let wait = {
+                let mut history = self.history.lock().unwrap();
+                let sent = history.entry(host.to_string()).or_default();
+                let now = Instant::now();
+                while sent
+                    .front()
+                    .is_some_and(|t| now.duration_since(*t) >= window)
+                {
+                    sent.pop_front();
+                }
+                if (sent.len() as u32) < max {
+                    sent.push_back(now);
+                    return;
+                }
+                window - now.duration_since(*sent.front().unwrap())
+            };
+            debug!(target: "seedarr::http", host = %host, wait_ms = wait.as_millis() as u64, "Rate limit reached, waiting");
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RateLimitConfig;
+    use std::sync::atomic::AtomicU32;
+
+    fn client(max_retries: u32, rate_limits: Vec<RateLimitConfig>) -> HttpClient {
+        HttpClient::from_config(&HttpConfig {
+            max_retries,
+            backoff_base_ms: 1,
+            backoff_max_ms: 5,
+            rate_limits,
+            ..HttpConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let p = RetryPolicy {
+            max_retries: 5,
+            backoff_base: Duration::from_millis(100),
+            backoff_max: Duration::from_millis(500),
+        };
+        assert_eq!(p.backoff(1), Duration::from_millis(100));
+        assert_eq!(p.backoff(2), Duration::from_millis(200));
+        assert_eq!(p.backoff(3), Duration::from_millis(400));
+        assert_eq!(p.backoff(4), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn retries_until_success_or_limit() {
+        let http = client(2, vec![]);
+        let calls = AtomicU32::new(0);
+        let out = http
+            .retry(
+                "http://radarr.local/api",
+                || async { calls.fetch_add(1, Ordering::SeqCst) },
+                |n: &u32| {
+                    if *n < 1 {
+                        Retry::After(None)
+                    } else {
+                        Retry::No
+                    }
+                },
+            )
+            .await;
+        assert_eq!(out, 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let calls = AtomicU32::new(0);
+        http.retry(
+            "http://radarr.local/api",
+            || async { calls.fetch_add(1, Ordering::SeqCst) },
+            |_: &u32| Retry::After(Some(Duration::from_millis(1))),
+        )
+        .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_after_is_capped_by_backoff_max() {
+        let http = client(1, vec![]);
+        let start = Instant::now();
+        http.retry(
+            "http://radarr.local/api",
+            || async {},
+            |_: &()| Retry::After(Some(Duration::from_secs(3600))),
+        )
+        .await;
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    /// Local server answering 503 to every request; returns its URL and request counter.
+    async fn unavailable_server() -> (String, Arc<AtomicU32>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let count = Arc::new(AtomicU32::new(0));
+        let seen = count.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                seen.fetch_add(1, Ordering::SeqCst);
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(b"HTTP/1.1 503 Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .await;
+            }
+        });
+        (url, count)
+    }
+
+    #[tokio::test]
+    async fn server_errors_retry_gets_but_not_posts() {
+        let http = client(2, vec![]);
+
+        let (url, count) = unavailable_server().await;
+        let resp = http.send(&url, |c| c.get(&url)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+
+        let (url, count) = unavailable_server().await;
+        http.send(&url, |c| c.post(&url).body("upload"))
+            .await
+            .unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_delays_requests_over_quota() {
+        let http = client(
+            0,
+            vec![RateLimitConfig {
+                host: "tracker.example".to_string(),
+                max_requests: 2,
+                per_secs: 1,
+            }],
+        );
+        let start = Instant::now();
+        for _ in 0..3 {
+            http.retry(
+                "https://tracker.example/upload",
+                || async {},
+                |_: &()| Retry::No,
+            )
+            .await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(900));
+    }
+
+    #[test]
+    fn classifies_statuses() {
+        assert!(matches!(
+            classify_status(StatusCode::SERVICE_UNAVAILABLE, None),
+            Retry::After(None)
+        ));
+        assert!(matches!(
+            classify_status(StatusCode::TOO_MANY_REQUESTS, Some(Duration::from_secs(3))),
+            Retry::After(Some(d)) if d == Duration::from_secs(3)
+        ));
+        assert!(matches!(
+            classify_status(StatusCode::CONFLICT, None),
+            Retry::No
+        ));
+    }
+}
@@ -1,4 +1,5 @@
 mod errors;
+pub mod http;
 pub mod pathmap;
 
 pub use errors::Error;